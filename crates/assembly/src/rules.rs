@@ -1,6 +1,183 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use std::sync::LazyLock;
+use regex::Regex;
+use thiserror::Error;
+
 #[derive(Debug, PartialEq)]
 pub struct AssemblyRule {
-    pub ref_des: String,
+    pub ref_des: RefDesPattern,
     pub manufacturer: String,
     pub mpn: String,
+    /// The name of the assembly variant the rule applies to, `None` for all assembly variants.
+    pub assembly_variant: Option<String>,
+}
+
+impl AssemblyRule {
+    pub fn applies_to_variant(&self, assembly_variant_name: &str) -> bool {
+        match &self.assembly_variant {
+            Some(name) => name.eq(assembly_variant_name),
+            None => true,
+        }
+    }
+}
+
+/// `C1` matches a single ref_des, `C1-C40` matches a range of ref_des with the same prefix and `/<regex>/` is a
+/// regular expression, e.g. `/^R1\d$/`.
+///
+/// When the patterns of several rules match the same ref_des the most specific pattern takes precedence, exact over
+/// range over regular expression, see `RefDesPattern::precedence`.
+#[derive(Debug)]
+pub enum RefDesPattern {
+    Exact(String),
+    Range { prefix: String, start: u32, end: u32 },
+    Regex(Regex),
+}
+
+#[derive(Error, Debug)]
+pub enum RefDesPatternError {
+    #[error("Invalid regular expression. reason: {error:?}")]
+    InvalidRegex { error: regex::Error },
+
+    #[error("Invalid range. value: {value:?}")]
+    InvalidRange { value: String },
+}
+
+impl RefDesPattern {
+    pub fn matches(&self, ref_des: &str) -> bool {
+        match self {
+            RefDesPattern::Exact(value) => value.eq(ref_des),
+            RefDesPattern::Range { prefix, start, end } => ref_des.strip_prefix(prefix.as_str())
+                .filter(|number| !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()))
+                .and_then(|number| u32::from_str(number).ok())
+                .is_some_and(|number| (*start..=*end).contains(&number)),
+            RefDesPattern::Regex(regex) => regex.is_match(ref_des),
+        }
+    }
+
+    /// A lower value takes precedence.
+    pub fn precedence(&self) -> u8 {
+        match self {
+            RefDesPattern::Exact(_) => 0,
+            RefDesPattern::Range { .. } => 1,
+            RefDesPattern::Regex(_) => 2,
+        }
+    }
+}
+
+/// e.g. `C1-C40`
+static RANGE_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^([A-Za-z_]+)(\d+)-([A-Za-z_]+)(\d+)$").unwrap());
+
+impl FromStr for RefDesPattern {
+    type Err = RefDesPatternError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if let Some(pattern) = value.strip_prefix('/').and_then(|value| value.strip_suffix('/')) {
+            let regex = Regex::new(pattern)
+                .map_err(|error| RefDesPatternError::InvalidRegex { error })?;
+
+            return Ok(RefDesPattern::Regex(regex))
+        }
+
+        if let Some(captures) = RANGE_REGEX.captures(value) {
+            let invalid_range = || RefDesPatternError::InvalidRange { value: value.to_string() };
+
+            let (start_prefix, end_prefix) = (&captures[1], &captures[3]);
+            let start = u32::from_str(&captures[2]).map_err(|_| invalid_range())?;
+            let end = u32::from_str(&captures[4]).map_err(|_| invalid_range())?;
+
+            if start_prefix.ne(end_prefix) || start > end {
+                return Err(invalid_range())
+            }
+
+            return Ok(RefDesPattern::Range { prefix: start_prefix.to_string(), start, end })
+        }
+
+        Ok(RefDesPattern::Exact(value.to_string()))
+    }
+}
+
+impl Display for RefDesPattern {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RefDesPattern::Exact(value) => write!(f, "{}", value),
+            RefDesPattern::Range { prefix, start, end } => write!(f, "{}{}-{}{}", prefix, start, prefix, end),
+            RefDesPattern::Regex(regex) => write!(f, "/{}/", regex),
+        }
+    }
+}
+
+// regular expressions are compared using their patterns
+impl PartialEq for RefDesPattern {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (RefDesPattern::Exact(value), RefDesPattern::Exact(other_value)) => value.eq(other_value),
+            (
+                RefDesPattern::Range { prefix, start, end },
+                RefDesPattern::Range { prefix: other_prefix, start: other_start, end: other_end },
+            ) => prefix.eq(other_prefix) && start.eq(other_start) && end.eq(other_end),
+            (RefDesPattern::Regex(regex), RefDesPattern::Regex(other_regex)) => regex.as_str().eq(other_regex.as_str()),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod rules_tests {
+    use std::str::FromStr;
+    use rstest::rstest;
+    use crate::rules::{AssemblyRule, RefDesPattern};
+
+    #[rstest]
+    #[case("C1", "C1", true)]
+    #[case("C1", "C10", false)]
+    #[case("C1-C40", "C1", true)]
+    #[case("C1-C40", "C40", true)]
+    #[case("C1-C40", "C41", false)]
+    #[case("C1-C40", "CN1", false)]
+    #[case("C1-C40", "C", false)]
+    #[case("/^R1\\d$/", "R12", true)]
+    #[case("/^R1\\d$/", "R1", false)]
+    fn matches(#[case] pattern: &str, #[case] ref_des: &str, #[case] expected_result: bool) {
+        // given
+        let pattern = RefDesPattern::from_str(pattern).unwrap();
+
+        // when
+        let result = pattern.matches(ref_des);
+
+        // then
+        assert_eq!(result, expected_result);
+    }
+
+    #[rstest]
+    #[case("C40-C1")]
+    #[case("C1-R40")]
+    #[case("/(/")]
+    fn invalid_pattern(#[case] pattern: &str) {
+        // when
+        let result = RefDesPattern::from_str(pattern);
+
+        // then
+        assert!(result.is_err());
+    }
+
+    #[rstest]
+    #[case(None, true)]
+    #[case(Some("Variant_1"), true)]
+    #[case(Some("Variant_2"), false)]
+    fn applies_to_variant(#[case] assembly_variant: Option<&str>, #[case] expected_result: bool) {
+        // given
+        let rule = AssemblyRule {
+            ref_des: RefDesPattern::Exact("R1".to_string()),
+            manufacturer: "MFR1".to_string(),
+            mpn: "PART1".to_string(),
+            assembly_variant: assembly_variant.map(str::to_string),
+        };
+
+        // when
+        let result = rule.applies_to_variant("Variant_1");
+
+        // then
+        assert_eq!(result, expected_result);
+    }
 }
@@ -1,5 +1,7 @@
 use clap::ValueEnum;
 use eda::EdaTool;
+use eda::profile::EdaProfile;
+use eda::units::LengthUnit;
 use pnp::pcb::{PcbKind, PcbSide};
 use util::sorting::SortOrder;
 use planning::placement::{PlacementOperation, PlacementSortingMode};
@@ -82,14 +84,35 @@ pub enum EdaToolArg {
     DipTrace,
     #[value(name("kicad"))]
     KiCad,
+    #[value(name("altium"))]
+    Altium,
+    #[value(name("easyeda"))]
+    EasyEda,
+    #[value(name("eagle"))]
+    Eagle,
+    #[value(name("ipc2581"))]
+    Ipc2581,
+    #[value(name("gerberx3"))]
+    GerberX3,
+    #[value(name("profile"))]
+    Profile,
 }
 
 impl EdaToolArg {
-    pub fn build(&self) -> EdaTool {
-        match self {
+    /// The profile is only used, and required, for `EdaToolArg::Profile`.
+    pub fn build(&self, eda_profile: Option<EdaProfile>) -> anyhow::Result<EdaTool> {
+        let eda_tool = match self {
             EdaToolArg::DipTrace => EdaTool::DipTrace,
             EdaToolArg::KiCad => EdaTool::KiCad,
-        }
+            EdaToolArg::Altium => EdaTool::Altium,
+            EdaToolArg::EasyEda => EdaTool::EasyEda,
+            EdaToolArg::Eagle => EdaTool::Eagle,
+            EdaToolArg::Ipc2581 => EdaTool::Ipc2581,
+            EdaToolArg::GerberX3 => EdaTool::GerberX3,
+            EdaToolArg::Profile => EdaTool::Profile(Box::new(eda_profile.ok_or(anyhow::anyhow!("An EDA profile is required"))?)),
+        };
+
+        Ok(eda_tool)
     }
 }
 
@@ -162,4 +185,23 @@ impl From<ProcessOperationSetArg> for ProcessOperationSetItem {
             ProcessOperationSetArg::Completed => ProcessOperationSetItem::Completed
         }
     }
-}
\ No newline at end of file
+}
+#[derive(ValueEnum, Clone, Debug)]
+pub enum LengthUnitArg {
+    #[value(name("mm"))]
+    Millimeters,
+    #[value(name("mil"))]
+    Mils,
+    #[value(name("in"))]
+    Inches,
+}
+
+impl From<&LengthUnitArg> for LengthUnit {
+    fn from(value: &LengthUnitArg) -> Self {
+        match value {
+            LengthUnitArg::Millimeters => Self::Millimeters,
+            LengthUnitArg::Mils => Self::Mils,
+            LengthUnitArg::Inches => Self::Inches,
+        }
+    }
+}
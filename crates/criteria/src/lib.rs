@@ -1,7 +1,9 @@
 use std::fmt::{Debug, Display, Formatter};
+use std::str::FromStr;
 use regex::Regex;
 use util::dynamic::as_any::AsAny;
 use util::dynamic::dynamic_eq::DynamicEq;
+use util::quantity::{Quantity, QuantityRange};
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ExactMatchCriterion {
@@ -98,6 +100,112 @@ mod regex_match_criterion_tests {
     }
 }
 
+/// Matches values that are equivalent quantities, e.g. a pattern of `10k` matches `10K0`, `10000` and `10kΩ`.
+///
+/// Values that are not quantities do not match.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct QuantityMatchCriterion {
+    pub field_name: String,
+    pub quantity: Quantity,
+}
+
+impl QuantityMatchCriterion {
+    pub fn new(field_name: String, quantity: Quantity) -> Self {
+        Self {
+            field_name,
+            quantity
+        }
+    }
+}
+
+impl Display for QuantityMatchCriterion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}_pattern: '[{}]'", self.field_name, self.quantity)
+    }
+}
+
+impl FieldCriterion for QuantityMatchCriterion {
+    fn matches(&self, name: &str, value: &str) -> bool {
+        self.field_name.eq(name) &&
+            Quantity::from_str(value).is_ok_and(|quantity| self.quantity.is_equivalent(&quantity))
+    }
+}
+
+#[cfg(test)]
+mod quantity_match_criterion_tests {
+    use std::str::FromStr;
+    use rstest::rstest;
+    use util::quantity::Quantity;
+    use crate::{FieldCriterion, QuantityMatchCriterion};
+
+    #[rstest]
+    #[case("10K0", true)]
+    #[case("10000", true)]
+    #[case("10kΩ", true)]
+    #[case("10kF", false)]
+    #[case("4k7", false)]
+    #[case("10k 1%", false)]
+    pub fn matches(#[case] value: &str, #[case] expected_result: bool) {
+        // given
+        let criterion = QuantityMatchCriterion { field_name: "value".to_string(), quantity: Quantity::from_str("10kΩ").unwrap() };
+
+        // expect
+        assert_eq!(criterion.matches("value", value), expected_result)
+    }
+}
+
+/// Matches values that are quantities within a range, e.g. `9.9k..10.1k`, `10k±1%` or `<=1.2mm`.
+///
+/// Values that are not quantities do not match.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct QuantityRangeCriterion {
+    pub field_name: String,
+    pub range: QuantityRange,
+}
+
+impl QuantityRangeCriterion {
+    pub fn new(field_name: String, range: QuantityRange) -> Self {
+        Self {
+            field_name,
+            range
+        }
+    }
+}
+
+impl Display for QuantityRangeCriterion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}_pattern: '[{}]'", self.field_name, self.range)
+    }
+}
+
+impl FieldCriterion for QuantityRangeCriterion {
+    fn matches(&self, name: &str, value: &str) -> bool {
+        self.field_name.eq(name) &&
+            Quantity::from_str(value).is_ok_and(|quantity| self.range.contains(&quantity))
+    }
+}
+
+#[cfg(test)]
+mod quantity_range_criterion_tests {
+    use std::str::FromStr;
+    use rstest::rstest;
+    use util::quantity::QuantityRange;
+    use crate::{FieldCriterion, QuantityRangeCriterion};
+
+    #[rstest]
+    #[case("10K", true)]
+    #[case("9k95", true)]
+    #[case("11k", false)]
+    #[case("RES_0402", false)]
+    pub fn matches(#[case] value: &str, #[case] expected_result: bool) {
+        // given
+        let criterion = QuantityRangeCriterion { field_name: "value".to_string(), range: QuantityRange::from_str("9.9k..10.1k").unwrap() };
+
+        // expect
+        assert_eq!(criterion.matches("value", value), expected_result)
+    }
+}
+
 impl PartialEq for dyn FieldCriterion
 {
     fn eq(&self, other: &Self) -> bool {
@@ -107,6 +215,140 @@ impl PartialEq for dyn FieldCriterion
 
 pub trait FieldCriterion: Display + Debug + AsAny + DynamicEq {
     fn matches(&self, name: &str, value: &str) -> bool;
+
+    /// Matches when any of the fields, (name, value) pairs, matches.
+    ///
+    /// Composite criteria override this so that they can combine criteria for different fields.
+    fn matches_fields(&self, fields: &[(&str, &str)]) -> bool {
+        fields.iter().any(|(name, value)| self.matches(name, value))
+    }
+}
+
+/// Matches when any of the criteria match.
+#[derive(Debug, PartialEq)]
+pub struct AnyOfCriterion {
+    pub criteria: Vec<Box<dyn FieldCriterion>>,
+}
+
+impl Display for AnyOfCriterion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "any({})", format_criteria(&self.criteria))
+    }
+}
+
+impl FieldCriterion for AnyOfCriterion {
+    fn matches(&self, name: &str, value: &str) -> bool {
+        self.criteria.iter().any(|criterion| criterion.matches(name, value))
+    }
+
+    fn matches_fields(&self, fields: &[(&str, &str)]) -> bool {
+        self.criteria.iter().any(|criterion| criterion.matches_fields(fields))
+    }
+}
+
+/// Matches when all the criteria match, an empty list of criteria does not match.
+#[derive(Debug, PartialEq)]
+pub struct AllOfCriterion {
+    pub criteria: Vec<Box<dyn FieldCriterion>>,
+}
+
+impl Display for AllOfCriterion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "all({})", format_criteria(&self.criteria))
+    }
+}
+
+impl FieldCriterion for AllOfCriterion {
+    fn matches(&self, name: &str, value: &str) -> bool {
+        !self.criteria.is_empty() && self.criteria.iter().all(|criterion| criterion.matches(name, value))
+    }
+
+    fn matches_fields(&self, fields: &[(&str, &str)]) -> bool {
+        !self.criteria.is_empty() && self.criteria.iter().all(|criterion| criterion.matches_fields(fields))
+    }
+}
+
+/// Matches when the criterion does not match, including when the field is not present.
+#[derive(Debug)]
+pub struct NotCriterion {
+    pub criterion: Box<dyn FieldCriterion>,
+}
+
+impl PartialEq for NotCriterion {
+    fn eq(&self, other: &Self) -> bool {
+        self.criterion.as_ref().eq(other.criterion.as_ref())
+    }
+}
+
+impl Display for NotCriterion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "not({})", self.criterion)
+    }
+}
+
+impl FieldCriterion for NotCriterion {
+    fn matches(&self, name: &str, value: &str) -> bool {
+        !self.criterion.matches(name, value)
+    }
+
+    fn matches_fields(&self, fields: &[(&str, &str)]) -> bool {
+        !self.criterion.matches_fields(fields)
+    }
+}
+
+fn format_criteria(criteria: &[Box<dyn FieldCriterion>]) -> String {
+    criteria.iter().map(|criterion| criterion.to_string()).collect::<Vec<_>>().join(", ")
+}
+
+#[cfg(test)]
+mod composite_criterion_tests {
+    use crate::{AllOfCriterion, AnyOfCriterion, ExactMatchCriterion, FieldCriterion, NotCriterion};
+
+    fn exact(field_name: &str, field_pattern: &str) -> Box<dyn FieldCriterion> {
+        Box::new(ExactMatchCriterion::new(field_name.to_string(), field_pattern.to_string()))
+    }
+
+    #[test]
+    pub fn any_of() {
+        // given
+        let criterion = AnyOfCriterion { criteria: vec![exact("package", "0402"), exact("package", "0603")] };
+
+        // expect
+        assert!(criterion.matches_fields(&[("package", "0603"), ("value", "10k")]));
+        assert!(!criterion.matches_fields(&[("package", "0805"), ("value", "10k")]));
+    }
+
+    #[test]
+    pub fn all_of_different_fields() {
+        // given
+        let criterion = AllOfCriterion { criteria: vec![exact("package", "0402"), exact("value", "10k")] };
+
+        // expect
+        assert!(criterion.matches_fields(&[("package", "0402"), ("value", "10k")]));
+        assert!(!criterion.matches_fields(&[("package", "0402"), ("value", "1k")]));
+    }
+
+    #[test]
+    pub fn not() {
+        // given
+        let criterion = NotCriterion { criterion: exact("value", "DNP") };
+
+        // expect
+        assert!(criterion.matches_fields(&[("package", "0402"), ("value", "10k")]));
+        assert!(!criterion.matches_fields(&[("package", "0402"), ("value", "DNP")]));
+    }
+
+    #[test]
+    pub fn display() {
+        // given
+        let criterion = AnyOfCriterion { criteria: vec![
+            exact("package", "0402"),
+            Box::new(NotCriterion { criterion: exact("package", "0603") }),
+        ] };
+
+        // expect
+        assert_eq!(criterion.to_string(), "any(package_pattern: '0402', not(package_pattern: '0603'))");
+    }
 }
 
 #[derive(Debug, PartialEq)]
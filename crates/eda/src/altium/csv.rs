@@ -0,0 +1,171 @@
+use std::str::FromStr;
+use rust_decimal::Decimal;
+use thiserror::Error;
+use pnp::pcb::PcbSide;
+use crate::placement::{EdaPlacement, EdaPlacementField};
+use crate::rotation::RotationConverter;
+use crate::units::LengthUnit;
+
+#[derive(Debug, serde::Deserialize)]
+pub struct AltiumPlacementRecord {
+    #[serde(rename = "Designator")]
+    ref_des: String,
+    #[serde(rename = "Comment")]
+    comment: String,
+    #[serde(rename = "Layer")]
+    layer: AltiumPcbSide,
+    #[serde(rename = "Footprint")]
+    footprint: String,
+    /// The unit suffix is stripped from headers when loading, see `units::split_header_unit`.
+    #[serde(rename = "Center-X", alias = "Center-X(mm)")]
+    x: Decimal,
+    #[serde(rename = "Center-Y", alias = "Center-Y(mm)")]
+    y: Decimal,
+    /// Positive values indicate anti-clockwise rotation
+    /// Range is 0 - < 360
+    #[serde(rename = "Rotation")]
+    rotation: Decimal,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
+enum AltiumPcbSide {
+    #[serde(alias = "T")]
+    TopLayer,
+    #[serde(alias = "B")]
+    BottomLayer,
+}
+
+impl From<&AltiumPcbSide> for PcbSide {
+    fn from(value: &AltiumPcbSide) -> Self {
+        match value {
+            AltiumPcbSide::TopLayer => PcbSide::Top,
+            AltiumPcbSide::BottomLayer => PcbSide::Bottom,
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum AltiumPlacementRecordError {
+    #[error("Unknown")]
+    Unknown
+}
+
+impl AltiumPlacementRecord {
+    /// Columns used by the record, or that are other reference points of the component, other columns are carried
+    /// as additional placement fields when loading.
+    pub const STANDARD_COLUMNS: &'static [&'static str] = &["Designator", "Comment", "Layer", "Footprint", "Center-X", "Center-Y", "Center-X(mm)", "Center-Y(mm)", "Ref-X", "Ref-Y", "Pad-X", "Pad-Y", "Rotation"];
+
+    pub fn build_eda_placement(&self) -> Result<EdaPlacement, AltiumPlacementRecordError> {
+        Ok(EdaPlacement {
+            ref_des: self.ref_des.to_string(),
+            place: true,
+            disable_reason: None,
+            fields: vec![
+                EdaPlacementField { name: "comment".to_string(), value: self.comment.to_string() },
+                EdaPlacementField { name: "footprint".to_string(), value: self.footprint.to_string() },
+            ],
+            pcb_side: PcbSide::from(&self.layer),
+            x: self.x,
+            y: self.y,
+            rotation: RotationConverter::convert(self.rotation),
+        })
+
+        // _ => Err(AltiumPlacementRecordError::Unknown)
+    }
+}
+
+/// Altium pick-and-place files start with a preamble (report name, file name, date, units, etc.) before the CSV header.
+///
+/// Returns the content starting from the header line, i.e. the line that begins with the 'Designator' column.
+pub fn strip_preamble(content: &str) -> &str {
+    let mut offset = 0;
+    for line in content.split_inclusive('\n') {
+        if line.trim_start().trim_start_matches('"').starts_with("Designator") {
+            return &content[offset..]
+        }
+        offset += line.len();
+    }
+
+    content
+}
+
+/// e.g. `Units used: mil`
+pub fn detect_preamble_units(content: &str) -> Option<LengthUnit> {
+    content.lines()
+        .find_map(|line| line.trim().strip_prefix("Units used:"))
+        .and_then(|unit| LengthUnit::from_str(unit).ok())
+}
+
+#[cfg(test)]
+mod altium_csv_tests {
+    use indoc::indoc;
+    use rust_decimal_macros::dec;
+    use pnp::pcb::PcbSide;
+    use crate::altium::csv::{detect_preamble_units, strip_preamble, AltiumPlacementRecord};
+    use crate::units::LengthUnit;
+    use crate::placement::{EdaPlacement, EdaPlacementField};
+
+    #[test]
+    fn preamble_and_records() {
+        // given
+        let content = indoc! {r#"
+            Altium Designer Pick and Place Locations
+            C:\Projects\Board\Project Outputs for Board\Pick Place for Board.csv
+
+            ========================================================================================================================
+            File Design Information:
+
+            Date:       01/08/24
+            Time:       12:00
+            Revision:   Not in VersionControl
+            Variant:    No variations
+            Units used: mm
+
+            "Designator","Comment","Layer","Footprint","Center-X(mm)","Center-Y(mm)","Rotation","Description"
+            "R1","10k","TopLayer","RESC1005X40N","10.0000","110.0000","270","Resistor"
+            "C1","100nF","BottomLayer","CAPC1608X90N","20.5000","30.2500","0","Capacitor"
+        "#};
+
+        // and
+        let expected_result = vec![
+            EdaPlacement {
+                ref_des: "R1".to_string(),
+                place: true,
+                disable_reason: None,
+                fields: vec![
+                    EdaPlacementField::new("comment".to_string(), "10k".to_string()),
+                    EdaPlacementField::new("footprint".to_string(), "RESC1005X40N".to_string()),
+                ],
+                pcb_side: PcbSide::Top,
+                x: dec!(10),
+                y: dec!(110),
+                rotation: dec!(-90),
+            },
+            EdaPlacement {
+                ref_des: "C1".to_string(),
+                place: true,
+                disable_reason: None,
+                fields: vec![
+                    EdaPlacementField::new("comment".to_string(), "100nF".to_string()),
+                    EdaPlacementField::new("footprint".to_string(), "CAPC1608X90N".to_string()),
+                ],
+                pcb_side: PcbSide::Bottom,
+                x: dec!(20.5),
+                y: dec!(30.25),
+                rotation: dec!(0),
+            },
+        ];
+
+        // when
+        let mut csv_reader = csv::ReaderBuilder::new().from_reader(strip_preamble(content).as_bytes());
+        let result: Vec<EdaPlacement> = csv_reader.deserialize()
+            .map(|record: Result<AltiumPlacementRecord, csv::Error>| record.unwrap().build_eda_placement().unwrap())
+            .collect();
+
+        // then
+        assert_eq!(result, expected_result);
+
+        // and
+        assert_eq!(detect_preamble_units(content), Some(LengthUnit::Millimeters));
+    }
+}
@@ -1,8 +1,7 @@
-use std::ops::{Add, Sub};
 use rust_decimal::Decimal;
-use rust_decimal_macros::dec;
 use thiserror::Error;
 use crate::placement::{EdaPlacement, EdaPlacementField};
+use crate::rotation::RotationConverter;
 use pnp::pcb::PcbSide;
 
 // TODO add tests for aliases
@@ -14,9 +13,10 @@ pub struct DiptracePlacementRecord {
     name: String,
     value: String,
     side: DipTracePcbSide,
-    #[serde(alias = "Center X (mm)")]
+    /// The unit suffix is stripped from headers when loading, see `units::split_header_unit`.
+    #[serde(alias = "Center X (mm)", alias = "Center X")]
     x: Decimal,
-    #[serde(alias = "Center Y (mm)")]
+    #[serde(alias = "Center Y (mm)", alias = "Center Y")]
     y: Decimal,
     /// Positive values indicate anti-clockwise rotation
     /// Range is 0 - < 360
@@ -47,10 +47,14 @@ pub enum DiptracePlacementRecordError {
 }
 
 impl DiptracePlacementRecord {
+    /// Columns used by the record, other columns are carried as additional placement fields when loading.
+    pub const STANDARD_COLUMNS: &'static [&'static str] = &["RefDes", "Name", "Value", "Side", "X", "Y", "Center X", "Center Y", "Center X (mm)", "Center Y (mm)", "Rotation"];
+
     pub fn build_eda_placement(&self) -> Result<EdaPlacement, DiptracePlacementRecordError> {
         Ok(EdaPlacement {
             ref_des: self.ref_des.to_string(),
             place: true,
+            disable_reason: None,
             fields: vec![
                 EdaPlacementField { name: "name".to_string(), value: self.name.to_string() },
                 EdaPlacementField { name: "value".to_string(), value: self.value.to_string() },
@@ -58,45 +62,9 @@ impl DiptracePlacementRecord {
             pcb_side: PcbSide::from(&self.side),
             x: self.x,
             y: self.y,
-            rotation: DipTraceRotationConverter::convert(self.rotation),
+            rotation: RotationConverter::convert(self.rotation),
         })
 
         // _ => Err(DiptracePlacementRecordError::Unknown)
     }
 }
-
-struct DipTraceRotationConverter {}
-impl DipTraceRotationConverter {
-    pub fn convert(mut input: Decimal) -> Decimal {
-        while input >= dec!(360) {
-            input = input.sub(dec!(360));
-        }
-        while input < dec!(0) {
-            input = input.add( dec!(360));
-        }
-        if input > dec!(180) {
-            input = input.sub(dec!(360));
-        }
-        input
-    }
-}
-
-#[cfg(test)]
-mod rotation_conversion_tests {
-
-    use rstest::rstest;
-    use rust_decimal::Decimal;
-    use rust_decimal_macros::dec;
-    use crate::diptrace::csv::DipTraceRotationConverter;
-
-    #[rstest]
-    #[case(dec!(0), dec!(0))]
-    #[case(dec!(180), dec!(180))]
-    #[case(dec!(-180), dec!(180))]
-    #[case(dec!(360), dec!(0))]
-    #[case(dec!(185), dec!(-175))]
-    #[case(dec!(-185), dec!(175))]
-    fn diptrace_to_eda_placement(#[case] value: Decimal, #[case] expected_value: Decimal) {
-        assert_eq!(DipTraceRotationConverter::convert(value), expected_value);
-    }
-}
@@ -0,0 +1,170 @@
+use std::str::FromStr;
+use rust_decimal::Decimal;
+use thiserror::Error;
+use pnp::pcb::PcbSide;
+use crate::placement::{EdaPlacement, EdaPlacementField};
+use crate::rotation::RotationConverter;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum EagleMountError {
+    #[error("Invalid line. line: {line}, reason: {reason}")]
+    InvalidLine { line: usize, reason: String },
+}
+
+/// A line from an Eagle / Fusion 360 `mountsmd.ulp` centroid file, e.g.
+///
+/// ```text
+/// R1 10.16 20.32 90 10k R0603
+/// ```
+///
+/// Columns are whitespace separated: name, x, y, rotation, value, package.
+/// The `.mnt` file contains the top side placements and the `.mnb` file contains the bottom side placements.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EagleMountRecord {
+    ref_des: String,
+    x: Decimal,
+    y: Decimal,
+    /// Positive values indicate anti-clockwise rotation
+    /// Range is 0 - < 360
+    rotation: Decimal,
+    value: String,
+    package: String,
+    side: PcbSide,
+}
+
+impl EagleMountRecord {
+    pub fn build_eda_placement(&self) -> Result<EdaPlacement, EagleMountError> {
+        Ok(EdaPlacement {
+            ref_des: self.ref_des.to_string(),
+            place: true,
+            disable_reason: None,
+            fields: vec![
+                EdaPlacementField { name: "value".to_string(), value: self.value.to_string() },
+                EdaPlacementField { name: "package".to_string(), value: self.package.to_string() },
+            ],
+            pcb_side: self.side.clone(),
+            x: self.x,
+            y: self.y,
+            rotation: RotationConverter::convert(self.rotation),
+        })
+    }
+}
+
+/// The side is not present in the file content, it is determined by which file the content came from.
+///
+/// Values may contain whitespace, the package is always the last column.
+pub fn parse_mount_file(content: &str, side: PcbSide) -> Result<Vec<EagleMountRecord>, EagleMountError> {
+    let mut records = vec![];
+
+    for (index, line) in content.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.trim();
+
+        if line.is_empty() {
+            continue
+        }
+
+        let invalid_line = |reason: String| EagleMountError::InvalidLine { line: line_number, reason };
+
+        let columns: Vec<&str> = line.split_whitespace().collect();
+        let (ref_des, x, y, rotation, value, package) = match columns[..] {
+            [ref_des, x, y, rotation, package] => (ref_des, x, y, rotation, "".to_string(), package),
+            [ref_des, x, y, rotation, ref value @ .., package] => (ref_des, x, y, rotation, value.join(" "), package),
+            _ => return Err(invalid_line(format!("expected at least 5 columns, found {}", columns.len()))),
+        };
+
+        let parse_decimal = |value: &str| Decimal::from_str(value)
+            .map_err(|_err| invalid_line(format!("invalid number '{}'", value)));
+
+        records.push(EagleMountRecord {
+            ref_des: ref_des.to_string(),
+            x: parse_decimal(x)?,
+            y: parse_decimal(y)?,
+            rotation: parse_decimal(rotation)?,
+            value,
+            package: package.to_string(),
+            side: side.clone(),
+        });
+    }
+
+    Ok(records)
+}
+
+#[cfg(test)]
+mod eagle_mount_tests {
+    use indoc::indoc;
+    use rust_decimal_macros::dec;
+    use pnp::pcb::PcbSide;
+    use crate::eagle::mnt::{parse_mount_file, EagleMountError};
+    use crate::placement::{EdaPlacement, EdaPlacementField};
+
+    #[test]
+    fn bottom_side() {
+        // given
+        let content = indoc! {"
+            R1  10.16  20.32  270  10k R0603
+            C1   5.00   6.50    0  100nF 16V C0402
+            TP1  1.00   2.00    0  TP1X1
+        "};
+
+        // and
+        let expected_result = vec![
+            EdaPlacement {
+                ref_des: "R1".to_string(),
+                place: true,
+                disable_reason: None,
+                fields: vec![
+                    EdaPlacementField::new("value".to_string(), "10k".to_string()),
+                    EdaPlacementField::new("package".to_string(), "R0603".to_string()),
+                ],
+                pcb_side: PcbSide::Bottom,
+                x: dec!(10.16),
+                y: dec!(20.32),
+                rotation: dec!(-90),
+            },
+            EdaPlacement {
+                ref_des: "C1".to_string(),
+                place: true,
+                disable_reason: None,
+                fields: vec![
+                    EdaPlacementField::new("value".to_string(), "100nF 16V".to_string()),
+                    EdaPlacementField::new("package".to_string(), "C0402".to_string()),
+                ],
+                pcb_side: PcbSide::Bottom,
+                x: dec!(5),
+                y: dec!(6.5),
+                rotation: dec!(0),
+            },
+            EdaPlacement {
+                ref_des: "TP1".to_string(),
+                place: true,
+                disable_reason: None,
+                fields: vec![
+                    EdaPlacementField::new("value".to_string(), "".to_string()),
+                    EdaPlacementField::new("package".to_string(), "TP1X1".to_string()),
+                ],
+                pcb_side: PcbSide::Bottom,
+                x: dec!(1),
+                y: dec!(2),
+                rotation: dec!(0),
+            },
+        ];
+
+        // when
+        let result: Vec<EdaPlacement> = parse_mount_file(content, PcbSide::Bottom).unwrap().iter()
+            .map(|record| record.build_eda_placement().unwrap())
+            .collect();
+
+        // then
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn invalid_line() {
+        // when
+        let result = parse_mount_file("R1 10.16 20.32 R0603\n", PcbSide::Top);
+
+        // then
+        assert_eq!(result, Err(EagleMountError::InvalidLine { line: 1, reason: "expected at least 5 columns, found 4".to_string() }));
+    }
+}
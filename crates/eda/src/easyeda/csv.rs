@@ -0,0 +1,228 @@
+use std::str::FromStr;
+use rust_decimal::Decimal;
+use serde::Deserializer;
+use thiserror::Error;
+use pnp::pcb::PcbSide;
+use crate::placement::{EdaPlacement, EdaPlacementField};
+use crate::rotation::RotationConverter;
+use crate::units::LengthUnit;
+
+/// EasyEDA / JLCPCB 'CPL' (component placement list) record.
+///
+/// `Designator,Footprint,Mid X,Mid Y,Ref X,Ref Y,Pad X,Pad Y,Layer,Rotation,Comment`
+///
+/// Coordinates have a unit suffix, e.g. `12.7mm` or `500mil`, and are converted to millimeters.
+/// The component center ('Mid X', 'Mid Y') is used for the placement position.
+#[derive(Debug, serde::Deserialize)]
+pub struct EasyEdaPlacementRecord {
+    #[serde(rename = "Designator")]
+    ref_des: String,
+    #[serde(rename = "Footprint")]
+    footprint: String,
+    #[serde(rename = "Mid X", deserialize_with = "deserialize_coordinate")]
+    x: Decimal,
+    #[serde(rename = "Mid Y", deserialize_with = "deserialize_coordinate")]
+    y: Decimal,
+    #[serde(rename = "Layer")]
+    layer: EasyEdaPcbSide,
+    /// Positive values indicate anti-clockwise rotation
+    /// Range is 0 - < 360
+    #[serde(rename = "Rotation")]
+    rotation: Decimal,
+    #[serde(rename = "Comment")]
+    comment: String,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
+enum EasyEdaPcbSide {
+    #[serde(rename = "T", alias = "Top", alias = "TopLayer")]
+    Top,
+    #[serde(rename = "B", alias = "Bottom", alias = "BottomLayer")]
+    Bottom,
+}
+
+impl From<&EasyEdaPcbSide> for PcbSide {
+    fn from(value: &EasyEdaPcbSide) -> Self {
+        match value {
+            EasyEdaPcbSide::Top => PcbSide::Top,
+            EasyEdaPcbSide::Bottom => PcbSide::Bottom,
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum EasyEdaPlacementRecordError {
+    #[error("Unknown")]
+    Unknown
+}
+
+impl EasyEdaPlacementRecord {
+    /// Columns used by the record, or that are other reference points of the component, other columns are carried
+    /// as additional placement fields when loading.
+    pub const STANDARD_COLUMNS: &'static [&'static str] = &["Designator", "Footprint", "Mid X", "Mid Y", "Ref X", "Ref Y", "Pad X", "Pad Y", "Layer", "Rotation", "Comment"];
+
+    pub fn build_eda_placement(&self) -> Result<EdaPlacement, EasyEdaPlacementRecordError> {
+        Ok(EdaPlacement {
+            ref_des: self.ref_des.to_string(),
+            place: true,
+            disable_reason: None,
+            fields: vec![
+                EdaPlacementField { name: "footprint".to_string(), value: self.footprint.to_string() },
+                EdaPlacementField { name: "comment".to_string(), value: self.comment.to_string() },
+            ],
+            pcb_side: PcbSide::from(&self.layer),
+            x: self.x,
+            y: self.y,
+            rotation: RotationConverter::convert(self.rotation),
+        })
+
+        // _ => Err(EasyEdaPlacementRecordError::Unknown)
+    }
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum EasyEdaCoordinateError {
+    #[error("Invalid coordinate. value: '{0}'")]
+    Invalid(String),
+}
+
+/// Parses a coordinate with an optional unit suffix, returns the value in millimeters.
+///
+/// Values without a suffix are assumed to be in millimeters.
+pub fn parse_coordinate(value: &str) -> Result<Decimal, EasyEdaCoordinateError> {
+    let value = value.trim();
+    let (number, unit) = if let Some(number) = value.strip_suffix("mm") {
+        (number, LengthUnit::Millimeters)
+    } else if let Some(number) = value.strip_suffix("mil") {
+        (number, LengthUnit::Mils)
+    } else {
+        (value, LengthUnit::Millimeters)
+    };
+
+    Decimal::from_str(number.trim())
+        .map(|number| unit.to_millimeters(number))
+        .map_err(|_err| EasyEdaCoordinateError::Invalid(value.to_string()))
+}
+
+fn deserialize_coordinate<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Decimal, D::Error> {
+    let value: String = serde::Deserialize::deserialize(deserializer)?;
+    parse_coordinate(&value).map_err(serde::de::Error::custom)
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum EasyEdaEncodingError {
+    #[error("Invalid UTF-16 content")]
+    InvalidUtf16,
+
+    #[error("Invalid UTF-8 content")]
+    InvalidUtf8,
+}
+
+/// EasyEDA Std exports UTF-16LE files with a byte order mark, other files are UTF-8, with or without a byte order mark.
+pub fn decode_content(bytes: &[u8]) -> Result<String, EasyEdaEncodingError> {
+    let decode_utf16 = |bytes: &[u8], from_bytes: fn([u8; 2]) -> u16| {
+        if !bytes.len().is_multiple_of(2) {
+            return Err(EasyEdaEncodingError::InvalidUtf16)
+        }
+        let units = bytes.chunks_exact(2).map(|chunk| from_bytes([chunk[0], chunk[1]]));
+        char::decode_utf16(units)
+            .collect::<Result<String, _>>()
+            .map_err(|_err| EasyEdaEncodingError::InvalidUtf16)
+    };
+
+    match bytes {
+        [0xFF, 0xFE, rest @ ..] => decode_utf16(rest, u16::from_le_bytes),
+        [0xFE, 0xFF, rest @ ..] => decode_utf16(rest, u16::from_be_bytes),
+        [0xEF, 0xBB, 0xBF, rest @ ..] => String::from_utf8(rest.to_vec()).map_err(|_err| EasyEdaEncodingError::InvalidUtf8),
+        _ => String::from_utf8(bytes.to_vec()).map_err(|_err| EasyEdaEncodingError::InvalidUtf8),
+    }
+}
+
+/// EasyEDA Std exports tab separated files, EasyEDA Pro and hand edited files are usually comma separated.
+pub fn detect_delimiter(content: &str) -> u8 {
+    match content.lines().next() {
+        Some(header) if header.contains('\t') => b'\t',
+        _ => b',',
+    }
+}
+
+#[cfg(test)]
+mod easyeda_csv_tests {
+    use indoc::indoc;
+    use rstest::rstest;
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+    use pnp::pcb::PcbSide;
+    use crate::easyeda::csv::{decode_content, detect_delimiter, parse_coordinate, EasyEdaEncodingError, EasyEdaPlacementRecord};
+    use crate::placement::{EdaPlacement, EdaPlacementField};
+
+    #[rstest]
+    #[case("12.7mm", dec!(12.7))]
+    #[case("-500mil", dec!(-12.7))]
+    #[case("3", dec!(3))]
+    fn coordinates(#[case] value: &str, #[case] expected_value: Decimal) {
+        assert_eq!(parse_coordinate(value), Ok(expected_value));
+    }
+
+    #[test]
+    fn records() {
+        // given
+        let content = indoc! {"
+            Designator\tFootprint\tMid X\tMid Y\tRef X\tRef Y\tPad X\tPad Y\tLayer\tRotation\tComment
+            R1\t0402\t500mil\t4000mil\t500mil\t4000mil\t480mil\t4000mil\tT\t270\t10k
+            C1\tC0603\t20.5mm\t30.25mm\t20.5mm\t30.25mm\t19.7mm\t30.25mm\tB\t0\t100nF
+        "};
+
+        // and
+        let expected_result = vec![
+            EdaPlacement {
+                ref_des: "R1".to_string(),
+                place: true,
+                disable_reason: None,
+                fields: vec![
+                    EdaPlacementField::new("footprint".to_string(), "0402".to_string()),
+                    EdaPlacementField::new("comment".to_string(), "10k".to_string()),
+                ],
+                pcb_side: PcbSide::Top,
+                x: dec!(12.7),
+                y: dec!(101.6),
+                rotation: dec!(-90),
+            },
+            EdaPlacement {
+                ref_des: "C1".to_string(),
+                place: true,
+                disable_reason: None,
+                fields: vec![
+                    EdaPlacementField::new("footprint".to_string(), "C0603".to_string()),
+                    EdaPlacementField::new("comment".to_string(), "100nF".to_string()),
+                ],
+                pcb_side: PcbSide::Bottom,
+                x: dec!(20.5),
+                y: dec!(30.25),
+                rotation: dec!(0),
+            },
+        ];
+
+        // when
+        let mut csv_reader = csv::ReaderBuilder::new()
+            .delimiter(detect_delimiter(content))
+            .from_reader(content.as_bytes());
+        let result: Vec<EdaPlacement> = csv_reader.deserialize()
+            .map(|record: Result<EasyEdaPlacementRecord, csv::Error>| record.unwrap().build_eda_placement().unwrap())
+            .collect();
+
+        // then
+        assert_eq!(result, expected_result);
+    }
+
+    #[rstest]
+    #[case(vec![0xFF, 0xFE, b'R', 0x00, b'1', 0x00], Ok("R1".to_string()))]
+    #[case(vec![0xFE, 0xFF, 0x00, b'R', 0x00, b'1'], Ok("R1".to_string()))]
+    #[case(vec![0xEF, 0xBB, 0xBF, b'R', b'1'], Ok("R1".to_string()))]
+    #[case(vec![b'R', b'1'], Ok("R1".to_string()))]
+    #[case(vec![0xFF, 0xFE, b'R'], Err(EasyEdaEncodingError::InvalidUtf16))]
+    #[case(vec![0xFF, 0xFF], Err(EasyEdaEncodingError::InvalidUtf8))]
+    fn encodings(#[case] bytes: Vec<u8>, #[case] expected_result: Result<String, EasyEdaEncodingError>) {
+        assert_eq!(decode_content(&bytes), expected_result);
+    }
+}
@@ -0,0 +1,386 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+use rust_decimal::Decimal;
+use thiserror::Error;
+use pnp::pcb::PcbSide;
+use crate::placement::{EdaPlacement, EdaPlacementField};
+use crate::rotation::RotationConverter;
+use crate::units::LengthUnit;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum GerberX3Error {
+    #[error("Not a component layer. file function: '{0}'")]
+    NotAComponentLayer(String),
+    #[error("Missing file function, expected '%TF.FileFunction,Component,<layer>,<Top|Bot>*%'")]
+    MissingFileFunction,
+    #[error("Missing or unsupported format specification, expected '%FSLAX<i><d>Y<i><d>*%'")]
+    UnsupportedFormat,
+    #[error("Invalid statement. statement: '{statement}', reason: {reason}")]
+    InvalidStatement { statement: String, reason: String },
+}
+
+/// A component from a Gerber X3 component layer.
+///
+/// Each component is a flash of a 'ComponentMain' aperture at the component's reference point, the
+/// component is described by the object attributes that are in effect at the time of the flash, e.g.
+///
+/// ```text
+/// %TO.C,R1*%
+/// %TO.CRot,90*%
+/// %TO.CMfr,Yageo*%
+/// %TO.CMPN,RC0402FR-0710KL*%
+/// %TO.CVal,10k*%
+/// %TO.CFtp,R_0402_1005Metric*%
+/// X10000000Y20000000D03*
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct GerberX3Component {
+    ref_des: String,
+    manufacturer: String,
+    mpn: String,
+    value: String,
+    footprint: String,
+    side: PcbSide,
+    /// Millimeters
+    x: Decimal,
+    /// Millimeters
+    y: Decimal,
+    /// Positive values indicate anti-clockwise rotation
+    rotation: Decimal,
+}
+
+impl GerberX3Component {
+    pub fn build_eda_placement(&self) -> Result<EdaPlacement, GerberX3Error> {
+        Ok(EdaPlacement {
+            ref_des: self.ref_des.to_string(),
+            place: true,
+            disable_reason: None,
+            fields: vec![
+                EdaPlacementField { name: "manufacturer".to_string(), value: self.manufacturer.to_string() },
+                EdaPlacementField { name: "mpn".to_string(), value: self.mpn.to_string() },
+                EdaPlacementField { name: "value".to_string(), value: self.value.to_string() },
+                EdaPlacementField { name: "footprint".to_string(), value: self.footprint.to_string() },
+            ],
+            pcb_side: self.side.clone(),
+            x: self.x,
+            y: self.y,
+            rotation: RotationConverter::convert(self.rotation),
+        })
+    }
+}
+
+/// The side is determined by the file function, e.g. `%TF.FileFunction,Component,L1,Top*%`.
+///
+/// Only flashes of 'ComponentMain' apertures are components, flashes of other apertures (e.g. 'ComponentPin') are ignored.
+pub fn parse_component_layer(content: &str) -> Result<Vec<GerberX3Component>, GerberX3Error> {
+    let mut side: Option<PcbSide> = None;
+    let mut units = LengthUnit::Millimeters;
+    let mut decimals: Option<(u32, u32)> = None;
+
+    let mut aperture_attributes: HashMap<String, String> = HashMap::new();
+    let mut aperture_functions: HashMap<String, String> = HashMap::new();
+    let mut object_attributes: HashMap<String, String> = HashMap::new();
+
+    let mut current_aperture: Option<String> = None;
+    let mut position = (Decimal::ZERO, Decimal::ZERO);
+
+    let mut components = vec![];
+
+    for statement in split_statements(content) {
+        let invalid_statement = |reason: &str| GerberX3Error::InvalidStatement { statement: statement.text.to_string(), reason: reason.to_string() };
+
+        if statement.extended {
+            let text = statement.text;
+            if let Some(file_attribute) = text.strip_prefix("TF.") {
+                let mut values = file_attribute.split(',');
+                if values.next() == Some("FileFunction") {
+                    let function: Vec<&str> = values.collect();
+                    side = match function[..] {
+                        ["Component", _layer, "Top"] => Some(PcbSide::Top),
+                        ["Component", _layer, "Bot"] => Some(PcbSide::Bottom),
+                        _ => return Err(GerberX3Error::NotAComponentLayer(function.join(","))),
+                    };
+                }
+            } else if let Some(format) = text.strip_prefix("FS") {
+                decimals = parse_format(format);
+                if decimals.is_none() {
+                    return Err(GerberX3Error::UnsupportedFormat)
+                }
+            } else if text == "MOMM" {
+                units = LengthUnit::Millimeters;
+            } else if text == "MOIN" {
+                units = LengthUnit::Inches;
+            } else if let Some(attribute) = text.strip_prefix("TA") {
+                let (name, value) = split_attribute(attribute);
+                aperture_attributes.insert(name, value);
+            } else if let Some(attribute) = text.strip_prefix("TO") {
+                let (name, value) = split_attribute(attribute);
+                object_attributes.insert(name, value);
+            } else if let Some(name) = text.strip_prefix("TD") {
+                if name.is_empty() {
+                    aperture_attributes.clear();
+                    object_attributes.clear();
+                } else {
+                    aperture_attributes.remove(name);
+                    object_attributes.remove(name);
+                }
+            } else if let Some(definition) = text.strip_prefix("AD") {
+                let aperture: String = definition.chars().take_while(|c| c.eq(&'D') || c.is_ascii_digit()).collect();
+                if let Some(function) = aperture_attributes.get(".AperFunction") {
+                    aperture_functions.insert(aperture, function.clone());
+                }
+            }
+            continue
+        }
+
+        let text = statement.text.trim_start_matches("G54");
+        if text.starts_with("G04") || text.starts_with("M02") || text.is_empty() {
+            continue
+        }
+
+        // mode settings, e.g. 'G01', 'G75'
+        if text.starts_with('G') && !text.contains('D') {
+            continue
+        }
+
+        if text.starts_with('D') && text[1..].chars().all(|c| c.is_ascii_digit()) {
+            current_aperture = Some(text.to_string());
+            continue
+        }
+
+        let (x, y, operation) = parse_operation(text).ok_or_else(|| invalid_statement("unsupported operation"))?;
+
+        let (integer_digits, decimal_digits) = decimals.ok_or(GerberX3Error::UnsupportedFormat)?;
+        let to_millimeters = |value: &str| -> Result<Decimal, GerberX3Error> {
+            let digits = value.trim_start_matches(['+', '-']).len() as u32;
+            if digits > integer_digits + decimal_digits {
+                return Err(invalid_statement("too many digits in coordinate"))
+            }
+            let value = i64::from_str(value).map_err(|_err| invalid_statement("invalid coordinate"))?;
+            Ok(units.to_millimeters(Decimal::new(value, decimal_digits)))
+        };
+
+        if let Some(x) = x {
+            position.0 = to_millimeters(x)?;
+        }
+        if let Some(y) = y {
+            position.1 = to_millimeters(y)?;
+        }
+
+        let is_component_main = current_aperture.as_ref()
+            .and_then(|aperture| aperture_functions.get(aperture))
+            .is_some_and(|function| function.eq("ComponentMain"));
+
+        if operation != 3 || !is_component_main {
+            continue
+        }
+
+        let side = side.clone().ok_or(GerberX3Error::MissingFileFunction)?;
+        let ref_des = object_attributes.get(".C").ok_or_else(|| invalid_statement("flash without '.C' attribute"))?;
+
+        let attribute = |name: &str| object_attributes.get(name).cloned().unwrap_or_default();
+
+        let rotation = match object_attributes.get(".CRot") {
+            Some(rotation) => Decimal::from_str(rotation).map_err(|_err| invalid_statement("invalid '.CRot' attribute"))?,
+            None => Decimal::ZERO,
+        };
+
+        components.push(GerberX3Component {
+            ref_des: ref_des.clone(),
+            manufacturer: attribute(".CMfr"),
+            mpn: attribute(".CMPN"),
+            value: attribute(".CVal"),
+            footprint: attribute(".CFtp"),
+            side,
+            x: position.0,
+            y: position.1,
+            rotation,
+        });
+    }
+
+    Ok(components)
+}
+
+struct Statement<'content> {
+    text: &'content str,
+    /// `%...*%` statements
+    extended: bool,
+}
+
+/// Splits the content into '*' terminated statements; an extended command block may contain multiple statements.
+fn split_statements(content: &str) -> Vec<Statement<'_>> {
+    let mut statements = vec![];
+
+    for (index, block) in content.split('%').enumerate() {
+        let extended = index % 2 == 1;
+        statements.extend(block.split('*')
+            .map(|text| text.trim())
+            .filter(|text| !text.is_empty())
+            .map(|text| Statement { text, extended }));
+    }
+
+    statements
+}
+
+/// e.g. `LAX46Y46`, only leading zero omission and absolute coordinates are supported.
+fn parse_format(format: &str) -> Option<(u32, u32)> {
+    let digits = format.strip_prefix("LAX")?;
+    let (x, y) = digits.split_once('Y')?;
+    if x != y || x.len() != 2 {
+        return None
+    }
+    let mut digits = x.chars().map(|c| c.to_digit(10));
+
+    Some((digits.next()??, digits.next()??))
+}
+
+/// e.g. `.CRot,90` -> (`.CRot`, `90`)
+fn split_attribute(attribute: &str) -> (String, String) {
+    match attribute.split_once(',') {
+        Some((name, value)) => (name.to_string(), value.to_string()),
+        None => (attribute.to_string(), "".to_string()),
+    }
+}
+
+/// e.g. `X10000000Y-20000000D03` -> (Some("10000000"), Some("-20000000"), 3)
+fn parse_operation(text: &str) -> Option<(Option<&str>, Option<&str>, u32)> {
+    let text = text.strip_prefix("G01").or(text.strip_prefix("G1")).unwrap_or(text);
+    let (coordinates, operation) = text.rsplit_once('D')?;
+    let operation = u32::from_str(operation).ok().filter(|operation| (1..=3).contains(operation))?;
+
+    let (x, y) = match coordinates.split_once('Y') {
+        Some((x, y)) => (x, Some(y)),
+        None => (coordinates, None),
+    };
+    let x = match x {
+        "" => None,
+        x => Some(x.strip_prefix('X')?),
+    };
+
+    Some((x, y, operation))
+}
+
+#[cfg(test)]
+mod gerber_x3_tests {
+    use indoc::indoc;
+    use rust_decimal_macros::dec;
+    use pnp::pcb::PcbSide;
+    use crate::gerber::x3::{parse_component_layer, GerberX3Error};
+    use crate::placement::{EdaPlacement, EdaPlacementField};
+
+    #[test]
+    fn bottom_component_layer() {
+        // given
+        let content = indoc! {"
+            %TF.GenerationSoftware,KiCad,Pcbnew,8.0.4*%
+            %TF.FileFunction,Component,L4,Bot*%
+            %TF.FilePolarity,Positive*%
+            %FSLAX46Y46*%
+            G04 Gerber Fmt 4.6, Leading zero omitted, Abs format (unit mm)*
+            %MOMM*%
+            %LPD*%
+            G01*
+            G04 APERTURE LIST*
+            %TA.AperFunction,ComponentMain*%
+            %ADD10C,0.300000*%
+            %TD*%
+            %TA.AperFunction,ComponentPin*%
+            %ADD11P,0.360000X4X0.000000*%
+            %TD*%
+            G04 APERTURE END LIST*
+            D10*
+            %TO.C,R1*%
+            %TO.CRot,270*%
+            %TO.CMfr,Yageo*%
+            %TO.CMPN,RC0402FR-0710KL*%
+            %TO.CVal,10k*%
+            %TO.CFtp,R_0402_1005Metric*%
+            X10000000Y-110000000D03*
+            D11*
+            %TO.P,R1,1,*%
+            X9490000Y-110000000D03*
+            %TD*%
+            D10*
+            %TO.C,C1*%
+            %TO.CVal,100nF*%
+            %TO.CFtp,C_0603_1608Metric*%
+            X20500000Y-30250000D03*
+            %TD*%
+            M02*
+        "};
+
+        // and
+        let expected_result = vec![
+            EdaPlacement {
+                ref_des: "R1".to_string(),
+                place: true,
+                disable_reason: None,
+                fields: vec![
+                    EdaPlacementField::new("manufacturer".to_string(), "Yageo".to_string()),
+                    EdaPlacementField::new("mpn".to_string(), "RC0402FR-0710KL".to_string()),
+                    EdaPlacementField::new("value".to_string(), "10k".to_string()),
+                    EdaPlacementField::new("footprint".to_string(), "R_0402_1005Metric".to_string()),
+                ],
+                pcb_side: PcbSide::Bottom,
+                x: dec!(10),
+                y: dec!(-110),
+                rotation: dec!(-90),
+            },
+            EdaPlacement {
+                ref_des: "C1".to_string(),
+                place: true,
+                disable_reason: None,
+                fields: vec![
+                    EdaPlacementField::new("manufacturer".to_string(), "".to_string()),
+                    EdaPlacementField::new("mpn".to_string(), "".to_string()),
+                    EdaPlacementField::new("value".to_string(), "100nF".to_string()),
+                    EdaPlacementField::new("footprint".to_string(), "C_0603_1608Metric".to_string()),
+                ],
+                pcb_side: PcbSide::Bottom,
+                x: dec!(20.5),
+                y: dec!(-30.25),
+                rotation: dec!(0),
+            },
+        ];
+
+        // when
+        let result: Vec<EdaPlacement> = parse_component_layer(content).unwrap().iter()
+            .map(|component| component.build_eda_placement().unwrap())
+            .collect();
+
+        // then
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn inches() {
+        // given
+        let content = indoc! {"
+            %TF.FileFunction,Component,L1,Top*%
+            %FSLAX25Y25*%
+            %MOIN*%
+            %TA.AperFunction,ComponentMain*%
+            %ADD10C,0.01*%
+            %TD*%
+            D10*
+            %TO.C,U1*%
+            X100000Y-50000D03*
+            M02*
+        "};
+
+        // when
+        let result = parse_component_layer(content).unwrap()[0].build_eda_placement().unwrap();
+
+        // then
+        assert_eq!((result.pcb_side, result.x, result.y), (PcbSide::Top, dec!(25.4), dec!(-12.7)));
+    }
+
+    #[test]
+    fn copper_layer() {
+        // when
+        let result = parse_component_layer("%TF.FileFunction,Copper,L1,Top*%\n");
+
+        // then
+        assert_eq!(result, Err(GerberX3Error::NotAComponentLayer("Copper,L1,Top".to_string())));
+    }
+}
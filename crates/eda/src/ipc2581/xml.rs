@@ -0,0 +1,332 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+use roxmltree::{Document, Node};
+use rust_decimal::Decimal;
+use thiserror::Error;
+use pnp::pcb::PcbSide;
+use crate::placement::{EdaPlacement, EdaPlacementDisableReason, EdaPlacementField};
+use crate::rotation::RotationConverter;
+use crate::units::LengthUnit;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum Ipc2581Error {
+    #[error("Invalid XML. reason: {0}")]
+    InvalidXml(String),
+    #[error("Missing attribute. element: {element}, attribute: {attribute}")]
+    MissingAttribute { element: String, attribute: String },
+    #[error("Invalid number. element: {element}, value: '{value}'")]
+    InvalidNumber { element: String, value: String },
+    #[error("Unknown units. value: '{0}'")]
+    UnknownUnits(String),
+    #[error("Unknown layer. ref_des: {ref_des}, layer: '{layer}'")]
+    UnknownLayer { ref_des: String, layer: String },
+}
+
+/// A `<Component>` element from the `<Step>` of an IPC-2581 file, combined with its BOM item, e.g.
+///
+/// ```xml
+/// <Component refDes="R1" packageRef="R0402" layerRef="TOP" part="RES-10K">
+///   <Xform rotation="90.0"/>
+///   <Location x="10.0" y="20.0"/>
+/// </Component>
+/// ```
+///
+/// The value and MPN come from the textual characteristics of the `<BomItem>` that lists the component's `<RefDes>`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ipc2581Component {
+    ref_des: String,
+    package: String,
+    value: String,
+    mpn: String,
+    populate: bool,
+    side: PcbSide,
+    x: Decimal,
+    y: Decimal,
+    /// Positive values indicate anti-clockwise rotation
+    rotation: Decimal,
+    units: LengthUnit,
+}
+
+impl Ipc2581Component {
+    pub fn units(&self) -> LengthUnit {
+        self.units
+    }
+
+    pub fn build_eda_placement(&self) -> Result<EdaPlacement, Ipc2581Error> {
+        Ok(EdaPlacement {
+            ref_des: self.ref_des.to_string(),
+            place: self.populate,
+            disable_reason: (!self.populate).then_some(EdaPlacementDisableReason::DoNotPopulate),
+            fields: vec![
+                EdaPlacementField { name: "package".to_string(), value: self.package.to_string() },
+                EdaPlacementField { name: "value".to_string(), value: self.value.to_string() },
+                EdaPlacementField { name: "mpn".to_string(), value: self.mpn.to_string() },
+            ],
+            pcb_side: self.side.clone(),
+            x: self.units.to_millimeters(self.x),
+            y: self.units.to_millimeters(self.y),
+            rotation: RotationConverter::convert(self.rotation),
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+struct BomEntry {
+    value: String,
+    mpn: String,
+    populate: bool,
+}
+
+/// Units default to millimeters if the file does not have a `<CadHeader>`.
+///
+/// Components without a BOM entry are placed and have empty value and MPN fields.
+pub fn parse_components(content: &str) -> Result<Vec<Ipc2581Component>, Ipc2581Error> {
+    let document = Document::parse(content)
+        .map_err(|error| Ipc2581Error::InvalidXml(error.to_string()))?;
+
+    let units = match descendants_named(&document, "CadHeader").next() {
+        Some(node) => parse_units(required_attribute(&node, "units")?)?,
+        None => LengthUnit::Millimeters,
+    };
+
+    let layer_sides: HashMap<&str, PcbSide> = descendants_named(&document, "Layer")
+        .filter_map(|node| {
+            let side = match node.attribute("side")? {
+                "TOP" => PcbSide::Top,
+                "BOTTOM" => PcbSide::Bottom,
+                _ => return None,
+            };
+            Some((node.attribute("name")?, side))
+        })
+        .collect();
+
+    let bom_entries = build_bom_entries(&document);
+
+    let mut components = vec![];
+
+    for node in descendants_named(&document, "Component") {
+        let ref_des = required_attribute(&node, "refDes")?;
+        let layer = required_attribute(&node, "layerRef")?;
+
+        let side = layer_sides.get(layer).cloned()
+            .ok_or(Ipc2581Error::UnknownLayer { ref_des: ref_des.to_string(), layer: layer.to_string() })?;
+
+        let location = children_named(&node, "Location").next()
+            .ok_or(Ipc2581Error::MissingAttribute { element: "Component".to_string(), attribute: "Location".to_string() })?;
+
+        let rotation = match children_named(&node, "Xform").next().and_then(|xform| xform.attribute("rotation")) {
+            Some(rotation) => parse_decimal("Xform", rotation)?,
+            None => Decimal::ZERO,
+        };
+
+        let bom_entry = bom_entries.get(ref_des);
+
+        components.push(Ipc2581Component {
+            ref_des: ref_des.to_string(),
+            package: required_attribute(&node, "packageRef")?.to_string(),
+            value: bom_entry.map(|entry| entry.value.clone()).unwrap_or_default(),
+            mpn: bom_entry.map(|entry| entry.mpn.clone()).unwrap_or_default(),
+            populate: bom_entry.is_none_or(|entry| entry.populate),
+            side,
+            x: parse_decimal("Location", required_attribute(&location, "x")?)?,
+            y: parse_decimal("Location", required_attribute(&location, "y")?)?,
+            rotation,
+            units,
+        });
+    }
+
+    Ok(components)
+}
+
+/// e.g.
+///
+/// ```xml
+/// <BomItem OEMDesignNumberRef="RES-10K" quantity="1">
+///   <RefDes name="R1" packageRef="R0402" populate="true" layerRef="TOP"/>
+///   <Characteristics category="ELECTRICAL">
+///     <Textual textualCharacteristicName="Value" textualCharacteristicValue="10k"/>
+///     <Textual textualCharacteristicName="MPN" textualCharacteristicValue="RC0402FR-0710KL"/>
+///   </Characteristics>
+/// </BomItem>
+/// ```
+fn build_bom_entries<'input>(document: &'input Document) -> HashMap<&'input str, BomEntry> {
+    let mut bom_entries = HashMap::new();
+
+    for bom_item in descendants_named(document, "BomItem") {
+        let mut value = String::new();
+        let mut mpn = String::new();
+
+        for textual in bom_item.descendants().filter(|node| node.has_tag_name("Textual")) {
+            let (Some(name), Some(characteristic_value)) = (
+                textual.attribute("textualCharacteristicName"),
+                textual.attribute("textualCharacteristicValue"),
+            ) else {
+                continue
+            };
+
+            match name.to_lowercase().as_str() {
+                "value" => value = characteristic_value.to_string(),
+                "mpn" | "manufacturer part number" | "partnumber" | "part number" => mpn = characteristic_value.to_string(),
+                _ => (),
+            }
+        }
+
+        for ref_des in children_named(&bom_item, "RefDes") {
+            let Some(name) = ref_des.attribute("name") else {
+                continue
+            };
+
+            bom_entries.insert(name, BomEntry {
+                value: value.clone(),
+                mpn: mpn.clone(),
+                populate: ref_des.attribute("populate").is_none_or(|populate| populate.eq_ignore_ascii_case("true")),
+            });
+        }
+    }
+
+    bom_entries
+}
+
+/// Matches on the local name, IPC-2581 files use a default namespace which differs between revisions.
+fn descendants_named<'a, 'input>(document: &'a Document<'input>, name: &'a str) -> impl Iterator<Item = Node<'a, 'input>> {
+    document.descendants().filter(move |node| node.tag_name().name().eq(name))
+}
+
+fn children_named<'a, 'input>(node: &Node<'a, 'input>, name: &'a str) -> impl Iterator<Item = Node<'a, 'input>> {
+    node.children().filter(move |node| node.tag_name().name().eq(name))
+}
+
+fn required_attribute<'a>(node: &Node<'a, '_>, attribute: &str) -> Result<&'a str, Ipc2581Error> {
+    node.attribute(attribute)
+        .ok_or(Ipc2581Error::MissingAttribute { element: node.tag_name().name().to_string(), attribute: attribute.to_string() })
+}
+
+fn parse_units(value: &str) -> Result<LengthUnit, Ipc2581Error> {
+    match value {
+        "MILLIMETER" => Ok(LengthUnit::Millimeters),
+        "MICRON" => Ok(LengthUnit::Micrometers),
+        "INCH" => Ok(LengthUnit::Inches),
+        _ => Err(Ipc2581Error::UnknownUnits(value.to_string())),
+    }
+}
+
+fn parse_decimal(element: &str, value: &str) -> Result<Decimal, Ipc2581Error> {
+    Decimal::from_str(value)
+        .or_else(|_err| Decimal::from_scientific(value))
+        .map_err(|_err| Ipc2581Error::InvalidNumber { element: element.to_string(), value: value.to_string() })
+}
+
+#[cfg(test)]
+mod ipc2581_xml_tests {
+    use indoc::indoc;
+    use rust_decimal_macros::dec;
+    use pnp::pcb::PcbSide;
+    use crate::ipc2581::xml::{parse_components, Ipc2581Error};
+    use crate::placement::{EdaPlacement, EdaPlacementDisableReason, EdaPlacementField};
+
+    #[test]
+    fn components() {
+        // given
+        let content = indoc! {r#"
+            <?xml version="1.0" encoding="UTF-8"?>
+            <IPC-2581 revision="C" xmlns="http://webstds.ipc.org/2581">
+              <Bom name="board_bom">
+                <BomHeader assembly="board" revision="1"/>
+                <BomItem OEMDesignNumberRef="RES-10K" quantity="1" category="ELECTRICAL">
+                  <RefDes name="R1" packageRef="R0402" populate="true" layerRef="TOP"/>
+                  <Characteristics category="ELECTRICAL">
+                    <Textual definitionSource="EDA" textualCharacteristicName="Value" textualCharacteristicValue="10k"/>
+                    <Textual definitionSource="EDA" textualCharacteristicName="MPN" textualCharacteristicValue="RC0402FR-0710KL"/>
+                  </Characteristics>
+                </BomItem>
+                <BomItem OEMDesignNumberRef="CAP-100NF" quantity="1" category="ELECTRICAL">
+                  <RefDes name="C1" packageRef="C0603" populate="false" layerRef="BOTTOM"/>
+                  <Characteristics category="ELECTRICAL">
+                    <Textual definitionSource="EDA" textualCharacteristicName="Value" textualCharacteristicValue="100nF"/>
+                  </Characteristics>
+                </BomItem>
+              </Bom>
+              <Ecad name="board">
+                <CadHeader units="INCH"/>
+                <CadData>
+                  <Layer name="TOP" layerFunction="CONDUCTOR" side="TOP" polarity="POSITIVE"/>
+                  <Layer name="BOTTOM" layerFunction="CONDUCTOR" side="BOTTOM" polarity="POSITIVE"/>
+                  <Step name="board">
+                    <Component refDes="R1" packageRef="R0402" layerRef="TOP" part="RES-10K" mountType="SMT">
+                      <Xform rotation="270.0"/>
+                      <Location x="0.5" y="4.0"/>
+                    </Component>
+                    <Component refDes="C1" packageRef="C0603" layerRef="BOTTOM" part="CAP-100NF" mountType="SMT">
+                      <Location x="1.0" y="-0.25"/>
+                    </Component>
+                  </Step>
+                </CadData>
+              </Ecad>
+            </IPC-2581>
+        "#};
+
+        // and
+        let expected_result = vec![
+            EdaPlacement {
+                ref_des: "R1".to_string(),
+                place: true,
+                disable_reason: None,
+                fields: vec![
+                    EdaPlacementField::new("package".to_string(), "R0402".to_string()),
+                    EdaPlacementField::new("value".to_string(), "10k".to_string()),
+                    EdaPlacementField::new("mpn".to_string(), "RC0402FR-0710KL".to_string()),
+                ],
+                pcb_side: PcbSide::Top,
+                x: dec!(12.7),
+                y: dec!(101.6),
+                rotation: dec!(-90),
+            },
+            EdaPlacement {
+                ref_des: "C1".to_string(),
+                place: false,
+                disable_reason: Some(EdaPlacementDisableReason::DoNotPopulate),
+                fields: vec![
+                    EdaPlacementField::new("package".to_string(), "C0603".to_string()),
+                    EdaPlacementField::new("value".to_string(), "100nF".to_string()),
+                    EdaPlacementField::new("mpn".to_string(), "".to_string()),
+                ],
+                pcb_side: PcbSide::Bottom,
+                x: dec!(25.4),
+                y: dec!(-6.35),
+                rotation: dec!(0),
+            },
+        ];
+
+        // when
+        let result: Vec<EdaPlacement> = parse_components(content).unwrap().iter()
+            .map(|component| component.build_eda_placement().unwrap())
+            .collect();
+
+        // then
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn unknown_layer() {
+        // given
+        let content = indoc! {r#"
+            <IPC-2581 revision="B">
+              <Ecad name="board">
+                <CadData>
+                  <Step name="board">
+                    <Component refDes="R1" packageRef="R0402" layerRef="INNER1">
+                      <Location x="0" y="0"/>
+                    </Component>
+                  </Step>
+                </CadData>
+              </Ecad>
+            </IPC-2581>
+        "#};
+
+        // when
+        let result = parse_components(content);
+
+        // then
+        assert_eq!(result, Err(Ipc2581Error::UnknownLayer { ref_des: "R1".to_string(), layer: "INNER1".to_string() }));
+    }
+}
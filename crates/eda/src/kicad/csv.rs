@@ -43,10 +43,14 @@ impl From<&KiCadPcbSide> for PcbSide {
 }
 
 impl KiCadPlacementRecord {
+    /// Columns used by the record, other columns are carried as additional placement fields when loading.
+    pub const STANDARD_COLUMNS: &'static [&'static str] = &["ref", "Package", "Val", "Side", "X", "Y", "Rotation"];
+
     pub fn build_eda_placement(&self) -> Result<EdaPlacement, KiCadPlacementRecordError> {
         Ok(EdaPlacement {
             ref_des: self.ref_des.to_string(),
             place: true,
+            disable_reason: None,
             fields: vec![
                 EdaPlacementField { name: "package".to_string(), value: self.package.to_string() },
                 EdaPlacementField { name: "val".to_string(), value: self.val.to_string() },
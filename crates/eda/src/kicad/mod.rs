@@ -1 +1,3 @@
-pub mod csv;
\ No newline at end of file
+pub mod csv;
+pub mod pcb;
+pub mod pos;
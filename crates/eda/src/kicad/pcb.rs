@@ -0,0 +1,416 @@
+use std::iter::Peekable;
+use std::str::{Chars, FromStr};
+use heck::ToSnakeCase;
+use rust_decimal::Decimal;
+use thiserror::Error;
+use pnp::pcb::PcbSide;
+use crate::placement::{EdaPlacement, EdaPlacementDisableReason, EdaPlacementField};
+use crate::rotation::RotationConverter;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum KiCadPcbError {
+    #[error("Unexpected end of file")]
+    UnexpectedEof,
+    #[error("Unexpected token. token: '{0}'")]
+    UnexpectedToken(String),
+    #[error("Invalid footprint. reason: {0}")]
+    InvalidFootprint(String),
+}
+
+/// A footprint from a KiCad board file.
+///
+/// Both the KiCad 6+ `(footprint ...)` and the older KiCad 5 `(module ...)` forms are supported.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KiCadPcbFootprint {
+    ref_des: String,
+    /// Footprint library identifier, e.g. `Resistor_SMD:R_0402_1005Metric`
+    footprint: String,
+    value: String,
+    layer: String,
+    /// KiCad board files use a Y-down coordinate system.
+    x: Decimal,
+    y: Decimal,
+    /// Positive values indicate anti-clockwise rotation
+    rotation: Decimal,
+    attributes: Vec<String>,
+    /// All properties, in the order they appear in the footprint.
+    properties: Vec<(String, String)>,
+}
+
+impl KiCadPcbFootprint {
+    /// Footprint name, without the library nickname, same as the 'Package' column of the position file export.
+    pub fn package(&self) -> &str {
+        match self.footprint.split_once(':') {
+            Some((_library, name)) => name,
+            None => self.footprint.as_str(),
+        }
+    }
+
+    pub fn is_dnp(&self) -> bool {
+        self.attributes.iter().any(|attribute| attribute.eq("dnp"))
+    }
+
+    pub fn is_excluded_from_pos_files(&self) -> bool {
+        self.attributes.iter().any(|attribute| attribute.eq("exclude_from_pos_files"))
+    }
+
+    /// Coordinates are converted to match the position file export, i.e. the Y axis is inverted.
+    pub fn build_eda_placement(&self) -> Result<EdaPlacement, KiCadPcbError> {
+        let pcb_side = match self.layer.as_str() {
+            "F.Cu" => Ok(PcbSide::Top),
+            "B.Cu" => Ok(PcbSide::Bottom),
+            _ => Err(KiCadPcbError::InvalidFootprint(format!("unsupported layer. ref_des: {}, layer: {}", self.ref_des, self.layer))),
+        }?;
+
+        let mut fields = vec![
+            EdaPlacementField { name: "package".to_string(), value: self.package().to_string() },
+            EdaPlacementField { name: "val".to_string(), value: self.value.to_string() },
+        ];
+
+        // 'Reference', 'Value' and 'Footprint' are already represented by the ref_des, 'val' and 'package'.
+        // the names are converted in the same way as the columns of CSV placements, e.g. 'MfrPN' -> 'mfr_pn'.
+        for (name, value) in self.properties.iter() {
+            if ["Reference", "Value", "Footprint"].contains(&name.as_str()) {
+                continue
+            }
+            fields.push(EdaPlacementField { name: name.to_snake_case(), value: value.to_string() });
+        }
+
+        let disable_reason = if self.is_dnp() {
+            Some(EdaPlacementDisableReason::DoNotPopulate)
+        } else if self.is_excluded_from_pos_files() {
+            Some(EdaPlacementDisableReason::ExcludedFromPositionFiles)
+        } else {
+            None
+        };
+
+        Ok(EdaPlacement {
+            ref_des: self.ref_des.to_string(),
+            place: disable_reason.is_none(),
+            disable_reason,
+            fields,
+            pcb_side,
+            x: self.x,
+            y: -self.y,
+            rotation: RotationConverter::convert(self.rotation),
+        })
+    }
+}
+
+pub fn parse_footprints(content: &str) -> Result<Vec<KiCadPcbFootprint>, KiCadPcbError> {
+    let mut chars = content.chars().peekable();
+    let root = parse_expression(&mut chars)?;
+
+    let footprints = root.children()
+        .filter(|&child| child.is_list_named("footprint") || child.is_list_named("module"))
+        .map(build_footprint)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(footprints)
+}
+
+fn build_footprint(expression: &SExpression) -> Result<KiCadPcbFootprint, KiCadPcbError> {
+    let footprint = expression.atom_at(1)
+        .ok_or(KiCadPcbError::InvalidFootprint("missing footprint name".to_string()))?;
+
+    let layer = expression.find_child("layer").and_then(|layer| layer.atom_at(1))
+        .ok_or(KiCadPcbError::InvalidFootprint(format!("missing layer. footprint: {}", footprint)))?;
+
+    let at = expression.find_child("at")
+        .ok_or(KiCadPcbError::InvalidFootprint(format!("missing position. footprint: {}", footprint)))?;
+    let x = at.decimal_at(1)?.unwrap_or_default();
+    let y = at.decimal_at(2)?.unwrap_or_default();
+    let rotation = at.decimal_at(3)?.unwrap_or_default();
+
+    let attributes: Vec<String> = expression.find_child("attr")
+        .map(|attr| attr.children().skip(1).filter_map(SExpression::atom).map(str::to_string).collect())
+        .unwrap_or_default();
+
+    let properties: Vec<(String, String)> = expression.children()
+        .filter(|child| child.is_list_named("property"))
+        .filter_map(|property| match (property.atom_at(1), property.atom_at(2)) {
+            (Some(name), Some(value)) => Some((name.to_string(), value.to_string())),
+            _ => None,
+        })
+        .collect();
+
+    // KiCad 5-7 store the reference and value as text items, KiCad 8+ stores them as properties.
+    let find_text = |kind: &str| {
+        expression.children()
+            .find(|child| child.is_list_named("fp_text") && child.atom_at(1) == Some(kind))
+            .and_then(|text| text.atom_at(2))
+            .or_else(|| properties.iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(kind))
+                .map(|(_, value)| value.as_str())
+            )
+            .map(str::to_string)
+    };
+
+    let ref_des = find_text("reference")
+        .ok_or(KiCadPcbError::InvalidFootprint(format!("missing reference. footprint: {}", footprint)))?;
+    let value = find_text("value").unwrap_or_default();
+
+    Ok(KiCadPcbFootprint {
+        ref_des,
+        footprint: footprint.to_string(),
+        value,
+        layer: layer.to_string(),
+        x,
+        y,
+        rotation,
+        attributes,
+        properties,
+    })
+}
+
+#[derive(Debug, PartialEq)]
+enum SExpression {
+    Atom(String),
+    List(Vec<SExpression>),
+}
+
+impl SExpression {
+    fn atom(&self) -> Option<&str> {
+        match self {
+            SExpression::Atom(atom) => Some(atom.as_str()),
+            SExpression::List(_) => None,
+        }
+    }
+
+    fn children(&self) -> impl Iterator<Item = &SExpression> {
+        let children: &[SExpression] = match self {
+            SExpression::List(children) => children.as_slice(),
+            SExpression::Atom(_) => &[],
+        };
+        children.iter()
+    }
+
+    fn atom_at(&self, index: usize) -> Option<&str> {
+        self.children().nth(index).and_then(SExpression::atom)
+    }
+
+    fn decimal_at(&self, index: usize) -> Result<Option<Decimal>, KiCadPcbError> {
+        self.atom_at(index)
+            .map(|atom| Decimal::from_str(atom).map_err(|_err| KiCadPcbError::UnexpectedToken(atom.to_string())))
+            .transpose()
+    }
+
+    fn is_list_named(&self, name: &str) -> bool {
+        self.atom_at(0) == Some(name)
+    }
+
+    fn find_child(&self, name: &str) -> Option<&SExpression> {
+        self.children().find(|child| child.is_list_named(name))
+    }
+}
+
+fn parse_expression(chars: &mut Peekable<Chars>) -> Result<SExpression, KiCadPcbError> {
+    skip_whitespace(chars);
+
+    match chars.next() {
+        None => Err(KiCadPcbError::UnexpectedEof),
+        Some('(') => {
+            let mut children = vec![];
+            loop {
+                skip_whitespace(chars);
+                match chars.peek() {
+                    None => return Err(KiCadPcbError::UnexpectedEof),
+                    Some(')') => {
+                        chars.next();
+                        break
+                    },
+                    Some(_) => children.push(parse_expression(chars)?),
+                }
+            }
+            Ok(SExpression::List(children))
+        },
+        Some(')') => Err(KiCadPcbError::UnexpectedToken(")".to_string())),
+        Some('"') => {
+            let mut atom = String::new();
+            loop {
+                match chars.next() {
+                    None => return Err(KiCadPcbError::UnexpectedEof),
+                    Some('"') => break,
+                    Some('\\') => match chars.next() {
+                        None => return Err(KiCadPcbError::UnexpectedEof),
+                        Some('n') => atom.push('\n'),
+                        Some(other) => atom.push(other),
+                    },
+                    Some(other) => atom.push(other),
+                }
+            }
+            Ok(SExpression::Atom(atom))
+        },
+        Some(first) => {
+            let mut atom = first.to_string();
+            while let Some(&next) = chars.peek() {
+                if next.is_whitespace() || next == '(' || next == ')' {
+                    break
+                }
+                atom.push(next);
+                chars.next();
+            }
+            Ok(SExpression::Atom(atom))
+        }
+    }
+}
+
+fn skip_whitespace(chars: &mut Peekable<Chars>) {
+    while chars.next_if(|char| char.is_whitespace()).is_some() {}
+}
+
+#[cfg(test)]
+mod kicad_pcb_tests {
+    use indoc::indoc;
+    use rust_decimal_macros::dec;
+    use pnp::pcb::PcbSide;
+    use crate::kicad::pcb::parse_footprints;
+    use crate::placement::{EdaPlacement, EdaPlacementDisableReason, EdaPlacementField};
+
+    #[test]
+    fn kicad_8_footprints() {
+        // given
+        let content = indoc! {r#"
+            (kicad_pcb
+                (version 20240108)
+                (generator "pcbnew")
+                (footprint "Resistor_SMD:R_0402_1005Metric"
+                    (layer "F.Cu")
+                    (uuid "3b0f0e9c-5d1a-4e1a-9f0a-000000000001")
+                    (at 110.5 -80.25 90)
+                    (property "Reference" "R1"
+                        (at 0 -1.17 90)
+                        (layer "F.SilkS")
+                    )
+                    (property "Value" "10k"
+                        (at 0 1.17 90)
+                        (layer "F.Fab")
+                    )
+                    (property "Footprint" "Resistor_SMD:R_0402_1005Metric" (at 0 0 90) (layer "F.Fab") (hide yes))
+                    (property "MPN" "RC0402FR-0710KL" (at 0 0 90) (layer "F.Fab") (hide yes))
+                    (attr smd)
+                    (pad "1" smd roundrect (at -0.51 0 90) (size 0.54 0.64) (layers "F.Cu" "F.Paste" "F.Mask"))
+                )
+                (footprint "Capacitor_SMD:C_0603_1608Metric"
+                    (layer "B.Cu")
+                    (at 20 30 270)
+                    (property "Reference" "C1" (at 0 0 0) (layer "B.SilkS"))
+                    (property "Value" "100nF" (at 0 0 0) (layer "B.Fab"))
+                    (attr smd dnp)
+                )
+            )
+        "#};
+
+        // and
+        let expected_result = vec![
+            EdaPlacement {
+                ref_des: "R1".to_string(),
+                place: true,
+                disable_reason: None,
+                fields: vec![
+                    EdaPlacementField::new("package".to_string(), "R_0402_1005Metric".to_string()),
+                    EdaPlacementField::new("val".to_string(), "10k".to_string()),
+                    EdaPlacementField::new("mpn".to_string(), "RC0402FR-0710KL".to_string()),
+                ],
+                pcb_side: PcbSide::Top,
+                x: dec!(110.5),
+                y: dec!(80.25),
+                rotation: dec!(90),
+            },
+            EdaPlacement {
+                ref_des: "C1".to_string(),
+                place: false,
+                disable_reason: Some(EdaPlacementDisableReason::DoNotPopulate),
+                fields: vec![
+                    EdaPlacementField::new("package".to_string(), "C_0603_1608Metric".to_string()),
+                    EdaPlacementField::new("val".to_string(), "100nF".to_string()),
+                ],
+                pcb_side: PcbSide::Bottom,
+                x: dec!(20),
+                y: dec!(-30),
+                rotation: dec!(-90),
+            },
+        ];
+
+        // when
+        let result: Vec<EdaPlacement> = parse_footprints(content).unwrap().iter()
+            .map(|footprint| footprint.build_eda_placement().unwrap())
+            .collect();
+
+        // then
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn kicad_6_footprints() {
+        // given
+        let content = indoc! {r#"
+            (kicad_pcb (version 20211014) (generator pcbnew)
+              (footprint "Diode_SMD:D_SOD-123" (layer "F.Cu")
+                (tedit 58645DC7) (tstamp 00000000-0000-0000-0000-000000000001)
+                (at 50 60 180)
+                (property "Sheetfile" "main.kicad_sch")
+                (attr smd exclude_from_pos_files)
+                (fp_text reference "D1" (at 0 -2 0) (layer "F.SilkS"))
+                (fp_text value "1N4148W" (at 0 2 0) (layer "F.Fab"))
+                (fp_text user "${REFERENCE}" (at 0 0 0) (layer "F.Fab"))
+              )
+            )
+        "#};
+
+        // and
+        let expected_result = vec![
+            EdaPlacement {
+                ref_des: "D1".to_string(),
+                place: false,
+                disable_reason: Some(EdaPlacementDisableReason::ExcludedFromPositionFiles),
+                fields: vec![
+                    EdaPlacementField::new("package".to_string(), "D_SOD-123".to_string()),
+                    EdaPlacementField::new("val".to_string(), "1N4148W".to_string()),
+                    EdaPlacementField::new("sheetfile".to_string(), "main.kicad_sch".to_string()),
+                ],
+                pcb_side: PcbSide::Top,
+                x: dec!(50),
+                y: dec!(-60),
+                rotation: dec!(180),
+            },
+        ];
+
+        // when
+        let result: Vec<EdaPlacement> = parse_footprints(content).unwrap().iter()
+            .map(|footprint| footprint.build_eda_placement().unwrap())
+            .collect();
+
+        // then
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn property_names_are_snake_case() {
+        // given
+        let content = indoc! {r#"
+            (kicad_pcb (version 20240108) (generator "pcbnew")
+              (footprint "Resistor_SMD:R_0402_1005Metric" (layer "F.Cu")
+                (at 10 20)
+                (property "Reference" "R1")
+                (property "Value" "10k")
+                (property "MfrPN" "RC0402FR-0710KL")
+                (property "Supplier Part" "311-10.0KLRCT-ND")
+              )
+            )
+        "#};
+
+        // when
+        let result: Vec<EdaPlacement> = parse_footprints(content).unwrap().iter()
+            .map(|footprint| footprint.build_eda_placement().unwrap())
+            .collect();
+
+        // then
+        assert_eq!(result[0].fields, vec![
+            EdaPlacementField::new("package".to_string(), "R_0402_1005Metric".to_string()),
+            EdaPlacementField::new("val".to_string(), "10k".to_string()),
+            EdaPlacementField::new("mfr_pn".to_string(), "RC0402FR-0710KL".to_string()),
+            EdaPlacementField::new("supplier_part".to_string(), "311-10.0KLRCT-ND".to_string()),
+        ]);
+    }
+}
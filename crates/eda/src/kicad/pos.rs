@@ -0,0 +1,212 @@
+use std::str::FromStr;
+use rust_decimal::Decimal;
+use thiserror::Error;
+use pnp::pcb::PcbSide;
+use crate::placement::{EdaPlacement, EdaPlacementField};
+use crate::rotation::RotationConverter;
+use crate::units::LengthUnit;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum KiCadPosError {
+    #[error("Unknown units. value: '{0}'")]
+    UnknownUnits(String),
+    #[error("Invalid line. line: {line}, reason: {reason}")]
+    InvalidLine { line: usize, reason: String },
+}
+
+/// A line from a KiCad ASCII position file, e.g.
+///
+/// ```text
+/// ### Module positions - created on 2024-08-01 12:00:00 ###
+/// ### Printed by KiCad version 8.0.4
+/// ## Unit = mm, Angle = deg.
+/// ## Side : top
+/// # Ref     Val       Package                PosX       PosY       Rot  Side
+/// R1        10k       R_0402_1005Metric   10.0000   110.0000    0.0000  top
+/// ## End
+/// ```
+///
+/// KiCad replaces whitespace in the value and package with underscores, so columns are whitespace separated.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KiCadPosRecord {
+    ref_des: String,
+    val: String,
+    package: String,
+    x: Decimal,
+    y: Decimal,
+    /// Positive values indicate anti-clockwise rotation
+    /// Older versions of KiCad use the range 0 - <360.
+    rotation: Decimal,
+    side: PcbSide,
+    units: LengthUnit,
+}
+
+impl KiCadPosRecord {
+    pub fn units(&self) -> LengthUnit {
+        self.units
+    }
+
+    pub fn build_eda_placement(&self) -> Result<EdaPlacement, KiCadPosError> {
+        Ok(EdaPlacement {
+            ref_des: self.ref_des.to_string(),
+            place: true,
+            disable_reason: None,
+            fields: vec![
+                EdaPlacementField { name: "package".to_string(), value: self.package.to_string() },
+                EdaPlacementField { name: "val".to_string(), value: self.val.to_string() },
+            ],
+            pcb_side: self.side.clone(),
+            x: self.units.to_millimeters(self.x),
+            y: self.units.to_millimeters(self.y),
+            rotation: RotationConverter::convert(self.rotation),
+        })
+    }
+}
+
+/// Units default to millimeters if the file does not declare them.
+pub fn parse_pos(content: &str) -> Result<Vec<KiCadPosRecord>, KiCadPosError> {
+    let mut units = LengthUnit::Millimeters;
+    let mut records = vec![];
+
+    for (index, line) in content.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.trim();
+
+        if line.is_empty() {
+            continue
+        }
+
+        if let Some(comment) = line.strip_prefix('#') {
+            // e.g. '## Unit = mm, Angle = deg.'
+            if let Some((_, remainder)) = comment.trim_start_matches('#').trim().split_once("Unit =") {
+                let unit = remainder.split(',').next().unwrap_or_default().trim();
+                units = LengthUnit::from_str(unit)
+                    .map_err(|_err| KiCadPosError::UnknownUnits(unit.to_string()))?;
+            }
+            continue
+        }
+
+        let invalid_line = |reason: String| KiCadPosError::InvalidLine { line: line_number, reason };
+
+        let columns: Vec<&str> = line.split_whitespace().collect();
+        let [ref_des, val, package, x, y, rotation, side] = columns[..] else {
+            return Err(invalid_line(format!("expected 7 columns, found {}", columns.len())))
+        };
+
+        let parse_decimal = |value: &str| Decimal::from_str(value)
+            .map_err(|_err| invalid_line(format!("invalid number '{}'", value)));
+
+        let side = match side.to_lowercase().as_str() {
+            "top" => Ok(PcbSide::Top),
+            "bottom" => Ok(PcbSide::Bottom),
+            _ => Err(invalid_line(format!("invalid side '{}'", side))),
+        }?;
+
+        records.push(KiCadPosRecord {
+            ref_des: ref_des.to_string(),
+            val: val.to_string(),
+            package: package.to_string(),
+            x: parse_decimal(x)?,
+            y: parse_decimal(y)?,
+            rotation: parse_decimal(rotation)?,
+            side,
+            units,
+        });
+    }
+
+    Ok(records)
+}
+
+#[cfg(test)]
+mod kicad_pos_tests {
+    use indoc::indoc;
+    use rust_decimal_macros::dec;
+    use pnp::pcb::PcbSide;
+    use crate::kicad::pos::{parse_pos, KiCadPosError};
+    use crate::placement::{EdaPlacement, EdaPlacementField};
+
+    #[test]
+    fn millimeters() {
+        // given
+        let content = indoc! {"
+            ### Module positions - created on 2024-08-01 12:00:00 ###
+            ### Printed by KiCad version 8.0.4
+            ## Unit = mm, Angle = deg.
+            ## Side : All
+            # Ref     Val       Package                PosX       PosY       Rot  Side
+            R1        10k       R_0402_1005Metric   10.0000   110.0000  270.0000  top
+            C1        100nF_16V C_0603_1608Metric   20.5000   -30.2500    0.0000  bottom
+            ## End
+        "};
+
+        // and
+        let expected_result = vec![
+            EdaPlacement {
+                ref_des: "R1".to_string(),
+                place: true,
+                disable_reason: None,
+                fields: vec![
+                    EdaPlacementField::new("package".to_string(), "R_0402_1005Metric".to_string()),
+                    EdaPlacementField::new("val".to_string(), "10k".to_string()),
+                ],
+                pcb_side: PcbSide::Top,
+                x: dec!(10),
+                y: dec!(110),
+                rotation: dec!(-90),
+            },
+            EdaPlacement {
+                ref_des: "C1".to_string(),
+                place: true,
+                disable_reason: None,
+                fields: vec![
+                    EdaPlacementField::new("package".to_string(), "C_0603_1608Metric".to_string()),
+                    EdaPlacementField::new("val".to_string(), "100nF_16V".to_string()),
+                ],
+                pcb_side: PcbSide::Bottom,
+                x: dec!(20.5),
+                y: dec!(-30.25),
+                rotation: dec!(0),
+            },
+        ];
+
+        // when
+        let result: Vec<EdaPlacement> = parse_pos(content).unwrap().iter()
+            .map(|record| record.build_eda_placement().unwrap())
+            .collect();
+
+        // then
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn inches() {
+        // given
+        let content = indoc! {"
+            ### Module positions - created on 2016-01-01 12:00:00 ###
+            ## Unit = inches, Angle = deg.
+            # Ref    Val       Package        PosX       PosY       Rot     Side
+            U1       ATMEGA328 TQFP-32       1.0000    -0.5000   45.0000   top
+        "};
+
+        // when
+        let result = parse_pos(content).unwrap()[0].build_eda_placement().unwrap();
+
+        // then
+        assert_eq!((result.x, result.y), (dec!(25.4), dec!(-12.7)));
+    }
+
+    #[test]
+    fn invalid_line() {
+        // given
+        let content = indoc! {"
+            ## Unit = mm, Angle = deg.
+            R1 10k R_0402 10.0000 110.0000 0.0000
+        "};
+
+        // when
+        let result = parse_pos(content);
+
+        // then
+        assert_eq!(result, Err(KiCadPosError::InvalidLine { line: 2, reason: "expected 7 columns, found 6".to_string() }));
+    }
+}
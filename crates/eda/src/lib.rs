@@ -1,12 +1,28 @@
+pub mod altium;
 pub mod diptrace;
+pub mod eagle;
+pub mod easyeda;
+pub mod gerber;
+pub mod ipc2581;
 pub mod kicad;
 
 pub mod placement;
 pub mod substitution;
 pub mod criteria;
+pub mod rotation;
+pub mod profile;
+pub mod units;
+
+use crate::profile::EdaProfile;
 
 #[derive(Debug)]
 pub enum EdaTool {
     DipTrace,
     KiCad,
+    Altium,
+    EasyEda,
+    Eagle,
+    Ipc2581,
+    GerberX3,
+    Profile(Box<EdaProfile>),
 }
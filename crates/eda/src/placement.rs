@@ -1,10 +1,14 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
 use rust_decimal::Decimal;
+use criteria::FieldCriterion;
 use pnp::pcb::PcbSide;
+use util::quantity::Quantity;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct EdaPlacementField {
     pub name: String,
-    // FUTURE if there's a requirement to store other EDA specific data types other than String, perhaps implement an enum named EdaPlacementValue.
+    /// The value as it appears in the placements file, see `typed_value`.
     pub value: String,
 }
 
@@ -15,6 +19,37 @@ impl EdaPlacementField {
             value,
         }
     }
+
+    pub fn typed_value(&self) -> EdaPlacementValue {
+        EdaPlacementValue::from(self.value.as_str())
+    }
+}
+
+/// A field value, values that are quantities, e.g. `10k`, `4k7` or `100nF`, are parsed so they can be compared by
+/// value, e.g. `10k` and `10000` are the same resistance.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum EdaPlacementValue {
+    String(String),
+    Quantity(Quantity),
+}
+
+impl EdaPlacementValue {
+    /// Quantities are equivalent when they have the same value and compatible units, other values must be the same.
+    pub fn is_equivalent(&self, other: &EdaPlacementValue) -> bool {
+        match (self, other) {
+            (EdaPlacementValue::Quantity(quantity), EdaPlacementValue::Quantity(other_quantity)) => quantity.is_equivalent(other_quantity),
+            _ => self.eq(other),
+        }
+    }
+}
+
+impl From<&str> for EdaPlacementValue {
+    fn from(value: &str) -> Self {
+        match Quantity::from_str(value) {
+            Ok(quantity) => EdaPlacementValue::Quantity(quantity),
+            Err(_) => EdaPlacementValue::String(value.to_string()),
+        }
+    }
 }
 
 /// Uses right-handed cartesian coordinate system
@@ -23,6 +58,8 @@ impl EdaPlacementField {
 pub struct EdaPlacement {
     pub ref_des: String,
     pub place: bool,
+    /// Why the placement is not placed, `None` when it is placed.
+    pub disable_reason: Option<EdaPlacementDisableReason>,
     pub fields: Vec<EdaPlacementField>,
     pub pcb_side: PcbSide,
     
@@ -40,6 +77,7 @@ impl Default for EdaPlacement {
         Self {
             ref_des: "".to_string(),
             place: false,
+            disable_reason: None,
             fields: vec![],
             pcb_side: PcbSide::Top,
             x: Default::default(),
@@ -47,4 +85,168 @@ impl Default for EdaPlacement {
             rotation: Default::default(),
         }
     }
-}
\ No newline at end of file
+}
+
+/// Names of the placement attributes that criteria can match, in addition to the fields.
+///
+/// The `pcb_side` value is `top` or `bottom`, the coordinates and rotation are numbers, e.g. `[10..50]` matches an
+/// `x` coordinate between 10 and 50.
+pub const EDA_PLACEMENT_ATTRIBUTE_NAMES: [&str; 5] = ["ref_des", "pcb_side", "x", "y", "rotation"];
+
+impl EdaPlacement {
+    /// Matches when all the criteria match the fields or attributes, an empty list of criteria does not match.
+    pub fn matches_criteria(&self, criteria: &[Box<dyn FieldCriterion>]) -> bool {
+        let pcb_side = match self.pcb_side {
+            PcbSide::Top => "top",
+            PcbSide::Bottom => "bottom",
+        };
+        let (x, y, rotation) = (self.x.to_string(), self.y.to_string(), self.rotation.to_string());
+        let attribute_values = [self.ref_des.as_str(), pcb_side, x.as_str(), y.as_str(), rotation.as_str()];
+
+        let fields: Vec<(&str, &str)> = self.fields.iter()
+            .map(|field| (field.name.as_str(), field.value.as_str()))
+            .chain(EDA_PLACEMENT_ATTRIBUTE_NAMES.into_iter().zip(attribute_values))
+            .collect();
+
+        !criteria.is_empty() && criteria.iter().all(|criterion| criterion.matches_fields(&fields))
+    }
+
+    /// The first reason is kept if the placement is already disabled.
+    pub fn disable(&mut self, reason: EdaPlacementDisableReason) {
+        self.place = false;
+        if self.disable_reason.is_none() {
+            self.disable_reason = Some(reason);
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum EdaPlacementDisableReason {
+    /// The EDA tool flags the placement as 'do not populate', e.g. the KiCad `dnp` footprint attribute.
+    DoNotPopulate,
+    /// The KiCad `exclude_from_pos_files` footprint attribute.
+    ExcludedFromPositionFiles,
+    /// A field that marks the placement as not fitted, e.g. a 'DNP' column or a 'No Pop' comment.
+    Marker { field_name: String, value: String },
+    /// Listed in the ref-des disable list.
+    DisableList,
+}
+
+impl Display for EdaPlacementDisableReason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EdaPlacementDisableReason::DoNotPopulate => write!(f, "do not populate"),
+            EdaPlacementDisableReason::ExcludedFromPositionFiles => write!(f, "excluded from position files"),
+            EdaPlacementDisableReason::Marker { field_name, value } => write!(f, "{}: '{}'", field_name, value),
+            EdaPlacementDisableReason::DisableList => write!(f, "ref-des disable list"),
+        }
+    }
+}
+
+/// Values, compared without case and punctuation, that mark a placement as not fitted, in any field.
+const NOT_FITTED_VALUES: [&str; 9] = ["dnp", "dnf", "nopop", "nopopulate", "donotplace", "donotpopulate", "notfitted", "nofit", "notmounted"];
+/// Flag fields, e.g. 'DNP', are set when they have one of these values.
+const SET_FLAG_VALUES: [&str; 5] = ["yes", "y", "true", "1", "x"];
+/// Fields that mark a placement as fitted, e.g. 'Fitted', are cleared when they have one of these values.
+const FITTED_FIELD_NAMES: [&str; 4] = ["fitted", "populate", "mounted", "place"];
+const CLEARED_FLAG_VALUES: [&str; 4] = ["no", "n", "false", "0"];
+
+/// Finds the first field that marks the placement as not fitted.
+///
+/// Handles the markers that EDA tools and common design practices use, e.g. a 'DNP' or 'Exclude From Pos Files'
+/// column that is set, a 'Fitted' column that is cleared, or a value/comment of 'DNP', 'No Pop' or 'Not Fitted'.
+pub fn find_disable_marker(fields: &[EdaPlacementField]) -> Option<EdaPlacementDisableReason> {
+    fields.iter().find(|field| {
+        let name = normalize_marker(&field.name);
+        let value = normalize_marker(&field.value);
+
+        NOT_FITTED_VALUES.contains(&value.as_str())
+            || ((NOT_FITTED_VALUES.contains(&name.as_str()) || name.eq("excludefromposfiles")) && SET_FLAG_VALUES.contains(&value.as_str()))
+            || (FITTED_FIELD_NAMES.contains(&name.as_str()) && CLEARED_FLAG_VALUES.contains(&value.as_str()))
+    }).map(|field| EdaPlacementDisableReason::Marker { field_name: field.name.clone(), value: field.value.clone() })
+}
+
+fn normalize_marker(value: &str) -> String {
+    value.chars().filter(|c| c.is_alphanumeric()).flat_map(char::to_lowercase).collect()
+}
+
+#[cfg(test)]
+mod placement_tests {
+    use rstest::rstest;
+    use std::str::FromStr;
+    use util::quantity::Quantity;
+    use criteria::{ExactMatchCriterion, FieldCriterion, QuantityMatchCriterion, QuantityRangeCriterion, RegexMatchCriterion};
+    use pnp::pcb::PcbSide;
+    use regex::Regex;
+    use rust_decimal_macros::dec;
+    use util::quantity::QuantityRange;
+    use crate::placement::{find_disable_marker, EdaPlacement, EdaPlacementDisableReason, EdaPlacementField, EdaPlacementValue};
+
+    #[rstest]
+    #[case("value", "DNP", true)]
+    #[case("comment", "No Pop", true)]
+    #[case("status", "Not Fitted", true)]
+    #[case("dnp", "Yes", true)]
+    #[case("do_not_populate", "1", true)]
+    #[case("exclude_from_pos_files", "true", true)]
+    #[case("fitted", "No", true)]
+    #[case("dnp", "", false)]
+    #[case("dnp", "No", false)]
+    #[case("fitted", "Yes", false)]
+    #[case("value", "10K", false)]
+    fn disable_markers(#[case] field_name: &str, #[case] value: &str, #[case] expect_marker: bool) {
+        // given
+        let fields = vec![
+            EdaPlacementField::new("name".to_string(), "RES_0402".to_string()),
+            EdaPlacementField::new(field_name.to_string(), value.to_string()),
+        ];
+
+        // and
+        let expected_result = expect_marker.then(|| EdaPlacementDisableReason::Marker { field_name: field_name.to_string(), value: value.to_string() });
+
+        // when
+        let result = find_disable_marker(&fields);
+
+        // then
+        assert_eq!(result, expected_result);
+    }
+
+    #[rstest]
+    #[case(Box::new(RegexMatchCriterion::new("ref_des".to_string(), Regex::new("^C").unwrap())), true)]
+    #[case(Box::new(RegexMatchCriterion::new("ref_des".to_string(), Regex::new("^R").unwrap())), false)]
+    #[case(Box::new(ExactMatchCriterion::new("pcb_side".to_string(), "bottom".to_string())), true)]
+    #[case(Box::new(QuantityRangeCriterion::new("x".to_string(), QuantityRange::from_str("10..50").unwrap())), true)]
+    #[case(Box::new(QuantityRangeCriterion::new("y".to_string(), QuantityRange::from_str("10..50").unwrap())), false)]
+    #[case(Box::new(QuantityMatchCriterion::new("rotation".to_string(), Quantity::from_str("-90").unwrap())), true)]
+    fn attribute_criteria(#[case] criterion: Box<dyn FieldCriterion>, #[case] expected_result: bool) {
+        // given
+        let placement = EdaPlacement {
+            ref_des: "C1".to_string(),
+            place: true,
+            fields: vec![EdaPlacementField::new("value".to_string(), "100nF".to_string())],
+            pcb_side: PcbSide::Bottom,
+            x: dec!(20.5),
+            y: dec!(-5),
+            rotation: dec!(-90),
+            ..EdaPlacement::default()
+        };
+
+        // expect
+        assert_eq!(placement.matches_criteria(&[criterion]), expected_result);
+    }
+
+    #[rstest]
+    #[case("10K0", EdaPlacementValue::Quantity(Quantity::from_str("10k").unwrap()))]
+    #[case("0.1uF", EdaPlacementValue::Quantity(Quantity::from_str("100nF").unwrap()))]
+    #[case("10uF 6.3V 20%", EdaPlacementValue::String("10uF 6.3V 20%".to_string()))]
+    fn typed_value(#[case] value: &str, #[case] expected_value: EdaPlacementValue) {
+        // given
+        let field = EdaPlacementField::new("value".to_string(), value.to_string());
+
+        // when
+        let result = field.typed_value();
+
+        // then
+        assert!(result.is_equivalent(&expected_value));
+    }
+}
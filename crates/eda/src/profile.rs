@@ -0,0 +1,224 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use thiserror::Error;
+use pnp::pcb::PcbSide;
+use crate::placement::{EdaPlacement, EdaPlacementField};
+use crate::rotation::RotationConverter;
+use crate::units::LengthUnit;
+
+/// A declarative description of a CSV placements export, for EDA tools that do not have built-in support.
+///
+/// Example (JSON):
+/// ```json
+/// {
+///     "name": "FabExport",
+///     "delimiter": ";",
+///     "columns": { "ref_des": "Ref", "x": "PosX", "y": "PosY", "rotation": "Rot", "side": "Side" },
+///     "sides": { "top": ["top", "T"], "bottom": ["bottom", "B"] },
+///     "rotation_direction": "clockwise",
+///     "units": "mils",
+///     "fields": [
+///         { "name": "value", "column": "Val" },
+///         { "name": "package", "column": "Package" }
+///     ]
+/// }
+/// ```
+///
+/// The name is used to refer to the profile in the 'Eda' column of the substitution and part-mapping files.
+/// Only one profile is used at a time, the one the placements are loaded with, so the files can refer to the built-in
+/// EDA tools and that profile, but not to other profiles.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+pub struct EdaProfile {
+    pub name: String,
+    /// A single-byte (ASCII) character, profiles with other delimiters are rejected when they are deserialized.
+    #[serde(default, deserialize_with = "deserialize_delimiter")]
+    pub delimiter: Option<char>,
+    pub columns: EdaProfileColumns,
+    pub sides: EdaProfileSides,
+    #[serde(default)]
+    pub rotation_direction: EdaProfileRotationDirection,
+    #[serde(default)]
+    pub units: LengthUnit,
+    pub fields: Vec<EdaProfileField>,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+pub struct EdaProfileColumns {
+    pub ref_des: String,
+    pub x: String,
+    pub y: String,
+    pub rotation: String,
+    pub side: String,
+}
+
+/// Side values are matched case-insensitively.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+pub struct EdaProfileSides {
+    pub top: Vec<String>,
+    pub bottom: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EdaProfileRotationDirection {
+    #[default]
+    AntiClockwise,
+    Clockwise,
+}
+
+/// A column that becomes an `EdaPlacementField`.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+pub struct EdaProfileField {
+    pub name: String,
+    pub column: String,
+}
+
+fn deserialize_delimiter<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Option<char>, D::Error> {
+    let delimiter: Option<char> = serde::Deserialize::deserialize(deserializer)?;
+    match delimiter {
+        Some(delimiter) if !delimiter.is_ascii() => Err(serde::de::Error::custom(
+            format!("Invalid delimiter, only single-byte ASCII delimiters are supported. delimiter: '{}'", delimiter)
+        )),
+        _ => Ok(delimiter),
+    }
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum EdaProfileError {
+    #[error("Missing column. column: '{0}'")]
+    MissingColumn(String),
+    #[error("Invalid number. column: '{column}', value: '{value}'")]
+    InvalidNumber { column: String, value: String },
+    #[error("Unknown side. value: '{0}'")]
+    UnknownSide(String),
+}
+
+impl EdaProfile {
+    pub fn field_names(&self) -> Vec<String> {
+        self.fields.iter().map(|field| field.name.clone()).collect()
+    }
+
+    /// The record is a row of the placements file, keyed by column name.
+    pub fn build_eda_placement(&self, record: &HashMap<String, String>) -> Result<EdaPlacement, EdaProfileError> {
+        let column = |name: &String| record.get(name)
+            .ok_or(EdaProfileError::MissingColumn(name.clone()));
+
+        let parse_decimal = |name: &String| column(name).and_then(|value| Decimal::from_str(value.trim())
+            .map_err(|_err| EdaProfileError::InvalidNumber { column: name.clone(), value: value.clone() }));
+
+        let side = column(&self.columns.side)?;
+        let pcb_side = if self.sides.top.iter().any(|candidate| candidate.eq_ignore_ascii_case(side.trim())) {
+            PcbSide::Top
+        } else if self.sides.bottom.iter().any(|candidate| candidate.eq_ignore_ascii_case(side.trim())) {
+            PcbSide::Bottom
+        } else {
+            return Err(EdaProfileError::UnknownSide(side.clone()))
+        };
+
+        let rotation = match self.rotation_direction {
+            EdaProfileRotationDirection::AntiClockwise => parse_decimal(&self.columns.rotation)?,
+            // subtracting from 360 instead of negating avoids a negative zero
+            EdaProfileRotationDirection::Clockwise => dec!(360) - parse_decimal(&self.columns.rotation)?,
+        };
+
+        let fields = self.fields.iter().map(|field| {
+            column(&field.column).map(|value| EdaPlacementField { name: field.name.clone(), value: value.clone() })
+        }).collect::<Result<Vec<_>, _>>()?;
+
+        Ok(EdaPlacement {
+            ref_des: column(&self.columns.ref_des)?.clone(),
+            place: true,
+            disable_reason: None,
+            fields,
+            pcb_side,
+            x: self.units.to_millimeters(parse_decimal(&self.columns.x)?),
+            y: self.units.to_millimeters(parse_decimal(&self.columns.y)?),
+            rotation: RotationConverter::convert(rotation),
+        })
+    }
+}
+
+#[cfg(test)]
+mod eda_profile_tests {
+    use std::collections::HashMap;
+    use rust_decimal_macros::dec;
+    use pnp::pcb::PcbSide;
+    use crate::placement::{EdaPlacement, EdaPlacementField};
+    use crate::profile::{EdaProfile, EdaProfileColumns, EdaProfileError, EdaProfileField, EdaProfileRotationDirection, EdaProfileSides};
+    use crate::units::LengthUnit;
+
+    fn build_profile() -> EdaProfile {
+        EdaProfile {
+            name: "FabExport".to_string(),
+            delimiter: None,
+            columns: EdaProfileColumns {
+                ref_des: "Ref".to_string(),
+                x: "PosX".to_string(),
+                y: "PosY".to_string(),
+                rotation: "Rot".to_string(),
+                side: "Side".to_string(),
+            },
+            sides: EdaProfileSides {
+                top: vec!["top".to_string(), "T".to_string()],
+                bottom: vec!["bottom".to_string(), "B".to_string()],
+            },
+            rotation_direction: EdaProfileRotationDirection::Clockwise,
+            units: LengthUnit::Mils,
+            fields: vec![
+                EdaProfileField { name: "value".to_string(), column: "Val".to_string() },
+                EdaProfileField { name: "package".to_string(), column: "Package".to_string() },
+            ],
+        }
+    }
+
+    fn build_record(values: &[(&str, &str)]) -> HashMap<String, String> {
+        values.iter().map(|(column, value)| (column.to_string(), value.to_string())).collect()
+    }
+
+    #[test]
+    fn build_eda_placement() {
+        // given
+        let profile = build_profile();
+
+        // and
+        let record = build_record(&[("Ref", "R1"), ("Val", "10k"), ("Package", "0402"), ("PosX", "500"), ("PosY", "-4000"), ("Rot", "90"), ("Side", "b")]);
+
+        // and
+        let expected_result = EdaPlacement {
+            ref_des: "R1".to_string(),
+            place: true,
+            disable_reason: None,
+            fields: vec![
+                EdaPlacementField::new("value".to_string(), "10k".to_string()),
+                EdaPlacementField::new("package".to_string(), "0402".to_string()),
+            ],
+            pcb_side: PcbSide::Bottom,
+            x: dec!(12.7),
+            y: dec!(-101.6),
+            rotation: dec!(-90),
+        };
+
+        // when
+        let result = profile.build_eda_placement(&record);
+
+        // then
+        assert_eq!(result, Ok(expected_result));
+    }
+
+    #[test]
+    fn unknown_side() {
+        // given
+        let profile = build_profile();
+
+        // and
+        let record = build_record(&[("Ref", "R1"), ("Val", "10k"), ("Package", "0402"), ("PosX", "0"), ("PosY", "0"), ("Rot", "0"), ("Side", "inner")]);
+
+        // when
+        let result = profile.build_eda_placement(&record);
+
+        // then
+        assert_eq!(result, Err(EdaProfileError::UnknownSide("inner".to_string())));
+    }
+}
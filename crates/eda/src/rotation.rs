@@ -0,0 +1,40 @@
+use std::ops::{Add, Sub};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+
+/// Converts rotations into the range used by `EdaPlacement`, i.e. >-180 to +180.
+pub struct RotationConverter {}
+impl RotationConverter {
+    pub fn convert(mut input: Decimal) -> Decimal {
+        while input >= dec!(360) {
+            input = input.sub(dec!(360));
+        }
+        while input < dec!(0) {
+            input = input.add( dec!(360));
+        }
+        if input > dec!(180) {
+            input = input.sub(dec!(360));
+        }
+        input
+    }
+}
+
+#[cfg(test)]
+mod rotation_conversion_tests {
+
+    use rstest::rstest;
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+    use crate::rotation::RotationConverter;
+
+    #[rstest]
+    #[case(dec!(0), dec!(0))]
+    #[case(dec!(180), dec!(180))]
+    #[case(dec!(-180), dec!(180))]
+    #[case(dec!(360), dec!(0))]
+    #[case(dec!(185), dec!(-175))]
+    #[case(dec!(-185), dec!(175))]
+    fn convert(#[case] value: Decimal, #[case] expected_value: Decimal) {
+        assert_eq!(RotationConverter::convert(value), expected_value);
+    }
+}
@@ -1,5 +1,7 @@
-use criteria::FieldCriterion;
-use crate::placement::EdaPlacement;
+use regex::{Captures, Regex};
+use thiserror::Error;
+use criteria::{FieldCriterion, RegexMatchCriterion};
+use crate::placement::{EdaPlacement, EdaPlacementField};
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct EdaSubstitutionRuleTransformItem {
@@ -10,7 +12,12 @@ pub struct EdaSubstitutionRuleTransformItem {
 #[derive(Debug, PartialEq)]
 pub struct EdaSubstitutionRule {
     pub criteria: Vec<Box<dyn FieldCriterion>>,
+    /// Fields that do not exist on the placement are added.
     pub transforms: Vec<EdaSubstitutionRuleTransformItem>,
+    /// Names of the fields that are removed from the placement, after the transforms are applied.
+    pub removals: Vec<String>,
+    /// The line of the file the rule was loaded from, the header is line 1.
+    pub line: Option<u64>,
 }
 
 impl EdaSubstitutionRule {
@@ -24,49 +31,154 @@ impl EdaSubstitutionRule {
     }
 
     pub fn format_transform(&self) -> String {
+        self.format_transform_items(&self.transforms)
+    }
+
+    /// Like `format_transform` but with the capture group references expanded, using the placement the rule was
+    /// applied to, e.g. `mpn: 'RC0402FR-0710KL'` instead of `mpn: 'RC${1}FR-0710KL'`.
+    pub fn format_applied_transform(&self, eda_placement: &EdaPlacement) -> String {
+        self.format_transform_items(&self.expand_transforms(eda_placement))
+    }
+
+    fn format_transform_items(&self, transforms: &[EdaSubstitutionRuleTransformItem]) -> String {
         let mut result: Vec<String> = vec![];
 
-        for transform in self.transforms.iter() {
+        for transform in transforms.iter() {
             result.push(format!("{}: '{}'", transform.field_name, transform.field_value));
         }
+        for field_name in self.removals.iter() {
+            result.push(format!("remove({})", field_name));
+        }
         result.join(", ")
     }
 
-    pub fn matches(&self, eda_placement: &EdaPlacement) -> bool {
-
-        let result: Option<bool> = self.criteria.iter().fold(None, |mut matched, criterion| {
-            let matched_field = eda_placement.fields.iter().find(|field | {
-                (*criterion).matches(field.name.as_str(), field.value.as_str())
-            });
-
-            match (&mut matched, matched_field) {
-                // matched, previous fields checked
-                (Some(accumulated_result), Some(_field)) => *accumulated_result &= true,
-                // matched, first field
-                (None, Some(_field)) => matched = Some(true),
-                // not matched, previous fields checked
-                (Some(accumulated_result), None) => *accumulated_result = false,
-                // not matched, first field
-                (None, None) => matched = Some(false),
-            }
-
-            matched
-        });
+    /// e.g. `(name_pattern: 'NAME1') -> (name: 'NAME2')`
+    pub fn format(&self) -> String {
+        format!("({}) -> ({})", self.format_criteria(), self.format_transform())
+    }
 
-        result.unwrap_or(false)
+    pub fn matches(&self, eda_placement: &EdaPlacement) -> bool {
+        eda_placement.matches_criteria(&self.criteria)
     }
 
+    /// Transform values can refer to the capture groups of the regular expression criteria, using `$1`, `${1}` or
+    /// `${name}`, e.g. a `footprint_pattern` of `/^R_(\d{4})_/` and a `footprint` of `R${1}` transforms
+    /// `R_0402_1005Metric` into `R0402`.  Use `$$` for a literal `$`.
+    ///
+    /// The captures of the criterion for the same field are used, otherwise the captures of the first regular
+    /// expression criterion.
+    ///
+    /// Transforms for fields that the placement does not have add the field, e.g. an `mpn` derived from the value and
+    /// package, then the fields in `removals` are removed.
     pub fn apply(&self, eda_placement: &EdaPlacement) -> EdaPlacement {
-        let result = self.transforms.iter().fold(eda_placement.clone(), |mut placement, change_item| {
-            if let Some(field) = placement.fields.iter_mut().find(|field| field.name.eq(change_item.field_name.as_str())) {
-                field.value.clone_from(&change_item.field_value);
+        let mut result = self.expand_transforms(eda_placement).into_iter().fold(eda_placement.clone(), |mut placement, change_item| {
+            match placement.fields.iter_mut().find(|field| field.name.eq(change_item.field_name.as_str())) {
+                Some(field) => field.value = change_item.field_value,
+                None => placement.fields.push(EdaPlacementField::new(change_item.field_name, change_item.field_value)),
             }
 
             placement
         });
 
+        result.fields.retain(|field| !self.removals.contains(&field.name));
+
         result
     }
+
+    /// The transforms, with the capture group references in their values expanded, see `apply`.
+    fn expand_transforms(&self, eda_placement: &EdaPlacement) -> Vec<EdaSubstitutionRuleTransformItem> {
+        let captures = self.captures(eda_placement);
+
+        self.transforms.iter().map(|change_item| {
+            let field_captures = captures.iter()
+                .find(|(field_name, _captures)| (*field_name).eq(&change_item.field_name))
+                .or(captures.first());
+
+            let field_value = match field_captures {
+                Some((_field_name, field_captures)) => {
+                    let mut value = String::new();
+                    field_captures.expand(&change_item.field_value, &mut value);
+                    value
+                },
+                None => change_item.field_value.clone(),
+            };
+
+            EdaSubstitutionRuleTransformItem { field_name: change_item.field_name.clone(), field_value }
+        }).collect()
+    }
+
+    /// (field name, reference) for each capture group reference in the transform values that the regular expression
+    /// used to expand it does not have, e.g. `$1x` refers to a group named `1x`, `${1}x` was probably intended.
+    ///
+    /// The values of rules without regular expression criteria are not expanded, so they have no references.
+    pub fn unknown_capture_group_references(&self) -> Vec<(String, String)> {
+        let patterns: Vec<(&String, &Regex)> = self.criteria.iter()
+            .filter_map(|criterion| (**criterion).as_any().downcast_ref::<RegexMatchCriterion>())
+            .map(|criterion| (&criterion.field_name, &criterion.field_pattern))
+            .collect();
+
+        self.transforms.iter().flat_map(|change_item| {
+            let field_pattern = patterns.iter()
+                .find(|(field_name, _pattern)| (*field_name).eq(&change_item.field_name))
+                .or(patterns.first());
+
+            let unknown_references: Vec<String> = match field_pattern {
+                Some((_field_name, pattern)) => capture_group_references(&change_item.field_value).into_iter()
+                    .filter(|reference| match reference.parse::<usize>() {
+                        Ok(index) => index >= pattern.captures_len(),
+                        Err(_) => !pattern.capture_names().flatten().any(|name| name.eq(reference)),
+                    })
+                    .collect(),
+                None => vec![],
+            };
+
+            unknown_references.into_iter().map(|reference| (change_item.field_name.clone(), reference))
+        }).collect()
+    }
+
+    /// (field name, captures) for each regular expression criterion that matches a field of the placement.
+    fn captures<'a>(&'a self, eda_placement: &'a EdaPlacement) -> Vec<(&'a String, Captures<'a>)> {
+        self.criteria.iter()
+            .filter_map(|criterion| (**criterion).as_any().downcast_ref::<RegexMatchCriterion>())
+            .filter_map(|criterion| {
+                eda_placement.fields.iter()
+                    .find(|field| field.name.eq(&criterion.field_name))
+                    .and_then(|field| criterion.field_pattern.captures(&field.value))
+                    .map(|captures| (&criterion.field_name, captures))
+            })
+            .collect()
+    }
+}
+
+/// The capture group references in a value, using the same syntax as `Captures::expand`, e.g. `1`, `2` and `size` for
+/// `$1_${2}_$size`, `$$` is a literal `$`.
+fn capture_group_references(value: &str) -> Vec<String> {
+    let mut references = vec![];
+    let mut remaining = value;
+
+    while let Some(index) = remaining.find('$') {
+        remaining = &remaining[index + 1..];
+
+        if let Some(rest) = remaining.strip_prefix('$') {
+            remaining = rest;
+            continue
+        }
+
+        // a '${' without a closing brace is a literal '$'
+        if let Some((name, rest)) = remaining.strip_prefix('{').and_then(|rest| rest.split_once('}')) {
+            references.push(name.to_string());
+            remaining = rest;
+            continue
+        }
+
+        let name_length = remaining.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_')).unwrap_or(remaining.len());
+        if name_length > 0 {
+            references.push(remaining[..name_length].to_string());
+            remaining = &remaining[name_length..];
+        }
+    }
+
+    references
 }
 
 #[derive(Debug, PartialEq)]
@@ -81,17 +193,35 @@ pub struct EdaSubstitutionResult<'placement, 'rule> {
     pub chain: Vec<EdaSubstitutionChainEntry<'rule>>,
 }
 
+#[derive(Error, Debug, PartialEq)]
+pub enum EdaSubstitutionError {
+    /// The rules, in the order they were applied, substitute a placement back to a previous state.
+    #[error("Substitution rules form a cycle. ref_des: {ref_des}, rules: [{}]", rules.join(", "))]
+    Cycle { ref_des: String, rules: Vec<String> },
+
+    /// A rule that keeps changing a placement, e.g. a value that grows with each pass, never reaches a previous state
+    /// so it cannot be detected as a cycle.
+    #[error("Substitution chain exceeds the limit. ref_des: {ref_des}, limit: {limit}, rule: {rule}")]
+    ChainTooLong { ref_des: String, limit: usize, rule: String },
+}
+
+/// The maximum number of substitutions that can be applied to a single placement.
+pub const MAX_SUBSTITUTION_CHAIN_LENGTH: usize = 100;
+
 pub struct EdaSubstitutor {}
 
 impl EdaSubstitutor {
-    pub fn substitute<'placement, 'rule>(original_eda_placements: &'placement [EdaPlacement], eda_substitution_rules: &'rule [EdaSubstitutionRule]) -> Vec<EdaSubstitutionResult<'placement, 'rule>> {
+    pub fn substitute<'placement, 'rule>(original_eda_placements: &'placement [EdaPlacement], eda_substitution_rules: &'rule [EdaSubstitutionRule]) -> Result<Vec<EdaSubstitutionResult<'placement, 'rule>>, EdaSubstitutionError> {
 
         let mut results = vec![];
 
         for original_eda_placement in original_eda_placements.iter() {
 
             let mut eda_placement = original_eda_placement.clone();
-            let mut chain = vec![];
+            let mut chain: Vec<EdaSubstitutionChainEntry> = vec![];
+
+            // each state the placement has been in, and the length of the chain when it was reached
+            let mut seen_states: Vec<(EdaPlacement, usize)> = vec![(eda_placement.clone(), 0)];
 
             loop {
                 let mut applied_rule_count_this_pass = 0;
@@ -108,6 +238,21 @@ impl EdaSubstitutor {
                         if eda_placement.ne(&original_eda_placement) {
                             applied_rule_count_this_pass += 1;
                             chain.push(EdaSubstitutionChainEntry { rule });
+
+                            if let Some((_state, chain_length)) = seen_states.iter().find(|(state, _chain_length)| state.eq(&eda_placement)) {
+                                return Err(EdaSubstitutionError::Cycle {
+                                    ref_des: eda_placement.ref_des.clone(),
+                                    rules: chain[*chain_length..].iter().map(|entry| entry.rule.format()).collect(),
+                                })
+                            }
+                            if chain.len() > MAX_SUBSTITUTION_CHAIN_LENGTH {
+                                return Err(EdaSubstitutionError::ChainTooLong {
+                                    ref_des: eda_placement.ref_des.clone(),
+                                    limit: MAX_SUBSTITUTION_CHAIN_LENGTH,
+                                    rule: rule.format(),
+                                })
+                            }
+                            seen_states.push((eda_placement.clone(), chain.len()));
                         }
                     }
                 }
@@ -124,7 +269,7 @@ impl EdaSubstitutor {
             })
         }
 
-        results
+        Ok(results)
     }
 }
 
@@ -133,7 +278,7 @@ pub mod eda_substitutor_tests {
     use regex::Regex;
     use criteria::{ExactMatchCriterion, RegexMatchCriterion};
     use crate::placement::{EdaPlacement, EdaPlacementField };
-    use crate::substitution::{EdaSubstitutionRule, EdaSubstitutionResult, EdaSubstitutor, EdaSubstitutionChainEntry, EdaSubstitutionRuleTransformItem};
+    use crate::substitution::{EdaSubstitutionRule, EdaSubstitutionResult, EdaSubstitutor, EdaSubstitutionChainEntry, EdaSubstitutionRuleTransformItem, EdaSubstitutionError, MAX_SUBSTITUTION_CHAIN_LENGTH};
     
     #[test]
     pub fn substitute_one_diptrace_placement_using_a_chain() {
@@ -159,6 +304,8 @@ pub mod eda_substitutor_tests {
                 EdaSubstitutionRuleTransformItem { field_name: "name".to_string(), field_value: "INTERMEDIATE_NAME1".to_string() },
                 EdaSubstitutionRuleTransformItem { field_name: "value".to_string(), field_value: "INTERMEDIATE_VALUE1".to_string() }
             ],
+            removals: vec![],
+            line: None,
         };
         let second_eda_substitution_rule = EdaSubstitutionRule {
             criteria: vec![
@@ -169,6 +316,8 @@ pub mod eda_substitutor_tests {
                 EdaSubstitutionRuleTransformItem { field_name: "name".to_string(), field_value: "SUBSTITUTED_NAME1".to_string() },
                 EdaSubstitutionRuleTransformItem { field_name: "value".to_string(), field_value: "SUBSTITUTED_VALUE1".to_string() }
             ],
+            removals: vec![],
+            line: None,
         };
         // and a list of rules, that are out-of-order (i.e. eda_substitution1 must be applied first)
         let eda_substitution_rules = vec![second_eda_substitution_rule, first_eda_substitution_rule];
@@ -197,7 +346,7 @@ pub mod eda_substitutor_tests {
         let results = EdaSubstitutor::substitute(
             eda_placements.as_slice(),
             eda_substitution_rules.as_slice()
-        );
+        ).unwrap();
 
         // then
         assert_eq!(results, expected_results);
@@ -226,6 +375,8 @@ pub mod eda_substitutor_tests {
                 EdaSubstitutionRuleTransformItem { field_name: "package".to_string(), field_value: "INTERMEDIATE_PACKAGE1".to_string() },
                 EdaSubstitutionRuleTransformItem { field_name: "val".to_string(), field_value: "INTERMEDIATE_VAL1".to_string() }
             ],
+            removals: vec![],
+            line: None,
         };
         let second_eda_substitution = EdaSubstitutionRule {
             criteria: vec![
@@ -236,6 +387,8 @@ pub mod eda_substitutor_tests {
                 EdaSubstitutionRuleTransformItem { field_name: "package".to_string(), field_value: "SUBSTITUTED_PACKAGE1".to_string() },
                 EdaSubstitutionRuleTransformItem { field_name: "val".to_string(), field_value: "SUBSTITUTED_VAL1".to_string() }
             ],
+            removals: vec![],
+            line: None,
         };
         // and a list of rules, that are out-of-order (i.e. eda_substitution1 must be applied first)
         let eda_substitutions= vec![second_eda_substitution, first_eda_substitution];
@@ -263,7 +416,7 @@ pub mod eda_substitutor_tests {
         let results = EdaSubstitutor::substitute(
             eda_placements.as_slice(),
             eda_substitutions.as_slice()
-        );
+        ).unwrap();
 
         // then
         assert_eq!(results, expected_results);
@@ -292,6 +445,8 @@ pub mod eda_substitutor_tests {
                 EdaSubstitutionRuleTransformItem { field_name: "name".to_string(), field_value: "NAME1".to_string() },
                 EdaSubstitutionRuleTransformItem { field_name: "value".to_string(), field_value: "SUBSTITUTED_VALUE1".to_string() }
             ],
+            removals: vec![],
+            line: None,
         };
         // and a list of rules, that are out-of-order (i.e. eda_substitution1 must be applied first)
         let eda_substitution_rules = vec![eda_substitution_rule];
@@ -319,9 +474,230 @@ pub mod eda_substitutor_tests {
         let results = EdaSubstitutor::substitute(
             eda_placements.as_slice(),
             eda_substitution_rules.as_slice()
-        );
+        ).unwrap();
 
         // then
         assert_eq!(results, expected_results);
     }
+
+    #[test]
+    pub fn substitute_using_rules_that_form_a_cycle() {
+        // given
+        let eda_placement1 = EdaPlacement {
+            ref_des: "R1".to_string(),
+            fields: vec![
+                EdaPlacementField::new("name".to_string(), "NAME1".to_string()),
+                EdaPlacementField::new("value".to_string(), "VALUE1".to_string()),
+            ],
+            ..EdaPlacement::default()
+        };
+        let eda_placements= vec![eda_placement1];
+
+        // and two rules that undo each other
+
+        let eda_substitution_rules = vec![
+            EdaSubstitutionRule {
+                criteria: vec![Box::new(ExactMatchCriterion { field_name: "value".to_string(), field_pattern: "VALUE1".to_string() })],
+                transforms: vec![EdaSubstitutionRuleTransformItem { field_name: "value".to_string(), field_value: "VALUE2".to_string() }],
+                removals: vec![],
+                line: None,
+            },
+            EdaSubstitutionRule {
+                criteria: vec![Box::new(ExactMatchCriterion { field_name: "value".to_string(), field_pattern: "VALUE2".to_string() })],
+                transforms: vec![EdaSubstitutionRuleTransformItem { field_name: "value".to_string(), field_value: "VALUE1".to_string() }],
+                removals: vec![],
+                line: None,
+            },
+        ];
+
+        // and
+        let expected_result = Err(EdaSubstitutionError::Cycle {
+            ref_des: "R1".to_string(),
+            rules: vec![
+                "(value_pattern: 'VALUE1') -> (value: 'VALUE2')".to_string(),
+                "(value_pattern: 'VALUE2') -> (value: 'VALUE1')".to_string(),
+            ],
+        });
+
+        // when
+        let result = EdaSubstitutor::substitute(
+            eda_placements.as_slice(),
+            eda_substitution_rules.as_slice()
+        );
+
+        // then
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    pub fn substitute_using_a_rule_that_never_stops_changing_the_placement() {
+        // given
+        let eda_placement1 = EdaPlacement {
+            ref_des: "R1".to_string(),
+            fields: vec![
+                EdaPlacementField::new("value".to_string(), "VALUE1".to_string()),
+            ],
+            ..EdaPlacement::default()
+        };
+        let eda_placements= vec![eda_placement1];
+
+        // and a rule that appends to the value every time it is applied
+
+        let eda_substitution_rules = vec![
+            EdaSubstitutionRule {
+                criteria: vec![Box::new(RegexMatchCriterion { field_name: "value".to_string(), field_pattern: Regex::new("^(.*)$").unwrap() })],
+                transforms: vec![EdaSubstitutionRuleTransformItem { field_name: "value".to_string(), field_value: "${1}x".to_string() }],
+                removals: vec![],
+                line: None,
+            },
+        ];
+
+        // and
+        let expected_result = Err(EdaSubstitutionError::ChainTooLong {
+            ref_des: "R1".to_string(),
+            limit: MAX_SUBSTITUTION_CHAIN_LENGTH,
+            rule: "(value_pattern: '^(.*)$') -> (value: '${1}x')".to_string(),
+        });
+
+        // when
+        let result = EdaSubstitutor::substitute(
+            eda_placements.as_slice(),
+            eda_substitution_rules.as_slice()
+        );
+
+        // then
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    pub fn substitute_using_capture_groups() {
+        // given
+        let eda_placements = vec![
+            EdaPlacement {
+                ref_des: "R1".to_string(),
+                fields: vec![
+                    EdaPlacementField::new("package".to_string(), "R_0402_1005Metric".to_string()),
+                    EdaPlacementField::new("val".to_string(), "10k".to_string()),
+                ],
+                ..EdaPlacement::default()
+            },
+            EdaPlacement {
+                ref_des: "C1".to_string(),
+                fields: vec![
+                    EdaPlacementField::new("package".to_string(), "C_0603_1608Metric".to_string()),
+                    EdaPlacementField::new("val".to_string(), "100nF".to_string()),
+                ],
+                ..EdaPlacement::default()
+            },
+        ];
+
+        // and one rule for the whole family of footprints
+        let eda_substitution_rules = vec![
+            EdaSubstitutionRule {
+                criteria: vec![
+                    Box::new(RegexMatchCriterion { field_name: "package".to_string(), field_pattern: Regex::new("^(?<kind>[RC])_(\\d{4})_\\d{4}Metric$").unwrap() }),
+                    Box::new(RegexMatchCriterion { field_name: "val".to_string(), field_pattern: Regex::new("^(.*)$").unwrap() }),
+                ],
+                transforms: vec![
+                    EdaSubstitutionRuleTransformItem { field_name: "package".to_string(), field_value: "${kind}$2".to_string() },
+                    EdaSubstitutionRuleTransformItem { field_name: "val".to_string(), field_value: "$1 $$".to_string() },
+                ],
+                removals: vec![],
+                line: None,
+            },
+        ];
+
+        // when
+        let results = EdaSubstitutor::substitute(
+            eda_placements.as_slice(),
+            eda_substitution_rules.as_slice()
+        ).unwrap();
+
+        // then
+        let resulting_fields: Vec<_> = results.iter().map(|result| result.resulting_placement.fields.clone()).collect();
+        assert_eq!(resulting_fields, vec![
+            vec![
+                EdaPlacementField::new("package".to_string(), "R0402".to_string()),
+                EdaPlacementField::new("val".to_string(), "10k $".to_string()),
+            ],
+            vec![
+                EdaPlacementField::new("package".to_string(), "C0603".to_string()),
+                EdaPlacementField::new("val".to_string(), "100nF $".to_string()),
+            ],
+        ]);
+    }
+
+    #[test]
+    pub fn substitute_adding_and_removing_fields() {
+        // given
+        let eda_placements = vec![
+            EdaPlacement {
+                ref_des: "R1".to_string(),
+                fields: vec![
+                    EdaPlacementField::new("package".to_string(), "R_0402_1005Metric".to_string()),
+                    EdaPlacementField::new("val".to_string(), "10k".to_string()),
+                    EdaPlacementField::new("description".to_string(), "Resistor".to_string()),
+                ],
+                ..EdaPlacement::default()
+            },
+        ];
+
+        // and a rule that derives an 'mpn' field from the value and package, and drops the description
+        let eda_substitution_rules = vec![
+            EdaSubstitutionRule {
+                criteria: vec![
+                    Box::new(RegexMatchCriterion { field_name: "package".to_string(), field_pattern: Regex::new("^R_(\\d{4})_\\d{4}Metric$").unwrap() }),
+                    Box::new(ExactMatchCriterion { field_name: "val".to_string(), field_pattern: "10k".to_string() }),
+                ],
+                transforms: vec![
+                    EdaSubstitutionRuleTransformItem { field_name: "mpn".to_string(), field_value: "RC${1}FR-0710KL".to_string() },
+                ],
+                removals: vec!["description".to_string()],
+                line: None,
+            },
+        ];
+
+        // when
+        let results = EdaSubstitutor::substitute(
+            eda_placements.as_slice(),
+            eda_substitution_rules.as_slice()
+        ).unwrap();
+
+        // then
+        assert_eq!(results[0].resulting_placement.fields, vec![
+            EdaPlacementField::new("package".to_string(), "R_0402_1005Metric".to_string()),
+            EdaPlacementField::new("val".to_string(), "10k".to_string()),
+            EdaPlacementField::new("mpn".to_string(), "RC0402FR-0710KL".to_string()),
+        ]);
+
+        // and
+        assert_eq!(eda_substitution_rules[0].format_transform(), "mpn: 'RC${1}FR-0710KL', remove(description)");
+        // and
+        assert_eq!(eda_substitution_rules[0].format_applied_transform(&eda_placements[0]), "mpn: 'RC0402FR-0710KL', remove(description)");
+    }
+
+    #[test]
+    pub fn unknown_capture_group_references() {
+        // given a rule with a numbered and a named group
+        let eda_substitution_rule = EdaSubstitutionRule {
+            criteria: vec![
+                Box::new(RegexMatchCriterion { field_name: "package".to_string(), field_pattern: Regex::new("^R_(\\d{4})_(?<size>\\d{4})Metric$").unwrap() }),
+            ],
+            transforms: vec![
+                EdaSubstitutionRuleTransformItem { field_name: "package".to_string(), field_value: "R${1}_$size".to_string() },
+                EdaSubstitutionRuleTransformItem { field_name: "mpn".to_string(), field_value: "RC$1x-FR-07$$10KL-$2-${name}".to_string() },
+            ],
+            removals: vec![],
+            line: None,
+        };
+
+        // when
+        let result = eda_substitution_rule.unknown_capture_group_references();
+
+        // then
+        assert_eq!(result, vec![
+            ("mpn".to_string(), "1x".to_string()),
+            ("mpn".to_string(), "name".to_string()),
+        ]);
+    }
 }
@@ -0,0 +1,94 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use thiserror::Error;
+
+/// `EdaPlacement` coordinates are always in millimeters, other units are converted when loading.
+#[derive(Debug, Clone, Copy, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LengthUnit {
+    #[default]
+    Millimeters,
+    Micrometers,
+    Inches,
+    Mils,
+}
+
+impl LengthUnit {
+    pub fn to_millimeters(self, value: Decimal) -> Decimal {
+        match self {
+            LengthUnit::Millimeters => value,
+            LengthUnit::Micrometers => value / dec!(1000),
+            LengthUnit::Inches => value * dec!(25.4),
+            LengthUnit::Mils => value * dec!(0.0254),
+        }
+    }
+}
+
+#[derive(Error, Debug, PartialEq)]
+#[error("Unknown length unit. value: '{0}'")]
+pub struct UnknownLengthUnitError(pub String);
+
+impl FromStr for LengthUnit {
+    type Err = UnknownLengthUnitError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "mm" | "millimeter" | "millimeters" | "millimetre" | "millimetres" => Ok(LengthUnit::Millimeters),
+            "um" | "µm" | "micron" | "microns" | "micrometer" | "micrometers" => Ok(LengthUnit::Micrometers),
+            "in" | "inch" | "inches" => Ok(LengthUnit::Inches),
+            "mil" | "mils" | "thou" => Ok(LengthUnit::Mils),
+            _ => Err(UnknownLengthUnitError(value.to_string())),
+        }
+    }
+}
+
+impl Display for LengthUnit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LengthUnit::Millimeters => write!(f, "mm"),
+            LengthUnit::Micrometers => write!(f, "um"),
+            LengthUnit::Inches => write!(f, "in"),
+            LengthUnit::Mils => write!(f, "mil"),
+        }
+    }
+}
+
+/// Splits a unit suffix from a column header, e.g. `Center X (mil)` -> (`Center X`, Some(LengthUnit::Mils)).
+///
+/// Headers with a suffix that is not a unit are returned unchanged, e.g. `Rotation (deg)`.
+pub fn split_header_unit(header: &str) -> (&str, Option<LengthUnit>) {
+    let split = header.trim_end().strip_suffix(')')
+        .and_then(|remainder| remainder.rsplit_once('('))
+        .and_then(|(name, unit)| LengthUnit::from_str(unit).ok().map(|unit| (name.trim_end(), Some(unit))));
+
+    split.unwrap_or((header, None))
+}
+
+#[cfg(test)]
+mod units_tests {
+    use rstest::rstest;
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+    use crate::units::{split_header_unit, LengthUnit};
+
+    #[rstest]
+    #[case(LengthUnit::Millimeters, dec!(12.7))]
+    #[case(LengthUnit::Micrometers, dec!(0.0127))]
+    #[case(LengthUnit::Inches, dec!(322.58))]
+    #[case(LengthUnit::Mils, dec!(0.32258))]
+    fn to_millimeters(#[case] unit: LengthUnit, #[case] expected_value: Decimal) {
+        assert_eq!(unit.to_millimeters(dec!(12.7)), expected_value);
+    }
+
+    #[rstest]
+    #[case("Center X (mm)", ("Center X", Some(LengthUnit::Millimeters)))]
+    #[case("Center X (mil)", ("Center X", Some(LengthUnit::Mils)))]
+    #[case("Center-Y(in)", ("Center-Y", Some(LengthUnit::Inches)))]
+    #[case("Rotation (deg)", ("Rotation (deg)", None))]
+    #[case("RefDes", ("RefDes", None))]
+    fn header_units(#[case] header: &str, #[case] expected_result: (&str, Option<LengthUnit>)) {
+        assert_eq!(split_header_unit(header), expected_result);
+    }
+}
@@ -1,5 +1,7 @@
 pub mod criteria;
 pub mod part_mapping;
+pub mod stock;
+pub mod suggestion;
 mod part_mapper;
 
 pub use part_mapper::*;
@@ -5,22 +5,29 @@ use eda::placement::EdaPlacement;
 use crate::part_mapping::PartMapping;
 use crate::PartMappingError::{ConflictingRules, NoRulesApplied};
 use pnp::load_out::LoadOutItem;
+use pnp::inventory::InventoryItem;
 use pnp::part::Part;
+use crate::stock::{build_stock_levels, StockLevel};
 
 pub struct PartMapper {}
 
 impl PartMapper {
+    /// When inventory items are given, the stock of each mapped part is compared with the quantity required for the
+    /// placements that the part's mappings match and that an assembly rule or the load-out did not resolve, see
+    /// [`crate::stock::StockLevel`].
     pub fn process<'placement, 'mapping>(
         eda_placements: &'placement [EdaPlacement],
         part_mappings: &'mapping [PartMapping<'mapping>],
         load_out_items: &[LoadOutItem],
-        assembly_rules: &[AssemblyRule]
+        inventory_items: &[InventoryItem],
+        assembly_rules: &[AssemblyRule],
+        assembly_variant_name: &str,
     ) -> Result<Vec<PlacementPartMappingResult<'placement, 'mapping>>, PartMapperError<'placement, 'mapping>> {
 
         let mut error_count: usize = 0;
         let mut mappings = vec![];
 
-        for eda_placement in eda_placements.iter() {
+        let mut placements_part_mapping_results: Vec<Vec<PartMappingResult>> = eda_placements.iter().map(|eda_placement| {
             let mut part_mapping_results = vec![];
 
             for part_mapping in part_mappings.iter() {
@@ -31,7 +38,26 @@ impl PartMapper {
                 }
             }
 
-            apply_rules(&eda_placement.ref_des, &mut part_mapping_results, load_out_items, assembly_rules);
+            part_mapping_results
+        }).collect();
+
+        for (eda_placement, part_mapping_results) in eda_placements.iter().zip(placements_part_mapping_results.iter_mut()) {
+            apply_rules(&eda_placement.ref_des, part_mapping_results, load_out_items, assembly_rules, assembly_variant_name);
+        }
+
+        let stock_levels = match inventory_items.is_empty() {
+            true => vec![],
+            false => {
+                let placement_mapping_results: Vec<(&EdaPlacement, &[PartMappingResult])> = eda_placements.iter()
+                    .zip(placements_part_mapping_results.iter().map(Vec::as_slice))
+                    .collect();
+                build_stock_levels(&placement_mapping_results, inventory_items)
+            }
+        };
+
+        for (eda_placement, mut part_mapping_results) in eda_placements.iter().zip(placements_part_mapping_results) {
+
+            apply_stock_rules(&mut part_mapping_results, &stock_levels);
 
             let applied_rule_count = part_mapping_results.iter().filter(|pmr|pmr.applied_rule.is_some()).count();
 
@@ -60,19 +86,34 @@ impl PartMapper {
     }
 }
 
-fn apply_rules<'mapping>(ref_des: &String, mapping_results: &mut [PartMappingResult<'mapping>], load_out_items: &[LoadOutItem], assembly_rules: &[AssemblyRule]) {
-    for mapping_result in mapping_results.iter_mut() {
-        let maybe_assembly_rule = assembly_rules.iter().find(|rule| {
-            let mapped_part = mapping_result.part_mapping;
-            *ref_des == rule.ref_des &&
-                mapped_part.part.manufacturer == rule.manufacturer &&
-                mapped_part.part.mpn == rule.mpn
-        });
-
-        if let Some(_rule) = maybe_assembly_rule {
+/// Applies the rules that do not depend on stock, i.e. assembly rules, auto-selection and the load-out, see `apply_stock_rules`.
+fn apply_rules<'mapping>(ref_des: &str, mapping_results: &mut [PartMappingResult<'mapping>], load_out_items: &[LoadOutItem], assembly_rules: &[AssemblyRule], assembly_variant_name: &str) {
+    let matching_assembly_rules: Vec<(&AssemblyRule, &Part)> = assembly_rules.iter()
+        .filter(|rule| rule.applies_to_variant(assembly_variant_name) && rule.ref_des.matches(ref_des))
+        .filter_map(|rule| mapping_results.iter()
+            .map(|mapping_result| mapping_result.part_mapping.part)
+            .find(|part| part.manufacturer == rule.manufacturer && part.mpn == rule.mpn)
+            .map(|part| (rule, part))
+        )
+        .collect();
+
+    // only the rules with the most specific pattern are applied, rules with equally specific patterns for different
+    // parts are conflicting rules.
+    if let Some(precedence) = matching_assembly_rules.iter().map(|(rule, _part)| rule.ref_des.precedence()).min() {
+        let mut parts: Vec<&Part> = vec![];
+        for (_rule, part) in matching_assembly_rules.iter().filter(|(rule, _part)| rule.ref_des.precedence() == precedence) {
+            if parts.contains(part) {
+                continue
+            }
+            parts.push(part);
+
+            // the same part can be mapped more than once, the rule is applied to the first mapping
+            let mapping_result = mapping_results.iter_mut()
+                .find(|mapping_result| mapping_result.part_mapping.part.eq(*part))
+                .unwrap();
             mapping_result.applied_rule = Some(AppliedMappingRule::AssemblyRule);
-            return
         }
+        return
     }
 
     match mapping_results.len() {
@@ -96,6 +137,66 @@ fn apply_rules<'mapping>(ref_des: &String, mapping_results: &mut [PartMappingRes
     }
 }
 
+/// Applied after `apply_rules`, to placements with alternate parts that an assembly rule did not resolve.
+///
+/// Parts in the load-out are used before parts in stock.  When more than one part has enough stock, the part with the
+/// highest priority, then the most stock, then the first in the part-mappings file is used.
+fn apply_stock_rules(mapping_results: &mut [PartMappingResult], stock_levels: &[StockLevel]) {
+    if mapping_results.len() < 2 || mapping_results.iter().any(|mapping_result| matches!(mapping_result.applied_rule, Some(AppliedMappingRule::AssemblyRule))) {
+        return
+    }
+
+    if mapping_results.iter().all(|mapping_result| mapping_result.applied_rule.is_none()) {
+        let in_stock = mapping_results.iter().enumerate()
+            .filter_map(|(index, mapping_result)| {
+                stock_levels.iter()
+                    .find(|stock_level| stock_level.part.eq(mapping_result.part_mapping.part))
+                    .filter(|stock_level| stock_level.is_sufficient())
+                    .map(|stock_level| (index, mapping_result.part_mapping.priority, stock_level))
+            })
+            // a mapping without a priority has the lowest priority, `min_by_key` returns the first of equal elements.
+            .min_by_key(|(_index, priority, stock_level)| (priority.is_none(), *priority, std::cmp::Reverse(stock_level.available)));
+
+        if let Some((index, _priority, stock_level)) = in_stock {
+            mapping_results[index].applied_rule = Some(AppliedMappingRule::InStock { quantity: stock_level.available, locations: stock_level.locations.clone() });
+        }
+    }
+
+    apply_preference(mapping_results);
+}
+
+/// When no rules, or more than one rule, were applied then the mapping with the highest priority is preferred, if there
+/// is only one mapping with that priority. When more than one rule was applied only the mappings with rules are considered.
+fn apply_preference(mapping_results: &mut [PartMappingResult]) {
+    let applied_rule_count = mapping_results.iter().filter(|mapping_result| mapping_result.applied_rule.is_some()).count();
+    if applied_rule_count == 1 {
+        return
+    }
+
+    let candidates: Vec<(usize, u32)> = mapping_results.iter().enumerate()
+        .filter(|(_index, mapping_result)| applied_rule_count == 0 || mapping_result.applied_rule.is_some())
+        .filter_map(|(index, mapping_result)| mapping_result.part_mapping.priority.map(|priority| (index, priority)))
+        .collect();
+
+    let Some(highest_priority) = candidates.iter().map(|(_index, priority)| *priority).min() else {
+        return
+    };
+
+    let mut preferred = candidates.iter().filter(|(_index, priority)| *priority == highest_priority);
+    let (preferred_index, _priority) = *preferred.next().unwrap();
+
+    // the same mapping can be matched by more than one of its criteria
+    let preferred_mapping = mapping_results[preferred_index].part_mapping;
+    if preferred.any(|(index, _priority)| !std::ptr::eq(mapping_results[*index].part_mapping, preferred_mapping)) {
+        return
+    }
+
+    for mapping_result in mapping_results.iter_mut() {
+        mapping_result.applied_rule = None;
+    }
+    mapping_results[preferred_index].applied_rule = Some(AppliedMappingRule::Preferred { priority: highest_priority });
+}
+
 #[cfg_attr(test, derive(PartialEq))]
 #[derive(Debug)]
 pub enum PartMapperError<'placement, 'mapping> {
@@ -119,6 +220,8 @@ pub enum AppliedMappingRule {
     AutoSelected,
     FoundInLoadOut(String),
     AssemblyRule,
+    Preferred { priority: u32 },
+    InStock { quantity: u32, locations: Vec<String> },
 }
 
 impl Display for AppliedMappingRule {
@@ -127,6 +230,8 @@ impl Display for AppliedMappingRule {
             AppliedMappingRule::AutoSelected => write!(f, "Auto-selected"),
             AppliedMappingRule::FoundInLoadOut(reference) => write!(f, "Found in load-out, reference: '{}'", reference),
             AppliedMappingRule::AssemblyRule => write!(f, "Matched assembly-rule"),
+            AppliedMappingRule::Preferred { priority } => write!(f, "Preferred, priority: {}", priority),
+            AppliedMappingRule::InStock { quantity, locations } => write!(f, "In stock, quantity: {}, locations: '{}'", quantity, locations.join("', '")),
         }
     }
 }
@@ -148,12 +253,16 @@ pub struct PlacementPartMappingResult<'placement, 'mapping> {
 
 #[cfg(test)]
 mod tests {
-    use assembly::rules::AssemblyRule;
+    use std::str::FromStr;
+    use rstest::rstest;
+    use assembly::rules::{AssemblyRule, RefDesPattern};
     use criteria::{ExactMatchCriterion, GenericCriteria};
     use pnp::part::Part;
     use pnp::load_out::LoadOutItem;
+    use pnp::inventory::InventoryItem;
     use eda::placement::{EdaPlacement, EdaPlacementField};
     use crate::part_mapping::PartMapping;
+    use crate::stock::{find_stock_shortfalls, StockShortfall};
     use crate::{AppliedMappingRule, PartMapper, PartMapperError, PartMappingError, PartMappingResult, PlacementPartMappingResult};
 
     #[test]
@@ -220,7 +329,7 @@ mod tests {
         ]);
 
         // when
-        let matched_mappings = PartMapper::process(&eda_placements, &part_mappings, &[], &[]);
+        let matched_mappings = PartMapper::process(&eda_placements, &part_mappings, &[], &[], &[], "Default");
 
         // then
         assert_eq!(matched_mappings, expected_results);
@@ -273,7 +382,7 @@ mod tests {
         ]));
 
         // when
-        let matched_mappings = PartMapper::process(&eda_placements, &part_mappings, &[], &[]);
+        let matched_mappings = PartMapper::process(&eda_placements, &part_mappings, &[], &[], &[], "Default");
 
         // then
         assert_eq!(matched_mappings, expected_results);
@@ -305,7 +414,7 @@ mod tests {
         ]));
 
         // when
-        let matched_mappings = PartMapper::process(&eda_placements, &part_mappings, &[], &[]);
+        let matched_mappings = PartMapper::process(&eda_placements, &part_mappings, &[], &[], &[], "Default");
 
         // then
         assert_eq!(matched_mappings, expected_results);
@@ -365,7 +474,7 @@ mod tests {
         ]);
 
         // when
-        let matched_mappings = PartMapper::process(&eda_placements, &part_mappings, &load_out_items, &[]);
+        let matched_mappings = PartMapper::process(&eda_placements, &part_mappings, &load_out_items, &[], &[], "Default");
 
         // then
         assert_eq!(matched_mappings, expected_results);
@@ -408,14 +517,16 @@ mod tests {
 
         // and
         let assembly_rule1 = AssemblyRule {
-            ref_des: "C1".to_string(),
+            ref_des: RefDesPattern::Exact("C1".to_string()),
             manufacturer: "MFR3".to_string(),
             mpn: "PART3".to_string(),
+            assembly_variant: None,
         };
         let assembly_rule2 = AssemblyRule {
-            ref_des: "R1".to_string(),
+            ref_des: RefDesPattern::Exact("R1".to_string()),
             manufacturer: "MFR2".to_string(),
             mpn: "PART2".to_string(),
+            assembly_variant: None,
         };
         let assembly_rules = &[assembly_rule1, assembly_rule2];
 
@@ -432,12 +543,75 @@ mod tests {
         ]);
 
         // when
-        let matched_mappings = PartMapper::process(&eda_placements, &part_mappings, &[], assembly_rules);
+        let matched_mappings = PartMapper::process(&eda_placements, &part_mappings, &[], &[], assembly_rules, "Default");
 
         // then
         assert_eq!(matched_mappings, expected_results);
     }
 
+    #[rstest]
+    #[case::exact_over_regex(vec![("/^R/", 1, None), ("R1", 2, None)], "Default", [false, true])]
+    #[case::exact_over_range(vec![("R1-R10", 1, None), ("R1", 2, None)], "Default", [false, true])]
+    #[case::range_over_regex(vec![("/^R1$/", 2, None), ("R1-R10", 1, None)], "Default", [true, false])]
+    #[case::equally_specific_rules_conflict(vec![("R1", 1, None), ("R1", 2, None)], "Default", [true, true])]
+    #[case::rule_for_the_assembly_variant(vec![("R1", 1, Some("Variant_1"))], "Variant_1", [true, false])]
+    #[case::rule_for_another_assembly_variant(vec![("R1", 1, Some("Variant_2")), ("/^R/", 2, None)], "Variant_1", [false, true])]
+    fn map_parts_with_overlapping_assembly_rules(#[case] rules: Vec<(&str, usize, Option<&str>)>, #[case] assembly_variant_name: &str, #[case] expected_applied_rules: [bool; 2]) {
+        // given
+        let eda_placement1 = EdaPlacement {
+            ref_des: "R1".to_string(),
+            fields: vec![
+                EdaPlacementField::new("name".to_string(), "NAME1".to_string()),
+                EdaPlacementField::new("value".to_string(), "VALUE1".to_string()),
+            ],
+            ..EdaPlacement::default()
+        };
+
+        let eda_placements = vec![eda_placement1];
+
+        // and
+        let parts = [
+            Part::new("MFR1".to_string(), "PART1".to_string()),
+            Part::new("MFR2".to_string(), "PART2".to_string()),
+        ];
+
+        // and two mappings that match the placement
+        let part_mappings: Vec<PartMapping> = parts.iter().map(|part| {
+            let criteria = GenericCriteria { criteria: vec![
+                Box::new(ExactMatchCriterion::new("name".to_string(), "NAME1".to_string() )),
+                Box::new(ExactMatchCriterion::new("value".to_string(), "VALUE1".to_string() )),
+            ]};
+            PartMapping::new(part, vec![Box::new(criteria)])
+        }).collect();
+
+        // and
+        let assembly_rules: Vec<AssemblyRule> = rules.iter().map(|(pattern, part_number, assembly_variant)| AssemblyRule {
+            ref_des: RefDesPattern::from_str(pattern).unwrap(),
+            manufacturer: parts[part_number - 1].manufacturer.clone(),
+            mpn: parts[part_number - 1].mpn.clone(),
+            assembly_variant: assembly_variant.map(str::to_string),
+        }).collect();
+
+        // when
+        let matched_mappings = PartMapper::process(&eda_placements, &part_mappings, &[], &[], &assembly_rules, assembly_variant_name);
+
+        // then
+        let mappings = match matched_mappings {
+            Ok(mappings) => mappings,
+            Err(PartMapperError::MappingErrors(mappings)) => mappings,
+        };
+        let part_mapping_results = match &mappings[0].mapping_result {
+            Ok(part_mapping_results) => part_mapping_results,
+            Err(PartMappingError::ConflictingRules(part_mapping_results)) => part_mapping_results,
+            Err(PartMappingError::NoRulesApplied(part_mapping_results)) => part_mapping_results,
+            Err(PartMappingError::NoMappings) => unreachable!(),
+        };
+        let applied_rules: Vec<bool> = part_mapping_results.iter()
+            .map(|part_mapping_result| part_mapping_result.applied_rule.eq(&Some(AppliedMappingRule::AssemblyRule)))
+            .collect();
+        assert_eq!(applied_rules, expected_applied_rules);
+    }
+
     #[test]
     fn map_parts_with_multiple_matching_mappings_with_an_assembly_rule_and_loadout_item() {
         // given
@@ -479,9 +653,10 @@ mod tests {
 
         // and
         let assembly_rule1 = AssemblyRule {
-            ref_des: "R1".to_string(),
+            ref_des: RefDesPattern::Exact("R1".to_string()),
             manufacturer: "MFR2".to_string(),
             mpn: "PART2".to_string(),
+            assembly_variant: None,
         };
         let assembly_rules = &[assembly_rule1];
 
@@ -498,9 +673,412 @@ mod tests {
         ]);
 
         // when
-        let matched_mappings = PartMapper::process(&eda_placements, &part_mappings, &load_out_items, assembly_rules);
+        let matched_mappings = PartMapper::process(&eda_placements, &part_mappings, &load_out_items, &[], assembly_rules, "Default");
 
         // then
         assert_eq!(matched_mappings, expected_results);
     }
+
+    #[test]
+    fn map_parts_with_multiple_matching_mappings_with_a_preferred_mapping() {
+        // given
+        let eda_placement1 = EdaPlacement {
+            ref_des: "R1".to_string(),
+            fields: vec![
+                EdaPlacementField::new("name".to_string(), "NAME1".to_string()),
+                EdaPlacementField::new("value".to_string(), "VALUE1".to_string()),
+            ],
+            ..EdaPlacement::default()
+        };
+
+        let eda_placements = vec![eda_placement1];
+
+        // and
+        let part1 = Part::new("MFR1".to_string(), "PART1".to_string());
+        let part2 = Part::new("MFR2".to_string(), "PART2".to_string());
+        let part3 = Part::new("MFR3".to_string(), "PART3".to_string());
+
+        let parts = [part1, part2, part3];
+
+        // and three mappings, one without a priority
+        let criteria1 = GenericCriteria { criteria: vec![
+            Box::new(ExactMatchCriterion::new("name".to_string(), "NAME1".to_string() )),
+            Box::new(ExactMatchCriterion::new("value".to_string(), "VALUE1".to_string() )),
+        ]};
+        let part_mapping1 = PartMapping::new(&parts[1 - 1], vec![Box::new(criteria1)]);
+        let criteria2 = GenericCriteria { criteria: vec![
+            Box::new(ExactMatchCriterion::new("name".to_string(), "NAME1".to_string() )),
+            Box::new(ExactMatchCriterion::new("value".to_string(), "VALUE1".to_string() )),
+        ]};
+        let part_mapping2 = PartMapping { priority: Some(2), ..PartMapping::new(&parts[2 - 1], vec![Box::new(criteria2)]) };
+        let criteria3 = GenericCriteria { criteria: vec![
+            Box::new(ExactMatchCriterion::new("name".to_string(), "NAME1".to_string() )),
+            Box::new(ExactMatchCriterion::new("value".to_string(), "VALUE1".to_string() )),
+        ]};
+        let part_mapping3 = PartMapping { priority: Some(1), ..PartMapping::new(&parts[3 - 1], vec![Box::new(criteria3)]) };
+
+        let part_mappings = vec![part_mapping1, part_mapping2, part_mapping3];
+
+        // and
+        let expected_results = Ok(vec![
+            PlacementPartMappingResult {
+                part: Some(&parts[3 - 1]),
+                eda_placement: &eda_placements[0],
+                mapping_result: Ok(vec![
+                    PartMappingResult { part_mapping: &part_mappings[0], applied_rule: None },
+                    PartMappingResult { part_mapping: &part_mappings[1], applied_rule: None },
+                    PartMappingResult { part_mapping: &part_mappings[2], applied_rule: Some(AppliedMappingRule::Preferred { priority: 1 }) },
+                ])
+            },
+        ]);
+
+        // when
+        let matched_mappings = PartMapper::process(&eda_placements, &part_mappings, &[], &[], &[], "Default");
+
+        // then
+        assert_eq!(matched_mappings, expected_results);
+    }
+
+    #[test]
+    fn map_parts_with_multiple_matching_mappings_in_the_load_out_with_a_preferred_mapping() {
+        // given
+        let eda_placement1 = EdaPlacement {
+            ref_des: "R1".to_string(),
+            fields: vec![
+                EdaPlacementField::new("name".to_string(), "NAME1".to_string()),
+                EdaPlacementField::new("value".to_string(), "VALUE1".to_string()),
+            ],
+            ..EdaPlacement::default()
+        };
+
+        let eda_placements = vec![eda_placement1];
+
+        // and
+        let part1 = Part::new("MFR1".to_string(), "PART1".to_string());
+        let part2 = Part::new("MFR2".to_string(), "PART2".to_string());
+        let part3 = Part::new("MFR3".to_string(), "PART3".to_string());
+
+        let parts = [part1, part2, part3];
+
+        // and the mapping with the highest priority is not in the load-out
+        let criteria1 = GenericCriteria { criteria: vec![
+            Box::new(ExactMatchCriterion::new("name".to_string(), "NAME1".to_string() )),
+            Box::new(ExactMatchCriterion::new("value".to_string(), "VALUE1".to_string() )),
+        ]};
+        let part_mapping1 = PartMapping { priority: Some(1), ..PartMapping::new(&parts[1 - 1], vec![Box::new(criteria1)]) };
+        let criteria2 = GenericCriteria { criteria: vec![
+            Box::new(ExactMatchCriterion::new("name".to_string(), "NAME1".to_string() )),
+            Box::new(ExactMatchCriterion::new("value".to_string(), "VALUE1".to_string() )),
+        ]};
+        let part_mapping2 = PartMapping { priority: Some(3), ..PartMapping::new(&parts[2 - 1], vec![Box::new(criteria2)]) };
+        let criteria3 = GenericCriteria { criteria: vec![
+            Box::new(ExactMatchCriterion::new("name".to_string(), "NAME1".to_string() )),
+            Box::new(ExactMatchCriterion::new("value".to_string(), "VALUE1".to_string() )),
+        ]};
+        let part_mapping3 = PartMapping { priority: Some(2), ..PartMapping::new(&parts[3 - 1], vec![Box::new(criteria3)]) };
+
+        let part_mappings = vec![part_mapping1, part_mapping2, part_mapping3];
+
+        // and
+        let load_out_items = vec![
+            LoadOutItem::new("REFERENCE_1".to_string(), "MFR3".to_string(), "PART3".to_string()),
+            LoadOutItem::new("REFERENCE_2".to_string(), "MFR2".to_string(), "PART2".to_string()),
+        ];
+
+        // and
+        let expected_results = Ok(vec![
+            PlacementPartMappingResult {
+                part: Some(&parts[3 - 1]),
+                eda_placement: &eda_placements[0],
+                mapping_result: Ok(vec![
+                    PartMappingResult { part_mapping: &part_mappings[0], applied_rule: None },
+                    PartMappingResult { part_mapping: &part_mappings[1], applied_rule: None },
+                    PartMappingResult { part_mapping: &part_mappings[2], applied_rule: Some(AppliedMappingRule::Preferred { priority: 2 }) },
+                ])
+            },
+        ]);
+
+        // when
+        let matched_mappings = PartMapper::process(&eda_placements, &part_mappings, &load_out_items, &[], &[], "Default");
+
+        // then
+        assert_eq!(matched_mappings, expected_results);
+    }
+
+    #[test]
+    fn map_parts_with_multiple_matching_mappings_with_one_in_stock() {
+        // given
+        let eda_placement1 = EdaPlacement {
+            ref_des: "R1".to_string(),
+            fields: vec![
+                EdaPlacementField::new("name".to_string(), "NAME1".to_string()),
+                EdaPlacementField::new("value".to_string(), "VALUE1".to_string()),
+            ],
+            place: true,
+            ..EdaPlacement::default()
+        };
+        let eda_placement2 = EdaPlacement {
+            ref_des: "R2".to_string(),
+            fields: vec![
+                EdaPlacementField::new("name".to_string(), "NAME1".to_string()),
+                EdaPlacementField::new("value".to_string(), "VALUE1".to_string()),
+            ],
+            place: true,
+            ..EdaPlacement::default()
+        };
+
+        let eda_placements = vec![eda_placement1, eda_placement2];
+
+        // and
+        let part1 = Part::new("MFR1".to_string(), "PART1".to_string());
+        let part2 = Part::new("MFR2".to_string(), "PART2".to_string());
+
+        let parts = [part1, part2];
+
+        // and two alternate parts for both placements
+        let criteria1 = GenericCriteria { criteria: vec![
+            Box::new(ExactMatchCriterion::new("name".to_string(), "NAME1".to_string() )),
+            Box::new(ExactMatchCriterion::new("value".to_string(), "VALUE1".to_string() )),
+        ]};
+        let part_mapping1 = PartMapping::new(&parts[1 - 1], vec![Box::new(criteria1)]);
+        let criteria2 = GenericCriteria { criteria: vec![
+            Box::new(ExactMatchCriterion::new("name".to_string(), "NAME1".to_string() )),
+            Box::new(ExactMatchCriterion::new("value".to_string(), "VALUE1".to_string() )),
+        ]};
+        let part_mapping2 = PartMapping::new(&parts[2 - 1], vec![Box::new(criteria2)]);
+
+        let part_mappings = vec![part_mapping1, part_mapping2];
+
+        // and not enough of the first part for both placements
+        let inventory_items = vec![
+            InventoryItem::new("MFR1".to_string(), "PART1".to_string(), 1, "SHELF_1".to_string()),
+            InventoryItem::new("MFR2".to_string(), "PART2".to_string(), 1, "SHELF_2".to_string()),
+            InventoryItem::new("MFR2".to_string(), "PART2".to_string(), 1, "DRAWER_1".to_string()),
+        ];
+
+        // and
+        let expected_mapping_result = || Ok(vec![
+            PartMappingResult { part_mapping: &part_mappings[0], applied_rule: None },
+            PartMappingResult { part_mapping: &part_mappings[1], applied_rule: Some(AppliedMappingRule::InStock { quantity: 2, locations: vec!["SHELF_2".to_string(), "DRAWER_1".to_string()] }) },
+        ]);
+        let expected_results = Ok(vec![
+            PlacementPartMappingResult { part: Some(&parts[2 - 1]), eda_placement: &eda_placements[0], mapping_result: expected_mapping_result() },
+            PlacementPartMappingResult { part: Some(&parts[2 - 1]), eda_placement: &eda_placements[1], mapping_result: expected_mapping_result() },
+        ]);
+
+        // when
+        let matched_mappings = PartMapper::process(&eda_placements, &part_mappings, &[], &inventory_items, &[], "Default");
+
+        // then
+        assert_eq!(matched_mappings, expected_results);
+
+        // and
+        assert_eq!(find_stock_shortfalls(&matched_mappings.unwrap(), &inventory_items), vec![]);
+    }
+
+    #[test]
+    fn map_parts_with_multiple_matching_mappings_with_insufficient_stock() {
+        // given
+        let eda_placement1 = EdaPlacement {
+            ref_des: "R1".to_string(),
+            fields: vec![
+                EdaPlacementField::new("name".to_string(), "NAME1".to_string()),
+                EdaPlacementField::new("value".to_string(), "VALUE1".to_string()),
+            ],
+            place: true,
+            ..EdaPlacement::default()
+        };
+        let eda_placement2 = EdaPlacement {
+            ref_des: "R2".to_string(),
+            fields: vec![
+                EdaPlacementField::new("name".to_string(), "NAME1".to_string()),
+                EdaPlacementField::new("value".to_string(), "VALUE1".to_string()),
+            ],
+            place: true,
+            ..EdaPlacement::default()
+        };
+
+        let eda_placements = vec![eda_placement1, eda_placement2];
+
+        // and
+        let part1 = Part::new("MFR1".to_string(), "PART1".to_string());
+        let part2 = Part::new("MFR2".to_string(), "PART2".to_string());
+
+        let parts = [part1, part2];
+
+        // and two alternate parts for both placements
+        let criteria1 = GenericCriteria { criteria: vec![
+            Box::new(ExactMatchCriterion::new("name".to_string(), "NAME1".to_string() )),
+            Box::new(ExactMatchCriterion::new("value".to_string(), "VALUE1".to_string() )),
+        ]};
+        let part_mapping1 = PartMapping::new(&parts[1 - 1], vec![Box::new(criteria1)]);
+        let criteria2 = GenericCriteria { criteria: vec![
+            Box::new(ExactMatchCriterion::new("name".to_string(), "NAME1".to_string() )),
+            Box::new(ExactMatchCriterion::new("value".to_string(), "VALUE1".to_string() )),
+        ]};
+        let part_mapping2 = PartMapping::new(&parts[2 - 1], vec![Box::new(criteria2)]);
+
+        let part_mappings = vec![part_mapping1, part_mapping2];
+
+        // and
+        let inventory_items = vec![
+            InventoryItem::new("MFR1".to_string(), "PART1".to_string(), 1, "SHELF_1".to_string()),
+        ];
+
+        // when
+        let matched_mappings = PartMapper::process(&eda_placements, &part_mappings, &[], &inventory_items, &[], "Default");
+
+        // then
+        let Err(PartMapperError::MappingErrors(mappings)) = matched_mappings else {
+            panic!("expected mapping errors");
+        };
+        assert_eq!(mappings[0].mapping_result, Err(PartMappingError::NoRulesApplied(vec![
+            PartMappingResult { part_mapping: &part_mappings[0], applied_rule: None },
+            PartMappingResult { part_mapping: &part_mappings[1], applied_rule: None },
+        ])));
+
+        // and
+        let expected_shortfalls = vec![
+            StockShortfall { part: &parts[0], required: 2, available: 1, ref_des: vec!["R1".to_string(), "R2".to_string()] },
+            StockShortfall { part: &parts[1], required: 2, available: 0, ref_des: vec!["R1".to_string(), "R2".to_string()] },
+        ];
+        assert_eq!(find_stock_shortfalls(&mappings, &inventory_items), expected_shortfalls);
+    }
+
+    #[rstest]
+    #[case::most_stock([None, None], [1, 2], 2)]
+    #[case::first_in_file_when_stock_is_equal([None, None], [2, 2], 1)]
+    #[case::highest_priority([Some(2), Some(1)], [3, 2], 2)]
+    #[case::priority_over_no_priority([None, Some(5)], [3, 2], 2)]
+    fn map_parts_with_multiple_matching_mappings_all_in_stock(#[case] priorities: [Option<u32>; 2], #[case] quantities: [u32; 2], #[case] expected_part: usize) {
+        // given
+        let eda_placement1 = EdaPlacement {
+            ref_des: "R1".to_string(),
+            fields: vec![
+                EdaPlacementField::new("name".to_string(), "NAME1".to_string()),
+                EdaPlacementField::new("value".to_string(), "VALUE1".to_string()),
+            ],
+            place: true,
+            ..EdaPlacement::default()
+        };
+
+        let eda_placements = vec![eda_placement1];
+
+        // and
+        let part1 = Part::new("MFR1".to_string(), "PART1".to_string());
+        let part2 = Part::new("MFR2".to_string(), "PART2".to_string());
+
+        let parts = [part1, part2];
+
+        // and two alternate parts
+        let part_mappings: Vec<PartMapping> = parts.iter().zip(priorities).map(|(part, priority)| {
+            let criteria = GenericCriteria { criteria: vec![
+                Box::new(ExactMatchCriterion::new("name".to_string(), "NAME1".to_string() )),
+                Box::new(ExactMatchCriterion::new("value".to_string(), "VALUE1".to_string() )),
+            ]};
+            PartMapping { priority, ..PartMapping::new(part, vec![Box::new(criteria)]) }
+        }).collect();
+
+        // and enough of both parts
+        let inventory_items = vec![
+            InventoryItem::new("MFR1".to_string(), "PART1".to_string(), quantities[0], "SHELF_1".to_string()),
+            InventoryItem::new("MFR2".to_string(), "PART2".to_string(), quantities[1], "SHELF_2".to_string()),
+        ];
+
+        // when
+        let matched_mappings = PartMapper::process(&eda_placements, &part_mappings, &[], &inventory_items, &[], "Default");
+
+        // then
+        let matched_mappings = matched_mappings.unwrap();
+        assert_eq!(matched_mappings[0].part, Some(&parts[expected_part - 1]));
+
+        // and
+        let Ok(mapping_results) = &matched_mappings[0].mapping_result else {
+            panic!("expected a mapping result");
+        };
+        let expected_applied_rule = AppliedMappingRule::InStock {
+            quantity: quantities[expected_part - 1],
+            locations: vec![format!("SHELF_{}", expected_part)],
+        };
+        assert_eq!(mapping_results[expected_part - 1].applied_rule, Some(expected_applied_rule));
+    }
+
+    #[test]
+    fn map_parts_with_stock_not_required_by_placements_in_the_load_out() {
+        // given
+        let eda_placement1 = EdaPlacement {
+            ref_des: "R1".to_string(),
+            fields: vec![
+                EdaPlacementField::new("name".to_string(), "NAME1".to_string()),
+            ],
+            place: true,
+            ..EdaPlacement::default()
+        };
+        let eda_placement2 = EdaPlacement {
+            ref_des: "R2".to_string(),
+            fields: vec![
+                EdaPlacementField::new("name".to_string(), "NAME2".to_string()),
+            ],
+            place: true,
+            ..EdaPlacement::default()
+        };
+
+        let eda_placements = vec![eda_placement1, eda_placement2];
+
+        // and
+        let part1 = Part::new("MFR1".to_string(), "PART1".to_string());
+        let part2 = Part::new("MFR2".to_string(), "PART2".to_string());
+        let part3 = Part::new("MFR3".to_string(), "PART3".to_string());
+
+        let parts = [part1, part2, part3];
+
+        // and a part that can be used for both placements, and an alternate part for each placement
+        let criteria = |name: &str| GenericCriteria { criteria: vec![
+            Box::new(ExactMatchCriterion::new("name".to_string(), name.to_string() )),
+        ]};
+        let part_mapping1 = PartMapping::new(&parts[1 - 1], vec![Box::new(criteria("NAME1")), Box::new(criteria("NAME2"))]);
+        let part_mapping2 = PartMapping::new(&parts[2 - 1], vec![Box::new(criteria("NAME1"))]);
+        let part_mapping3 = PartMapping::new(&parts[3 - 1], vec![Box::new(criteria("NAME2"))]);
+
+        let part_mappings = vec![part_mapping1, part_mapping2, part_mapping3];
+
+        // and the alternate part for the first placement is in the load-out
+        let load_out_items = vec![
+            LoadOutItem::new("REFERENCE_1".to_string(), "MFR2".to_string(), "PART2".to_string()),
+        ];
+
+        // and only enough of the first part for one placement
+        let inventory_items = vec![
+            InventoryItem::new("MFR1".to_string(), "PART1".to_string(), 1, "SHELF_1".to_string()),
+        ];
+
+        // and
+        let expected_results = Ok(vec![
+            PlacementPartMappingResult {
+                part: Some(&parts[2 - 1]),
+                eda_placement: &eda_placements[0],
+                mapping_result: Ok(vec![
+                    PartMappingResult { part_mapping: &part_mappings[0], applied_rule: None },
+                    PartMappingResult { part_mapping: &part_mappings[1], applied_rule: Some(AppliedMappingRule::FoundInLoadOut("REFERENCE_1".to_string())) },
+                ])
+            },
+            PlacementPartMappingResult {
+                part: Some(&parts[1 - 1]),
+                eda_placement: &eda_placements[1],
+                mapping_result: Ok(vec![
+                    PartMappingResult { part_mapping: &part_mappings[0], applied_rule: Some(AppliedMappingRule::InStock { quantity: 1, locations: vec!["SHELF_1".to_string()] }) },
+                    PartMappingResult { part_mapping: &part_mappings[2], applied_rule: None },
+                ])
+            },
+        ]);
+
+        // when
+        let matched_mappings = PartMapper::process(&eda_placements, &part_mappings, &load_out_items, &inventory_items, &[], "Default");
+
+        // then
+        assert_eq!(matched_mappings, expected_results);
+
+        // and
+        assert_eq!(find_stock_shortfalls(&matched_mappings.unwrap(), &inventory_items), vec![]);
+    }
 }
@@ -7,13 +7,20 @@ pub struct PartMapping<'part>
 {
     pub part: &'part Part,
     pub criteria: Vec<Box<dyn PlacementMappingCriteria>>,
+    /// Used to choose between mappings when more than one mapping matches a placement, a lower value is preferred,
+    /// e.g. `1` is preferred over `2`, mappings without a priority are never preferred.
+    pub priority: Option<u32>,
+    /// The line of the file the mapping was loaded from, the header is line 1.
+    pub line: Option<u64>,
 }
 
 impl<'part> PartMapping<'part> {
     pub fn new(part: &'part Part, criteria: Vec<Box<dyn PlacementMappingCriteria>>) -> Self {
         Self {
             part,
-            criteria
+            criteria,
+            priority: None,
+            line: None,
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,120 @@
+use eda::placement::EdaPlacement;
+use pnp::inventory::{find_inventory_items_by_part, InventoryItem};
+use pnp::part::Part;
+use crate::{AppliedMappingRule, PartMappingError, PartMappingResult, PlacementPartMappingResult};
+
+/// The quantity of a part that is in the inventory and the quantity that is required to place the placements that the
+/// part's mappings match, placements resolved by an assembly rule or the load-out do not use stock.
+#[derive(Debug, PartialEq)]
+pub struct StockLevel<'mapping> {
+    pub part: &'mapping Part,
+    pub required: u32,
+    pub available: u32,
+    pub locations: Vec<String>,
+}
+
+impl StockLevel<'_> {
+    pub fn is_sufficient(&self) -> bool {
+        self.available > 0 && self.available >= self.required
+    }
+}
+
+/// A part that does not have enough stock, and the placements for which no mapped part has enough stock.
+#[derive(Debug, PartialEq)]
+pub struct StockShortfall<'mapping> {
+    pub part: &'mapping Part,
+    pub required: u32,
+    pub available: u32,
+    pub ref_des: Vec<String>,
+}
+
+/// Placements that are not placed, or that are resolved without stock, do not require stock.
+pub(crate) fn build_stock_levels<'mapping>(
+    placement_mapping_results: &[(&EdaPlacement, &[PartMappingResult<'mapping>])],
+    inventory_items: &[InventoryItem],
+) -> Vec<StockLevel<'mapping>> {
+    let mut stock_levels: Vec<StockLevel> = vec![];
+
+    for (_eda_placement, part_mapping_results) in placement_mapping_results.iter() {
+        for part_mapping_result in part_mapping_results.iter() {
+            let part = part_mapping_result.part_mapping.part;
+            if stock_levels.iter().any(|stock_level| stock_level.part.eq(part)) {
+                continue
+            }
+
+            let required = placement_mapping_results.iter()
+                .filter(|(eda_placement, part_mapping_results)| {
+                    eda_placement.place
+                        && !is_resolved_without_stock(part_mapping_results)
+                        && part_mapping_results.iter().any(|candidate| candidate.part_mapping.part.eq(part))
+                })
+                .count() as u32;
+
+            let inventory_items = find_inventory_items_by_part(inventory_items, part);
+
+            stock_levels.push(StockLevel {
+                part,
+                required,
+                available: inventory_items.iter().map(|item| item.quantity).sum(),
+                locations: inventory_items.iter().map(|item| item.location.clone()).collect(),
+            });
+        }
+    }
+
+    stock_levels
+}
+
+/// A placement resolved by an assembly rule or found in the load-out.
+fn is_resolved_without_stock(part_mapping_results: &[PartMappingResult]) -> bool {
+    part_mapping_results.iter().any(|part_mapping_result| matches!(
+        part_mapping_result.applied_rule,
+        Some(AppliedMappingRule::AssemblyRule) | Some(AppliedMappingRule::FoundInLoadOut(_))
+    ))
+}
+
+/// Finds the placements for which none of the mapped parts have enough stock for the whole variant.
+pub fn find_stock_shortfalls<'mapping>(
+    mappings: &[PlacementPartMappingResult<'_, 'mapping>],
+    inventory_items: &[InventoryItem],
+) -> Vec<StockShortfall<'mapping>> {
+    let placement_mapping_results: Vec<(&EdaPlacement, &[PartMappingResult])> = mappings.iter()
+        .filter_map(|PlacementPartMappingResult { eda_placement, mapping_result, .. }| {
+            let part_mapping_results = match mapping_result {
+                Ok(part_mapping_results) => part_mapping_results,
+                Err(PartMappingError::ConflictingRules(part_mapping_results)) => part_mapping_results,
+                Err(PartMappingError::NoRulesApplied(part_mapping_results)) => part_mapping_results,
+                Err(PartMappingError::NoMappings) => return None,
+            };
+            Some((*eda_placement, part_mapping_results.as_slice()))
+        })
+        .collect();
+
+    let stock_levels = build_stock_levels(&placement_mapping_results, inventory_items);
+
+    let mut shortfalls: Vec<StockShortfall> = vec![];
+
+    for (eda_placement, part_mapping_results) in placement_mapping_results.iter()
+        .filter(|(eda_placement, part_mapping_results)| eda_placement.place && !is_resolved_without_stock(part_mapping_results)) {
+        let placement_stock_levels: Vec<&StockLevel> = stock_levels.iter()
+            .filter(|stock_level| part_mapping_results.iter().any(|candidate| candidate.part_mapping.part.eq(stock_level.part)))
+            .collect();
+
+        if placement_stock_levels.iter().any(|stock_level| stock_level.is_sufficient()) {
+            continue
+        }
+
+        for stock_level in placement_stock_levels {
+            match shortfalls.iter_mut().find(|shortfall| shortfall.part.eq(stock_level.part)) {
+                Some(shortfall) => shortfall.ref_des.push(eda_placement.ref_des.clone()),
+                None => shortfalls.push(StockShortfall {
+                    part: stock_level.part,
+                    required: stock_level.required,
+                    available: stock_level.available,
+                    ref_des: vec![eda_placement.ref_des.clone()],
+                }),
+            }
+        }
+    }
+
+    shortfalls
+}
@@ -0,0 +1,174 @@
+use criteria::{ExactMatchCriterion, FieldCriterion, GenericCriteria};
+use eda::placement::{EdaPlacement, EdaPlacementValue};
+use pnp::part::Part;
+use crate::part_mapping::PartMapping;
+
+/// Mappings that are less similar to the placement than this are not suggested.
+const MINIMUM_SIMILARITY: f64 = 0.5;
+
+/// The maximum amount of suggestions for a placement.
+const MAXIMUM_SUGGESTIONS: usize = 3;
+
+#[derive(Debug, PartialEq)]
+pub struct PartMappingSuggestion<'mapping> {
+    pub part: &'mapping Part,
+    /// 0.0 to 1.0, where 1.0 is the most similar.
+    pub similarity: f64,
+}
+
+/// Suggests parts for a placement that has no mappings, using the existing mappings that are the most similar to the
+/// placement, e.g. a mapping for `RES_0402` and `10K 1%` is similar to a placement with `RES_0603` and `10K 1%`.
+///
+/// Exact match criteria are compared with the placement's field using the normalized levenshtein distance, other
+/// criteria are either similar, when they match, or not.  The similarity of a mapping is the average similarity of its
+/// criteria.
+pub fn suggest_part_mappings<'mapping>(eda_placement: &EdaPlacement, part_mappings: &'mapping [PartMapping<'mapping>]) -> Vec<PartMappingSuggestion<'mapping>> {
+    let mut suggestions: Vec<PartMappingSuggestion> = vec![];
+
+    for part_mapping in part_mappings.iter() {
+        let similarity = part_mapping.criteria.iter()
+            .filter_map(|criteria| (**criteria).as_any().downcast_ref::<GenericCriteria>())
+            .map(|criteria| criteria_similarity(eda_placement, &criteria.criteria))
+            .fold(0.0, f64::max);
+
+        if similarity < MINIMUM_SIMILARITY {
+            continue
+        }
+
+        // only the most similar mapping for each part is suggested
+        match suggestions.iter_mut().find(|suggestion| suggestion.part.eq(part_mapping.part)) {
+            Some(suggestion) => suggestion.similarity = suggestion.similarity.max(similarity),
+            None => suggestions.push(PartMappingSuggestion { part: part_mapping.part, similarity }),
+        }
+    }
+
+    // the order of the mappings is used when the similarity is the same
+    suggestions.sort_by(|a, b| b.similarity.total_cmp(&a.similarity));
+    suggestions.truncate(MAXIMUM_SUGGESTIONS);
+
+    suggestions
+}
+
+fn criteria_similarity(eda_placement: &EdaPlacement, criteria: &[Box<dyn FieldCriterion>]) -> f64 {
+    if criteria.is_empty() {
+        return 0.0
+    }
+
+    let total: f64 = criteria.iter().map(|criterion| {
+        if let Some(criterion) = (**criterion).as_any().downcast_ref::<ExactMatchCriterion>() {
+            return eda_placement.fields.iter()
+                .find(|field| field.name.eq(&criterion.field_name))
+                .map_or(0.0, |field| {
+                    // e.g. a '10K0' value is the same resistance as a '10k' pattern
+                    if field.typed_value().is_equivalent(&EdaPlacementValue::from(criterion.field_pattern.as_str())) {
+                        return 1.0
+                    }
+                    strsim::normalized_levenshtein(&field.value.to_lowercase(), &criterion.field_pattern.to_lowercase())
+                })
+        }
+
+        match eda_placement.matches_criteria(std::slice::from_ref(criterion)) {
+            true => 1.0,
+            false => 0.0,
+        }
+    }).sum();
+
+    total / criteria.len() as f64
+}
+
+#[cfg(test)]
+mod suggestion_tests {
+    use regex::Regex;
+    use criteria::{ExactMatchCriterion, GenericCriteria, RegexMatchCriterion};
+    use eda::placement::{EdaPlacement, EdaPlacementField};
+    use pnp::part::Part;
+    use crate::part_mapping::PartMapping;
+    use crate::suggestion::suggest_part_mappings;
+
+    #[test]
+    fn suggest_parts_from_similar_mappings() {
+        // given
+        let eda_placement = EdaPlacement {
+            ref_des: "R1".to_string(),
+            fields: vec![
+                EdaPlacementField::new("name".to_string(), "RES_0603".to_string()),
+                EdaPlacementField::new("value".to_string(), "10K 1%".to_string()),
+            ],
+            ..EdaPlacement::default()
+        };
+
+        // and
+        let part1 = Part::new("RES_MFR1".to_string(), "RES1".to_string());
+        let part2 = Part::new("RES_MFR2".to_string(), "RES2".to_string());
+        let part3 = Part::new("CAP_MFR1".to_string(), "CAP1".to_string());
+
+        let parts = [part1, part2, part3];
+
+        // and a mapping for the same value in a different package
+        let criteria1 = GenericCriteria { criteria: vec![
+            Box::new(ExactMatchCriterion::new("name".to_string(), "RES_0402".to_string() )),
+            Box::new(ExactMatchCriterion::new("value".to_string(), "10K 1%".to_string() )),
+        ]};
+        let part_mapping1 = PartMapping::new(&parts[1 - 1], vec![Box::new(criteria1)]);
+        // and a mapping with a regular expression that matches the value
+        let criteria2 = GenericCriteria { criteria: vec![
+            Box::new(ExactMatchCriterion::new("name".to_string(), "RES_0805".to_string() )),
+            Box::new(RegexMatchCriterion::new("value".to_string(), Regex::new("^10K").unwrap() )),
+        ]};
+        let part_mapping2 = PartMapping::new(&parts[2 - 1], vec![Box::new(criteria2)]);
+        // and a mapping for a different kind of part
+        let criteria3 = GenericCriteria { criteria: vec![
+            Box::new(ExactMatchCriterion::new("name".to_string(), "CAP_0402".to_string() )),
+            Box::new(ExactMatchCriterion::new("value".to_string(), "100nF 16V".to_string() )),
+        ]};
+        let part_mapping3 = PartMapping::new(&parts[3 - 1], vec![Box::new(criteria3)]);
+
+        let part_mappings = vec![part_mapping1, part_mapping2, part_mapping3];
+
+        // when
+        let suggestions = suggest_part_mappings(&eda_placement, &part_mappings);
+
+        // then
+        let suggested: Vec<(&Part, String)> = suggestions.iter()
+            .map(|suggestion| (suggestion.part, format!("{:.3}", suggestion.similarity)))
+            .collect();
+        assert_eq!(suggested, vec![
+            (&parts[0], "0.875".to_string()),
+            (&parts[1], "0.875".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn suggest_parts_with_equivalent_values() {
+        // given
+        let eda_placement = EdaPlacement {
+            ref_des: "R1".to_string(),
+            fields: vec![
+                EdaPlacementField::new("name".to_string(), "RES_0603".to_string()),
+                EdaPlacementField::new("value".to_string(), "10K0".to_string()),
+            ],
+            ..EdaPlacement::default()
+        };
+
+        // and
+        let parts = [Part::new("RES_MFR1".to_string(), "RES1".to_string())];
+
+        // and a mapping for the same resistance, written differently, in a different package
+        let criteria1 = GenericCriteria { criteria: vec![
+            Box::new(ExactMatchCriterion::new("name".to_string(), "RES_0402".to_string() )),
+            Box::new(ExactMatchCriterion::new("value".to_string(), "10k".to_string() )),
+        ]};
+        let part_mappings = vec![PartMapping::new(&parts[0], vec![Box::new(criteria1)])];
+
+        // when
+        let suggestions = suggest_part_mappings(&eda_placement, &part_mappings);
+
+        // then
+        let suggested: Vec<(&Part, String)> = suggestions.iter()
+            .map(|suggestion| (suggestion.part, format!("{:.3}", suggestion.similarity)))
+            .collect();
+        assert_eq!(suggested, vec![
+            (&parts[0], "0.875".to_string()),
+        ]);
+    }
+}
@@ -0,0 +1,28 @@
+use crate::part::Part;
+
+/// A quantity of a part that is in stock, e.g. a reel on a shelf.
+#[derive(Debug, PartialEq)]
+pub struct InventoryItem {
+    pub manufacturer: String,
+    pub mpn: String,
+    pub quantity: u32,
+    pub location: String,
+}
+
+impl InventoryItem {
+    pub fn new(manufacturer: String, mpn: String, quantity: u32, location: String) -> Self {
+        Self {
+            manufacturer,
+            mpn,
+            quantity,
+            location,
+        }
+    }
+}
+
+pub fn find_inventory_items_by_part<'inventory>(inventory_items: &'inventory [InventoryItem], part: &Part) -> Vec<&'inventory InventoryItem> {
+    inventory_items.iter().filter(|&inventory_item| {
+        inventory_item.manufacturer.eq(&part.manufacturer)
+            && inventory_item.mpn.eq(&part.mpn)
+    }).collect()
+}
@@ -1,7 +1,9 @@
 pub mod part;
+pub mod part_catalog;
 pub mod placement;
 
 pub mod load_out;
+pub mod inventory;
 pub mod object_path;
 
 pub mod pcb;
\ No newline at end of file
@@ -0,0 +1,227 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use thiserror::Error;
+use util::quantity::Quantity;
+use crate::part::Part;
+
+/// The value of a part attribute, e.g. the `height` of a part.
+///
+/// Well-known attributes are typed, e.g. `height` is a quantity and `pin_count` is an integer, other attributes are
+/// text.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PartAttributeValue {
+    Text(String),
+    Quantity(Quantity),
+    Integer(u32),
+    Boolean(bool),
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum PartAttributeError {
+    #[error("Invalid part attribute. name: {name:?}, value: {value:?}")]
+    InvalidValue { name: String, value: String },
+}
+
+impl PartAttributeValue {
+    pub fn parse(name: &str, value: &str) -> Result<Self, PartAttributeError> {
+        let invalid_value = || PartAttributeError::InvalidValue { name: name.to_string(), value: value.to_string() };
+
+        match name {
+            // values such as `10K 1%` are not quantities, so they are kept as text
+            "value" => Ok(Quantity::from_str(value)
+                .map_or_else(|_| PartAttributeValue::Text(value.to_string()), PartAttributeValue::Quantity)),
+            "height" => Quantity::from_str(value)
+                .map(PartAttributeValue::Quantity)
+                .map_err(|_| invalid_value()),
+            "pin_count" => u32::from_str(value)
+                .map(PartAttributeValue::Integer)
+                .map_err(|_| invalid_value()),
+            "polarity" => match value.to_lowercase().as_str() {
+                "true" | "yes" | "1" => Ok(PartAttributeValue::Boolean(true)),
+                "false" | "no" | "0" => Ok(PartAttributeValue::Boolean(false)),
+                _ => Err(invalid_value()),
+            },
+            _ => Ok(PartAttributeValue::Text(value.to_string())),
+        }
+    }
+}
+
+impl Display for PartAttributeValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PartAttributeValue::Text(text) => write!(f, "{}", text),
+            PartAttributeValue::Quantity(quantity) => write!(f, "{}", quantity),
+            PartAttributeValue::Integer(integer) => write!(f, "{}", integer),
+            PartAttributeValue::Boolean(boolean) => write!(f, "{}", boolean),
+        }
+    }
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum PartCatalogError {
+    #[error("Duplicate part with different attributes. manufacturer: {manufacturer:?}, mpn: {mpn:?}")]
+    DuplicatePart { manufacturer: String, mpn: String },
+}
+
+/// Attributes by name, e.g. `package`, `description`, `value`, `height`, `body_size`, `pin_count`, `polarity` and `msl`.
+pub type PartAttributes = BTreeMap<String, PartAttributeValue>;
+
+/// The parts and their attributes.
+#[derive(Debug, Default, PartialEq)]
+pub struct PartCatalog {
+    parts: Vec<Part>,
+    // same order as `parts`
+    attributes: Vec<PartAttributes>,
+}
+
+impl PartCatalog {
+    /// Adding a part again with the same attributes has no effect, a part cannot be added again with different
+    /// attributes, otherwise it would be ambiguous which attributes apply to it.
+    pub fn add(&mut self, part: Part, attributes: PartAttributes) -> Result<(), PartCatalogError> {
+        if let Some(existing_attributes) = self.attributes(&part) {
+            return match existing_attributes.eq(&attributes) {
+                true => Ok(()),
+                false => Err(PartCatalogError::DuplicatePart { manufacturer: part.manufacturer, mpn: part.mpn }),
+            }
+        }
+
+        self.parts.push(part);
+        self.attributes.push(attributes);
+
+        Ok(())
+    }
+
+    pub fn parts(&self) -> &[Part] {
+        &self.parts
+    }
+
+    pub fn attributes(&self, part: &Part) -> Option<&PartAttributes> {
+        self.parts.iter()
+            .position(|candidate| candidate.eq(part))
+            .map(|index| &self.attributes[index])
+    }
+
+    pub fn attribute(&self, part: &Part, name: &str) -> Option<&PartAttributeValue> {
+        self.attributes(part)
+            .and_then(|attributes| attributes.get(name))
+    }
+
+    /// Finds the parts that have an attribute matching the predicate, e.g. all the parts with a `pin_count` of 2.
+    pub fn find_by_attribute<P>(&self, name: &str, predicate: P) -> Vec<&Part>
+    where
+        P: Fn(&PartAttributeValue) -> bool
+    {
+        self.parts.iter().zip(self.attributes.iter())
+            .filter(|(_part, attributes)| attributes.get(name).is_some_and(&predicate))
+            .map(|(part, _attributes)| part)
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.parts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parts.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod part_catalog_tests {
+    use std::str::FromStr;
+    use rstest::rstest;
+    use util::quantity::Quantity;
+    use crate::part::Part;
+    use crate::part_catalog::{PartAttributeError, PartAttributes, PartAttributeValue, PartCatalog, PartCatalogError};
+
+    #[rstest]
+    #[case("package", "0402", Ok(PartAttributeValue::Text("0402".to_string())))]
+    #[case("value", "100nF", Ok(PartAttributeValue::Quantity(Quantity::from_str("100nF").unwrap())))]
+    #[case("value", "10K 1%", Ok(PartAttributeValue::Text("10K 1%".to_string())))]
+    #[case("height", "0.5mm", Ok(PartAttributeValue::Quantity(Quantity::from_str("0.5mm").unwrap())))]
+    #[case("height", "tall", Err(PartAttributeError::InvalidValue { name: "height".to_string(), value: "tall".to_string() }))]
+    #[case("pin_count", "2", Ok(PartAttributeValue::Integer(2)))]
+    #[case("pin_count", "-1", Err(PartAttributeError::InvalidValue { name: "pin_count".to_string(), value: "-1".to_string() }))]
+    #[case("polarity", "Yes", Ok(PartAttributeValue::Boolean(true)))]
+    #[case("polarity", "0", Ok(PartAttributeValue::Boolean(false)))]
+    #[case("polarity", "maybe", Err(PartAttributeError::InvalidValue { name: "polarity".to_string(), value: "maybe".to_string() }))]
+    #[case("supplier_note", "42", Ok(PartAttributeValue::Text("42".to_string())))]
+    fn parse_attribute(#[case] name: &str, #[case] value: &str, #[case] expected_result: Result<PartAttributeValue, PartAttributeError>) {
+        // when
+        let result = PartAttributeValue::parse(name, value);
+
+        // then
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn query_catalog() {
+        // given
+        let part1 = Part::new("RES_MFR1".to_string(), "RES1".to_string());
+        let part2 = Part::new("CAP_MFR1".to_string(), "CAP1".to_string());
+        let part3 = Part::new("CONN_MFR1".to_string(), "CONN1".to_string());
+
+        // and
+        let mut catalog = PartCatalog::default();
+        catalog.add(part1.clone(), PartAttributes::from([
+            ("package".to_string(), PartAttributeValue::Text("0402".to_string())),
+            ("pin_count".to_string(), PartAttributeValue::Integer(2)),
+        ])).unwrap();
+        catalog.add(part2.clone(), PartAttributes::from([
+            ("package".to_string(), PartAttributeValue::Text("0402".to_string())),
+        ])).unwrap();
+        catalog.add(part3.clone(), PartAttributes::new()).unwrap();
+
+        // when
+        let parts_in_0402 = catalog.find_by_attribute("package", |value| value.eq(&PartAttributeValue::Text("0402".to_string())));
+
+        // then
+        assert_eq!(catalog.parts(), &[part1.clone(), part2.clone(), part3.clone()]);
+        assert_eq!(parts_in_0402, vec![&part1, &part2]);
+        assert_eq!(catalog.attribute(&part1, "pin_count"), Some(&PartAttributeValue::Integer(2)));
+        assert_eq!(catalog.attribute(&part2, "pin_count"), None);
+        assert_eq!(catalog.attributes(&part3), Some(&PartAttributes::new()));
+    }
+
+    #[test]
+    fn add_duplicate_part_with_the_same_attributes() {
+        // given
+        let part1 = Part::new("RES_MFR1".to_string(), "RES1".to_string());
+        let attributes = PartAttributes::from([
+            ("package".to_string(), PartAttributeValue::Text("0402".to_string())),
+        ]);
+
+        // and
+        let mut catalog = PartCatalog::default();
+        catalog.add(part1.clone(), attributes.clone()).unwrap();
+
+        // when
+        let result = catalog.add(part1.clone(), attributes);
+
+        // then
+        assert_eq!(result, Ok(()));
+        assert_eq!(catalog.parts(), &[part1]);
+    }
+
+    #[test]
+    fn add_duplicate_part_with_different_attributes() {
+        // given
+        let part1 = Part::new("RES_MFR1".to_string(), "RES1".to_string());
+
+        // and
+        let mut catalog = PartCatalog::default();
+        catalog.add(part1.clone(), PartAttributes::from([
+            ("package".to_string(), PartAttributeValue::Text("0402".to_string())),
+        ])).unwrap();
+
+        // when
+        let result = catalog.add(part1.clone(), PartAttributes::from([
+            ("package".to_string(), PartAttributeValue::Text("0603".to_string())),
+        ]));
+
+        // then
+        assert_eq!(result, Err(PartCatalogError::DuplicatePart { manufacturer: "RES_MFR1".to_string(), mpn: "RES1".to_string() }));
+        assert_eq!(catalog.attribute(&part1, "package"), Some(&PartAttributeValue::Text("0402".to_string())));
+    }
+}
@@ -26,4 +26,90 @@ pub fn load(assembly_rule_source: &String) -> Result<Vec<AssemblyRule>, Error>
         assembly_rules.push(assembly_rule);
     }
     Ok(assembly_rules)
-}
\ No newline at end of file
+}
+#[cfg(test)]
+pub mod csv_loading_tests {
+    use std::str::FromStr;
+    use assert_fs::TempDir;
+    use indoc::indoc;
+    use assembly::rules::{AssemblyRule, RefDesPattern};
+    use crate::assembly_rules::load;
+
+    #[test]
+    pub fn load_rules_with_patterns_and_assembly_variants() -> anyhow::Result<()> {
+        // given
+        let temp_dir = TempDir::new()?;
+        let mut test_assembly_rules_path = temp_dir.path().to_path_buf();
+        test_assembly_rules_path.push("assembly_rules.csv");
+        let test_assembly_rules_source = test_assembly_rules_path.to_str().unwrap().to_string();
+
+        std::fs::write(test_assembly_rules_path, indoc! {r#"
+            "RefDes","Manufacturer","Mpn","AssemblyVariant"
+            "R1","RES_MFR1","RES1",""
+            "C1-C40","CAP_MFR1","CAP1","Variant_1"
+            "/^D\d$/","DIO_MFR1","DIO1","Variant_2"
+        "#})?;
+
+        // and
+        let expected_result = vec![
+            AssemblyRule {
+                ref_des: RefDesPattern::Exact("R1".to_string()),
+                manufacturer: "RES_MFR1".to_string(),
+                mpn: "RES1".to_string(),
+                assembly_variant: None,
+            },
+            AssemblyRule {
+                ref_des: RefDesPattern::Range { prefix: "C".to_string(), start: 1, end: 40 },
+                manufacturer: "CAP_MFR1".to_string(),
+                mpn: "CAP1".to_string(),
+                assembly_variant: Some("Variant_1".to_string()),
+            },
+            AssemblyRule {
+                ref_des: RefDesPattern::from_str(r"/^D\d$/")?,
+                manufacturer: "DIO_MFR1".to_string(),
+                mpn: "DIO1".to_string(),
+                assembly_variant: Some("Variant_2".to_string()),
+            },
+        ];
+
+        // when
+        let result = load(&test_assembly_rules_source)?;
+
+        // then
+        assert_eq!(result, expected_result);
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn load_rules_without_assembly_variants() -> anyhow::Result<()> {
+        // given
+        let temp_dir = TempDir::new()?;
+        let mut test_assembly_rules_path = temp_dir.path().to_path_buf();
+        test_assembly_rules_path.push("assembly_rules.csv");
+        let test_assembly_rules_source = test_assembly_rules_path.to_str().unwrap().to_string();
+
+        std::fs::write(test_assembly_rules_path, indoc! {r#"
+            "RefDes","Manufacturer","Mpn"
+            "R1","RES_MFR1","RES1"
+        "#})?;
+
+        // and
+        let expected_result = vec![
+            AssemblyRule {
+                ref_des: RefDesPattern::Exact("R1".to_string()),
+                manufacturer: "RES_MFR1".to_string(),
+                mpn: "RES1".to_string(),
+                assembly_variant: None,
+            },
+        ];
+
+        // when
+        let result = load(&test_assembly_rules_source)?;
+
+        // then
+        assert_eq!(result, expected_result);
+
+        Ok(())
+    }
+}
@@ -1,21 +1,21 @@
 use std::collections::HashMap;
 use thiserror::Error;
-use heck::ToUpperCamelCase;
-use regex::{Error, Regex};
-use assembly::rules::AssemblyRule;
-use criteria::{ExactMatchCriterion, GenericCriteria, RegexMatchCriterion, FieldCriterion};
+use heck::{ToSnakeCase, ToUpperCamelCase};
+use std::str::FromStr;
+use regex::Regex;
+use assembly::rules::{AssemblyRule, RefDesPattern};
+use criteria::{AllOfCriterion, AnyOfCriterion, ExactMatchCriterion, GenericCriteria, NotCriterion, RegexMatchCriterion, FieldCriterion, QuantityMatchCriterion, QuantityRangeCriterion};
 use eda::EdaTool;
+use eda::placement::{EdaPlacement, EDA_PLACEMENT_ATTRIBUTE_NAMES};
+use eda::profile::EdaProfile;
 use eda::substitution::{EdaSubstitutionRule, EdaSubstitutionRuleTransformItem};
 use part_mapper::criteria::PlacementMappingCriteria;
 use part_mapper::part_mapping::PartMapping;
 use pnp::part::Part;
+use pnp::part_catalog::{PartAttributeError, PartAttributes, PartAttributeValue};
 use pnp::load_out::LoadOutItem;
-
-#[derive(Debug, serde::Deserialize)]
-enum CSVEdaToolValue {
-    DipTrace,
-    KiCad,
-}
+use pnp::inventory::InventoryItem;
+use util::quantity::{Quantity, QuantityRange};
 
 #[derive(Debug, serde::Deserialize)]
 #[serde(rename_all(deserialize = "PascalCase"))]
@@ -36,11 +36,30 @@ pub enum PartMappingRecordError {
     MissingField { field: String },
 
     #[error("Invalid regular expression. reason: {error:?}")]
-    InvalidRegex { error: regex::Error }
+    InvalidRegex { error: regex::Error },
+
+    #[error("Invalid expression. value: {value:?}")]
+    InvalidExpression { value: String },
+
+    #[error("Invalid PCB side, expected 'top' or 'bottom'. value: {value:?}")]
+    InvalidPcbSide { value: String },
+
+    #[error("Invalid priority. value: {value:?}")]
+    InvalidPriority { value: String },
+}
+
+impl From<ValueKindError> for PartMappingRecordError {
+    fn from(error: ValueKindError) -> Self {
+        match error {
+            ValueKindError::InvalidRegex(error) => PartMappingRecordError::InvalidRegex { error },
+            ValueKindError::InvalidExpression(value) => PartMappingRecordError::InvalidExpression { value },
+            ValueKindError::InvalidPcbSide(value) => PartMappingRecordError::InvalidPcbSide { value },
+        }
+    }
 }
 
 impl PartMappingRecord {
-    pub fn build_part_mapping<'part>(&self, parts: &'part [Part]) -> Result<PartMapping<'part>, PartMappingRecordError> {
+    pub fn build_part_mapping<'part>(&self, parts: &'part [Part], eda_profiles: &[EdaProfile]) -> Result<PartMapping<'part>, PartMappingRecordError> {
 
         // NOTE: Initially the PartMappingRecord had more properties and was using serde flatten on the fields but there was a bug;
         //       so we have to do some deserialization manually instead.
@@ -55,7 +74,7 @@ impl PartMappingRecord {
         let mpn = fields.get("Mpn")
             .ok_or(PartMappingRecordError::MissingField{ field: "Mpn".to_string() })?;
 
-        let eda = csv_eda_tool_value_to_eda_tool(eda).ok_or(PartMappingRecordError::UnknownEda { eda: eda.clone() })?;
+        let eda = csv_eda_tool_value_to_eda_tool(eda, eda_profiles).ok_or(PartMappingRecordError::UnknownEda { eda: eda.clone() })?;
 
 
         let part_criteria: Part = Part { manufacturer: manufacturer.clone(), mpn: mpn.clone() };
@@ -73,33 +92,39 @@ impl PartMappingRecord {
 
         let mut mapping_criteria: Vec<Box<dyn PlacementMappingCriteria>> = vec![];
 
-        let mut matched_fields: Vec<(&String, &String)> = fields.iter().filter_map(|(key, value)|{
-            match fields_names.contains(&key.to_lowercase().as_str()) {
-                true => Some((key, value)),
-                false => None,
+        // the EDA specific fields are always criteria, other placement fields are only criteria when their 'Eda' prefixed
+        // column, e.g. 'EdaMpn', has a value, so that a file can contain mappings for multiple EDA tools.
+        // any other columns, e.g. 'Notes', are not criteria.
+        let mut matched_fields: Vec<(String, &String)> = fields.iter().filter_map(|(key, value)|{
+            let field_name = key.to_snake_case();
+            if fields_names.contains(&field_name) {
+                Some((field_name, value))
+            } else if value.is_empty() {
+                None
+            } else {
+                eda_field_column_to_field_name(key).map(|field_name| (field_name, value))
             }
         }).collect();
         
         matched_fields.sort();
 
-        let criteria_fields: Vec<Box<dyn FieldCriterion>> = matched_fields.iter().try_fold(vec![], |mut acc, (&ref key, &ref value)| {
-            let value_kind = build_value_kind(&value)
-                .map_err(|error| PartMappingRecordError::InvalidRegex { error })?;
-
-            let boxed_criterion: Box<dyn FieldCriterion> = match value_kind {
-                ValueKind::Regex(regex) => 
-                    Box::new(RegexMatchCriterion::new(key.to_lowercase(), regex)),
-                ValueKind::ExactMatch(value) => 
-                    Box::new(ExactMatchCriterion::new(key.to_lowercase(), value)),
-            };
+        let criteria_fields: Vec<Box<dyn FieldCriterion>> = matched_fields.iter().try_fold(vec![], |mut acc, (field_name, &ref value)| {
+            let boxed_criterion = build_field_criterion(field_name, build_field_value_kind(field_name, value)?);
             acc.push(boxed_criterion);
-            Ok(acc)
+            Ok::<_, PartMappingRecordError>(acc)
         })?;
         let criteria = GenericCriteria { criteria: criteria_fields };
 
         mapping_criteria.push(Box::new(criteria));
 
-        let part_mapping = PartMapping::new(part_ref, mapping_criteria);
+        // an optional column, an empty value means the mapping has no priority.
+        let priority = match fields.get("Priority").map(|value| value.trim()) {
+            Some(value) if !value.is_empty() => Some(value.parse::<u32>()
+                .map_err(|_error| PartMappingRecordError::InvalidPriority { value: value.to_string() })?),
+            _ => None,
+        };
+
+        let part_mapping = PartMapping { priority, ..PartMapping::new(part_ref, mapping_criteria) };
 
         Ok(part_mapping)
     }
@@ -107,37 +132,222 @@ impl PartMappingRecord {
 
 pub enum ValueKind {
     Regex(Regex),
-    ExactMatch(String)
+    ExactMatch(String),
+    Quantity(Quantity),
+    QuantityRange(QuantityRange),
+    AnyOf(Vec<ValueKind>),
+    AllOf(Vec<ValueKind>),
+    Not(Box<ValueKind>),
+}
+
+#[derive(Error, Debug)]
+pub enum ValueKindError {
+    #[error("Invalid regular expression. reason: {0:?}")]
+    InvalidRegex(regex::Error),
+
+    #[error("Invalid expression. value: {0:?}")]
+    InvalidExpression(String),
+
+    #[error("Invalid PCB side, expected 'top' or 'bottom'. value: {0:?}")]
+    InvalidPcbSide(String),
 }
 
-pub fn build_value_kind(value: &str) -> Result<ValueKind, Error> {
+/// `/<regex>/` is a regular expression, `[<quantity>]` is a quantity, e.g. `[10k]`, `[<range>]` is a range of
+/// quantities, e.g. `[9.9k..10.1k]`, `[10k±1%]` or `[<=1.2mm]`, anything else is an exact match, including bracketed
+/// values that are not quantities, e.g. `[DNP]`.
+///
+/// Values can be combined using `any(<value>, ...)`, `all(<value>, ...)` and `not(<value>)`, e.g. `any(0402, 0603)`,
+/// `not(DNP)` or `all(/^RES_/, not(RES_0201))`.
+pub fn build_value_kind(value: &str) -> Result<ValueKind, ValueKindError> {
     if value.starts_with('/') && value.ends_with('/') {
         let (_prefix, remainder) = value.split_at(1);
         let mut value = remainder.to_string();
         value.pop();
 
-        let regex = Regex::new(&value)?;
+        let regex = Regex::new(&value)
+            .map_err(ValueKindError::InvalidRegex)?;
 
         Ok(ValueKind::Regex(regex))
+    } else if let Some(value_kind) = value.strip_prefix('[')
+        .and_then(|remainder| remainder.strip_suffix(']'))
+        .and_then(build_quantity_value_kind) {
+
+        Ok(value_kind)
+    } else if let Some((function, arguments)) = split_function(value) {
+        let arguments = split_arguments(arguments)
+            .into_iter()
+            .map(build_value_kind)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        match (function, arguments.len()) {
+            (_, 0) => Err(ValueKindError::InvalidExpression(value.to_string())),
+            ("any", _) => Ok(ValueKind::AnyOf(arguments)),
+            ("all", _) => Ok(ValueKind::AllOf(arguments)),
+            ("not", 1) => Ok(ValueKind::Not(Box::new(arguments.into_iter().next().unwrap()))),
+            _ => Err(ValueKindError::InvalidExpression(value.to_string())),
+        }
     } else {
         Ok(ValueKind::ExactMatch(value.to_string()))
     }
 }
 
+/// As `build_value_kind`, but exact `pcb_side` values are matched against the lowercase `top` or `bottom` that
+/// placements expose, so `Top` is accepted and unknown sides are rejected instead of silently matching nothing.
+fn build_field_value_kind(field_name: &str, value: &str) -> Result<ValueKind, ValueKindError> {
+    let value_kind = build_value_kind(value)?;
+
+    match field_name {
+        "pcb_side" => normalize_pcb_side(value_kind),
+        _ => Ok(value_kind),
+    }
+}
+
+fn normalize_pcb_side(value_kind: ValueKind) -> Result<ValueKind, ValueKindError> {
+    let normalize_all = |value_kinds: Vec<ValueKind>| value_kinds.into_iter()
+        .map(normalize_pcb_side)
+        .collect::<Result<Vec<_>, _>>();
+
+    match value_kind {
+        ValueKind::ExactMatch(value) => match value.trim().to_lowercase().as_str() {
+            side @ ("top" | "bottom") => Ok(ValueKind::ExactMatch(side.to_string())),
+            _ => Err(ValueKindError::InvalidPcbSide(value)),
+        },
+        ValueKind::AnyOf(value_kinds) => Ok(ValueKind::AnyOf(normalize_all(value_kinds)?)),
+        ValueKind::AllOf(value_kinds) => Ok(ValueKind::AllOf(normalize_all(value_kinds)?)),
+        ValueKind::Not(value_kind) => Ok(ValueKind::Not(Box::new(normalize_pcb_side(*value_kind)?))),
+        value_kind => Ok(value_kind),
+    }
+}
+
+/// `None` when the value is neither a quantity nor a range of quantities.
+fn build_quantity_value_kind(value: &str) -> Option<ValueKind> {
+    if let Ok(quantity) = Quantity::from_str(value) {
+        return Some(ValueKind::Quantity(quantity))
+    }
+
+    QuantityRange::from_str(value).ok().map(ValueKind::QuantityRange)
+}
+
+/// e.g. `any(0402, 0603)` -> (`any`, `0402, 0603`)
+fn split_function(value: &str) -> Option<(&str, &str)> {
+    ["any", "all", "not"].into_iter().find_map(|function| {
+        value.trim()
+            .strip_prefix(function)
+            .and_then(|remainder| remainder.strip_prefix('('))
+            .and_then(|remainder| remainder.strip_suffix(')'))
+            .map(|arguments| (function, arguments))
+    })
+}
+
+/// Splits on the commas that are not inside a nested expression, a quantity or a regular expression.
+fn split_arguments(arguments: &str) -> Vec<&str> {
+    let mut result = vec![];
+    let mut depth = 0;
+    let mut in_regex = false;
+    let mut start = 0;
+
+    for (index, c) in arguments.char_indices() {
+        if in_regex {
+            // a regular expression ends with a '/' that is followed by the end of the argument
+            let remainder = arguments[index + 1..].trim_start();
+            if c == '/' && (remainder.is_empty() || remainder.starts_with([',', ')'])) {
+                in_regex = false;
+            }
+            continue
+        }
+
+        match c {
+            '/' if arguments[start..index].trim().is_empty() => in_regex = true,
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth -= 1,
+            ',' if depth == 0 => {
+                result.push(arguments[start..index].trim());
+                start = index + 1;
+            },
+            _ => (),
+        }
+    }
+
+    let last = arguments[start..].trim();
+    if !(result.is_empty() && last.is_empty()) {
+        result.push(last);
+    }
+
+    result
+}
+
+fn build_field_criterion(field_name: &str, value_kind: ValueKind) -> Box<dyn FieldCriterion> {
+    let build_field_criteria = |value_kinds: Vec<ValueKind>| value_kinds.into_iter()
+        .map(|value_kind| build_field_criterion(field_name, value_kind))
+        .collect();
+
+    match value_kind {
+        ValueKind::Regex(regex) =>
+            Box::new(RegexMatchCriterion::new(field_name.to_string(), regex)),
+        ValueKind::ExactMatch(value) =>
+            Box::new(ExactMatchCriterion::new(field_name.to_string(), value)),
+        ValueKind::Quantity(quantity) =>
+            Box::new(QuantityMatchCriterion::new(field_name.to_string(), quantity)),
+        ValueKind::QuantityRange(range) =>
+            Box::new(QuantityRangeCriterion::new(field_name.to_string(), range)),
+        ValueKind::AnyOf(value_kinds) =>
+            Box::new(AnyOfCriterion { criteria: build_field_criteria(value_kinds) }),
+        ValueKind::AllOf(value_kinds) =>
+            Box::new(AllOfCriterion { criteria: build_field_criteria(value_kinds) }),
+        ValueKind::Not(value_kind) =>
+            Box::new(NotCriterion { criterion: build_field_criterion(field_name, *value_kind) }),
+    }
+}
+
+/// The `Manufacturer` and `Mpn` columns are required, any other columns are part attributes, e.g. `Package`,
+/// `Description`, `Value`, `Height`, `BodySize`, `PinCount`, `Polarity` and `Msl`.
 #[derive(Debug, serde::Deserialize)]
-#[serde(rename_all(deserialize = "PascalCase"))]
-pub struct PartRecord {
-    manufacturer: String,
-    mpn: String,
+pub struct PartRecord(HashMap<String, String>);
+
+#[derive(Error, Debug)]
+pub enum PartRecordError {
+    #[error("Missing field. field: {field:?}")]
+    MissingField { field: String },
+
+    #[error("Invalid attribute. column: {column:?}, reason: {error}")]
+    InvalidAttribute { column: String, error: PartAttributeError },
 }
 
+const PART_RESERVED_COLUMNS: [&str; 2] = ["Manufacturer", "Mpn"];
+
 impl PartRecord {
-    pub fn build_part(&self) -> Result<Part, anyhow::Error> {
+    pub fn build_part(&self) -> Result<Part, PartRecordError> {
+        // NOTE: See `PartMappingRecord` for why serde flatten is not used.
+
+        let fields = &self.0;
+
+        let manufacturer = fields.get("Manufacturer")
+            .ok_or(PartRecordError::MissingField { field: "Manufacturer".to_string() })?;
+        let mpn = fields.get("Mpn")
+            .ok_or(PartRecordError::MissingField { field: "Mpn".to_string() })?;
+
         Ok(Part {
-            manufacturer: self.manufacturer.clone(),
-            mpn: self.mpn.clone(),
+            manufacturer: manufacturer.clone(),
+            mpn: mpn.clone(),
         })
     }
+
+    /// Attribute names are the snake case column names, e.g. `BodySize` -> `body_size`, empty values are ignored.
+    pub fn build_part_attributes(&self) -> Result<PartAttributes, PartRecordError> {
+        let mut attributes = PartAttributes::new();
+
+        for (column, value) in self.0.iter()
+            .filter(|(column, value)| !PART_RESERVED_COLUMNS.contains(&column.as_str()) && !value.is_empty()) {
+
+            let name = column.to_snake_case();
+            let attribute_value = PartAttributeValue::parse(&name, value)
+                .map_err(|error| PartRecordError::InvalidAttribute { column: column.clone(), error })?;
+
+            attributes.insert(name, attribute_value);
+        }
+
+        Ok(attributes)
+    }
 }
 
 #[derive(Debug, serde::Deserialize, serde::Serialize)]
@@ -158,6 +368,26 @@ impl LoadOutItemRecord {
     }
 }
 
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct InventoryItemRecord {
+    pub manufacturer: String,
+    pub mpn: String,
+    pub quantity: u32,
+    pub location: String,
+}
+
+impl InventoryItemRecord {
+    pub fn build_inventory_item(&self) -> Result<InventoryItem, anyhow::Error> {
+        Ok(InventoryItem {
+            manufacturer: self.manufacturer.clone(),
+            mpn: self.mpn.clone(),
+            quantity: self.quantity,
+            location: self.location.clone(),
+        })
+    }
+}
+
 #[derive(Debug, serde::Deserialize)]
 #[serde(rename_all(deserialize = "PascalCase"))]
 pub struct SubstitutionRecord(HashMap<String, String>);
@@ -174,11 +404,34 @@ pub enum SubstitutionRecordError {
     MissingField { field: String },
 
     #[error("Invalid regular expression. reason: {error:?}")]
-    InvalidRegex { error: regex::Error }
+    InvalidRegex { error: regex::Error },
+
+    #[error("Invalid expression. value: {value:?}")]
+    InvalidExpression { value: String },
+
+    #[error("Invalid PCB side, expected 'top' or 'bottom'. value: {value:?}")]
+    InvalidPcbSide { value: String },
+
+    #[error("Placement attributes cannot be substituted. column: {column:?}")]
+    AttributeTransform { column: String },
+
+    /// e.g. `$1x` refers to a group named `1x`, use `${1}x` to refer to group 1, or `$$` for a literal `$`.
+    #[error("Unknown capture group reference. field: {field:?}, reference: {reference:?}")]
+    UnknownCaptureGroup { field: String, reference: String },
+}
+
+impl From<ValueKindError> for SubstitutionRecordError {
+    fn from(error: ValueKindError) -> Self {
+        match error {
+            ValueKindError::InvalidRegex(error) => SubstitutionRecordError::InvalidRegex { error },
+            ValueKindError::InvalidExpression(value) => SubstitutionRecordError::InvalidExpression { value },
+            ValueKindError::InvalidPcbSide(value) => SubstitutionRecordError::InvalidPcbSide { value },
+        }
+    }
 }
 
 impl SubstitutionRecord {
-    pub fn build_eda_substitution(&self) -> anyhow::Result<EdaSubstitutionRule, SubstitutionRecordError> {
+    pub fn build_eda_substitution(&self, eda_profiles: &[EdaProfile]) -> anyhow::Result<EdaSubstitutionRule, SubstitutionRecordError> {
 
         // NOTE: Initially the SubstitutionRecord had more properties and was using serde flatten on the fields but there was a bug;
         //       so we have to do some deserialization manually instead.
@@ -189,58 +442,207 @@ impl SubstitutionRecord {
         let eda = fields.get("Eda")
             .ok_or(SubstitutionRecordError::MissingField{ field: "Eda".to_string() })?;
 
-        let eda = csv_eda_tool_value_to_eda_tool(eda).ok_or(SubstitutionRecordError::UnknownEda { eda: eda.clone() })?;
+        let eda = csv_eda_tool_value_to_eda_tool(eda, eda_profiles).ok_or(SubstitutionRecordError::UnknownEda { eda: eda.clone() })?;
 
         let fields_names = eda_fields_names(&eda);
 
         let mut criteria: Vec<Box<dyn FieldCriterion>> = vec![];
         let mut transforms: Vec<EdaSubstitutionRuleTransformItem> = vec![];
 
-        for &field_name in fields_names.iter() {
-            // Note: heck's UpperCamelCase appears to be the same as serde's PascalCase
-            //       however we can't use serde's case transforms as they are internal to serde.
-            //       see serde_derive::internals::case::RenameRule
+        // Note: heck's UpperCamelCase appears to be the same as serde's PascalCase
+        //       however we can't use serde's case transforms as they are internal to serde.
+        //       see serde_derive::internals::case::RenameRule
 
-            let name_field = field_name.to_upper_camel_case();
-            let pattern_field = format!("{}_pattern", field_name).to_upper_camel_case();
+        let mut fields_columns: Vec<(String, String, String)> = fields_names.iter().map(|field_name| {
+            (field_name.clone(), field_name.to_upper_camel_case(), format!("{}_pattern", field_name).to_upper_camel_case())
+        }).collect();
 
-            match (fields.get(&name_field), fields.get(&pattern_field)) {
-                (Some(field_name_value), Some(pattern_value)) => {
+        // other '<Name>Pattern' columns are only used when they have a value, so that a file can contain substitutions for multiple EDA tools.
+        let mut other_fields_columns: Vec<(String, String, String)> = fields.iter().filter_map(|(key, value)| {
+            let name_field = key.strip_suffix("Pattern")?;
+            let field_name = column_to_field_name(name_field);
+            match fields_names.contains(&field_name) || value.is_empty() {
+                true => None,
+                false => Some((field_name, name_field.to_string(), key.clone())),
+            }
+        }).collect();
+        other_fields_columns.sort();
+        fields_columns.extend(other_fields_columns);
+
+        for (field_name, name_field, pattern_field) in fields_columns.iter() {
 
-                    let value_kind = build_value_kind(&pattern_value)
-                        .map_err(|error| SubstitutionRecordError::InvalidRegex { error })?;
+            match (fields.get(name_field), fields.get(pattern_field)) {
+                // placement attributes, e.g. 'RefDesPattern' or 'XPattern', are only criteria, they cannot be substituted.
+                (_, Some(pattern_value)) if EDA_PLACEMENT_ATTRIBUTE_NAMES.contains(&field_name.as_str()) => {
+                    criteria.push(build_field_criterion(field_name, build_field_value_kind(field_name, pattern_value)?));
+                },
+                (Some(field_name_value), Some(pattern_value)) => {
 
-                    let boxed_criterion: Box<dyn FieldCriterion> = match value_kind {
-                        ValueKind::Regex(regex) =>
-                            Box::new(RegexMatchCriterion { field_name: field_name.to_string(), field_pattern: regex }),
-                        ValueKind::ExactMatch(value) =>
-                            Box::new(ExactMatchCriterion { field_name: field_name.to_string(), field_pattern: value }),
-                    };
+                    let boxed_criterion = build_field_criterion(field_name, build_field_value_kind(field_name, pattern_value)?);
                     criteria.push(boxed_criterion);
                     transforms.push(EdaSubstitutionRuleTransformItem { field_name: field_name.to_string(), field_value: field_name_value.to_string() } );
                 },
-                _ => return Err(SubstitutionRecordError::FieldMismatch(vec![name_field, pattern_field])),
+                _ => return Err(SubstitutionRecordError::FieldMismatch(vec![name_field.clone(), pattern_field.clone()])),
             }
         }
 
-        Ok(EdaSubstitutionRule { criteria, transforms })
+        // 'Set<Name>' columns are only transforms, the field is added to placements that do not have it.
+        let mut set_fields_columns: Vec<(&String, &str, &String)> = fields.iter().filter_map(|(key, value)| {
+            let name_field = key.strip_prefix(SET_FIELD_COLUMN_PREFIX)?;
+            match name_field.starts_with(char::is_uppercase) && !value.is_empty() {
+                true => Some((key, name_field, value)),
+                false => None,
+            }
+        }).collect();
+        set_fields_columns.sort();
+
+        for (key, name_field, value) in set_fields_columns {
+            let field_name = column_to_field_name(name_field);
+            if EDA_PLACEMENT_ATTRIBUTE_NAMES.contains(&field_name.as_str()) {
+                return Err(SubstitutionRecordError::AttributeTransform { column: key.clone() })
+            }
+            transforms.push(EdaSubstitutionRuleTransformItem { field_name, field_value: value.clone() });
+        }
+
+        // e.g. 'RemoveFields' = 'Description, Notes'
+        let removals = match fields.get(REMOVE_FIELDS_COLUMN) {
+            Some(value) => value.split(',')
+                .map(str::trim)
+                .filter(|column| !column.is_empty())
+                .map(|column| {
+                    let field_name = column_to_field_name(column);
+                    match EDA_PLACEMENT_ATTRIBUTE_NAMES.contains(&field_name.as_str()) {
+                        true => Err(SubstitutionRecordError::AttributeTransform { column: REMOVE_FIELDS_COLUMN.to_string() }),
+                        false => Ok(field_name),
+                    }
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+            None => vec![],
+        };
+
+        let eda_substitution_rule = EdaSubstitutionRule { criteria, transforms, removals, line: None };
+
+        if let Some((field, reference)) = eda_substitution_rule.unknown_capture_group_references().into_iter().next() {
+            return Err(SubstitutionRecordError::UnknownCaptureGroup { field, reference })
+        }
+
+        Ok(eda_substitution_rule)
     }
 }
 
-fn eda_fields_names(eda: &EdaTool) -> &'static [&'static str] {
-    match eda {
+/// Prefix for substitution columns that set a field without a criterion, e.g. `SetMpn` adds or overwrites the `mpn` field.
+const SET_FIELD_COLUMN_PREFIX: &str = "Set";
+
+/// Substitution column listing the fields to remove from the placement, e.g. `Description, Notes`.
+const REMOVE_FIELDS_COLUMN: &str = "RemoveFields";
+
+/// Columns of the part-mapping file that are not placement fields, placement fields with the same name use the prefixed
+/// column, see `EDA_FIELD_COLUMN_PREFIX`.
+const PART_MAPPING_RESERVED_COLUMNS: [&str; 4] = ["Eda", "Manufacturer", "Mpn", "Priority"];
+
+/// Prefix for part-mapping columns that refer to placement fields, e.g. `EdaMpn` refers to the `mpn` field.
+const EDA_FIELD_COLUMN_PREFIX: &str = "Eda";
+
+/// Maps a column name to a placement field name, e.g. `Description` -> `description`, `EdaMpn` -> `mpn`.
+fn column_to_field_name(column: &str) -> String {
+    match column.strip_prefix(EDA_FIELD_COLUMN_PREFIX) {
+        Some(remainder) if remainder.starts_with(char::is_uppercase) => remainder.to_snake_case(),
+        _ => column.to_snake_case(),
+    }
+}
+
+/// e.g. `EdaDescription` -> `description`, `None` for columns without the prefix, e.g. `Notes`.
+fn eda_field_column_to_field_name(column: &str) -> Option<String> {
+    column.strip_prefix(EDA_FIELD_COLUMN_PREFIX)
+        .filter(|remainder| remainder.starts_with(char::is_uppercase))
+        .map(|remainder| remainder.to_snake_case())
+}
+
+/// The fields that are always present for placements from the EDA tool, other placement fields depend on the
+/// columns/properties of the placements file.
+///
+/// These fields are always criteria, even when empty, and other columns are only criteria when they have a value, see
+/// `PartMappingRecord::build_part_mapping`.  The fields are per EDA tool, rather than taken from the loaded placements,
+/// because the substitution and part-mapping files are loaded independently of the placements and a file can contain
+/// rows for multiple EDA tools.
+fn eda_fields_names(eda: &EdaTool) -> Vec<String> {
+    let fields_names: &[&str] = match eda {
         EdaTool::DipTrace => &["name", "value"],
         EdaTool::KiCad => &["package", "val"],
+        EdaTool::Altium => &["comment", "footprint"],
+        EdaTool::EasyEda => &["footprint", "comment"],
+        EdaTool::Eagle => &["value", "package"],
+        // 'mpn' is also a placement field, but it is not included as it would clash with the part's 'Mpn' column,
+        // use the 'EdaMpn' column to match on it.
+        EdaTool::Ipc2581 => &["package", "value"],
+        // likewise for 'manufacturer' and 'mpn', use the 'EdaManufacturer' and 'EdaMpn' columns to match on them.
+        EdaTool::GerberX3 => &["value", "footprint"],
+        EdaTool::Profile(profile) => return profile.field_names(),
+    };
+
+    fields_names.iter().map(|field_name| field_name.to_string()).collect()
+}
+
+/// The value of the 'Eda' column for the EDA tool, e.g. `KiCad`, or the name of the profile.
+fn eda_tool_to_csv_eda_tool_value(eda: &EdaTool) -> String {
+    match eda {
+        EdaTool::DipTrace => "DipTrace".to_string(),
+        EdaTool::KiCad => "KiCad".to_string(),
+        EdaTool::Altium => "Altium".to_string(),
+        EdaTool::EasyEda => "EasyEda".to_string(),
+        EdaTool::Eagle => "Eagle".to_string(),
+        EdaTool::Ipc2581 => "Ipc2581".to_string(),
+        EdaTool::GerberX3 => "GerberX3".to_string(),
+        EdaTool::Profile(profile) => profile.name.clone(),
     }
 }
 
-fn csv_eda_tool_value_to_eda_tool(eda: &String) -> Option<EdaTool> {
-    if eda.to_upper_camel_case().eq("DipTrace") {
-        Some(EdaTool::DipTrace)
-    } else if eda.to_upper_camel_case().eq("KiCad") {
-        Some(EdaTool::KiCad)
-    } else {
-        None
+/// The columns and values of a part-mapping record for a placement, the EDA tool's fields are the criteria and the
+/// part is the optional 'Manufacturer' and 'Mpn' that the placement maps to.
+///
+/// Placement fields with the same name as a reserved column, e.g. the `manufacturer` and `mpn` of a Gerber X3
+/// placement, use the prefixed column, e.g. `EdaMpn`, so that 'Manufacturer' and 'Mpn' are always the part.
+pub fn build_part_mapping_columns(eda: &EdaTool, eda_placement: &EdaPlacement, part: Option<&Part>) -> Vec<(String, String)> {
+    let mut columns: Vec<(String, String)> = vec![("Eda".to_string(), eda_tool_to_csv_eda_tool_value(eda))];
+
+    let mut field_names = eda_fields_names(eda);
+    for column in PART_MAPPING_RESERVED_COLUMNS {
+        let field_name = column.to_snake_case();
+        if !field_names.contains(&field_name) && eda_placement.fields.iter().any(|field| field.name.eq(&field_name)) {
+            field_names.push(field_name);
+        }
+    }
+
+    for field_name in field_names {
+        let column = match field_name.to_upper_camel_case() {
+            column if PART_MAPPING_RESERVED_COLUMNS.contains(&column.as_str()) => format!("{}{}", EDA_FIELD_COLUMN_PREFIX, column),
+            column => column,
+        };
+        let value = eda_placement.fields.iter()
+            .find(|field| field.name.eq(&field_name))
+            .map_or_else(String::new, |field| field.value.clone());
+        columns.push((column, value));
+    }
+
+    columns.push(("Manufacturer".to_string(), part.map(|part| part.manufacturer.clone()).unwrap_or_default()));
+    columns.push(("Mpn".to_string(), part.map(|part| part.mpn.clone()).unwrap_or_default()));
+
+    columns
+}
+
+/// Built-in EDA tools take precedence over profiles with the same name.
+fn csv_eda_tool_value_to_eda_tool(eda: &String, eda_profiles: &[EdaProfile]) -> Option<EdaTool> {
+    match eda.to_upper_camel_case().as_str() {
+        "DipTrace" => Some(EdaTool::DipTrace),
+        "KiCad" => Some(EdaTool::KiCad),
+        "Altium" => Some(EdaTool::Altium),
+        "EasyEda" => Some(EdaTool::EasyEda),
+        "Eagle" => Some(EdaTool::Eagle),
+        "Ipc2581" => Some(EdaTool::Ipc2581),
+        "GerberX3" => Some(EdaTool::GerberX3),
+        _ => eda_profiles.iter()
+            .find(|profile| profile.name.eq(eda))
+            .map(|profile| EdaTool::Profile(Box::new(profile.clone()))),
     }
 }
 
@@ -253,17 +655,22 @@ pub enum CSVSubstitutionRecordError {
 #[derive(Debug, serde::Deserialize)]
 #[serde(rename_all(deserialize = "PascalCase"))]
 pub struct AssemblyRuleRecord {
+    /// See [`RefDesPattern`], e.g. `C1`, `C1-C40` or `/^R1\d$/`.
     ref_des: String,
     manufacturer: String,
     mpn: String,
+    /// Optional, empty for all assembly variants.
+    #[serde(default)]
+    assembly_variant: Option<String>,
 }
 
 impl AssemblyRuleRecord {
     pub fn build_assembly_rule(&self) -> Result<AssemblyRule, anyhow::Error> {
         Ok(AssemblyRule {
-            ref_des: self.ref_des.clone(),
+            ref_des: RefDesPattern::from_str(&self.ref_des)?,
             manufacturer: self.manufacturer.clone(),
             mpn: self.mpn.clone(),
+            assembly_variant: self.assembly_variant.clone().filter(|name| !name.is_empty()),
         })
     }
 }
@@ -1,28 +1,229 @@
 use tracing::Level;
 use anyhow::{Context, Error};
-use std::path::PathBuf;
-use tracing::trace;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use std::fmt::Debug;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use tracing::{info, trace, warn};
+use heck::ToSnakeCase;
+use serde::de::DeserializeOwned;
+use thiserror::Error;
 use eda::diptrace::csv::DiptracePlacementRecord;
-use eda::placement::EdaPlacement;
+use eda::placement::{find_disable_marker, EdaPlacement, EdaPlacementField};
 use eda::EdaTool;
 use eda::kicad::csv::KiCadPlacementRecord;
+use eda::kicad::pcb::parse_footprints;
+use eda::kicad::pos::parse_pos;
+use eda::altium::csv::{detect_preamble_units, strip_preamble, AltiumPlacementRecord};
+use eda::easyeda::csv::{decode_content, detect_delimiter, EasyEdaPlacementRecord};
+use eda::eagle::mnt::parse_mount_file;
+use eda::ipc2581::xml::parse_components;
+use eda::gerber::x3::parse_component_layer;
+use eda::units::{split_header_unit, LengthUnit};
+use pnp::pcb::PcbSide;
 
+/// A placements file, optionally with a forced side, e.g. `bottom:design-bottom.csv`.
+///
+/// The side is used for tools that export the top and bottom placements to separate files without a side column,
+/// or with a side column that should be ignored.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlacementsSource {
+    pub source: String,
+    pub pcb_side: Option<PcbSide>,
+}
+
+impl FromStr for PlacementsSource {
+    type Err = PlacementsSourceError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (pcb_side, source) = match s.split_once(':') {
+            Some((prefix, source)) if prefix.eq_ignore_ascii_case("top") => (Some(PcbSide::Top), source),
+            Some((prefix, source)) if prefix.eq_ignore_ascii_case("bottom") => (Some(PcbSide::Bottom), source),
+            _ => (None, s),
+        };
+
+        if source.is_empty() {
+            return Err(PlacementsSourceError(s.to_string()))
+        }
+
+        Ok(PlacementsSource { source: source.to_string(), pcb_side })
+    }
+}
+
+impl Display for PlacementsSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.pcb_side {
+            Some(PcbSide::Top) => write!(f, "top:{}", self.source),
+            Some(PcbSide::Bottom) => write!(f, "bottom:{}", self.source),
+            None => f.write_str(self.source.as_str()),
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+#[error("Invalid placements source. value: '{0}'")]
+pub struct PlacementsSourceError(String);
+
+#[derive(Debug, Error, PartialEq)]
+#[error("Duplicate reference designators. duplicates: [{}]", format_duplicates(.0))]
+pub struct DuplicateRefDesError(pub Vec<DuplicateRefDes>);
+
+/// A reference designator and the sources that contain it, in the order the sources were given.
+#[derive(Debug, PartialEq)]
+pub struct DuplicateRefDes {
+    pub ref_des: String,
+    pub sources: Vec<String>,
+}
+
+fn format_duplicates(duplicates: &[DuplicateRefDes]) -> String {
+    duplicates.iter()
+        .map(|duplicate| format!("{} ({})", duplicate.ref_des, duplicate.sources.join(", ")))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// The units of the coordinates in a placements source, before they were converted to millimeters.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct SourcePlacementUnits {
+    pub source: String,
+    /// `None` when each value declares its own units, e.g. EasyEDA coordinates have a unit suffix.
+    pub units: Option<LengthUnit>,
+    pub origin: PlacementUnitsOrigin,
+}
+
+/// How the units of a placements source were found.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlacementUnitsOrigin {
+    /// Detected from the headers/preamble of the file.
+    Detected,
+    /// Given by the units override.
+    Override,
+    /// Not known, the default units were used.
+    Default,
+    /// Part of the file format.
+    Declared,
+}
+
+impl Display for PlacementUnitsOrigin {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PlacementUnitsOrigin::Detected => write!(f, "detected"),
+            PlacementUnitsOrigin::Override => write!(f, "override"),
+            PlacementUnitsOrigin::Default => write!(f, "default"),
+            PlacementUnitsOrigin::Declared => write!(f, "declared"),
+        }
+    }
+}
+
+/// Loads and merges the placements from each source, e.g. separate top and bottom placement files, the units of each
+/// source are returned in the same order as the sources.
+///
+/// Fails with a `DuplicateRefDesError` when a reference designator appears more than once across all the sources.
+#[tracing::instrument(level = Level::DEBUG)]
+pub fn load_eda_placements_from_sources(eda_tool: &EdaTool, placements_sources: &[PlacementsSource], units_override: Option<LengthUnit>) -> Result<(Vec<EdaPlacement>, Vec<SourcePlacementUnits>), Error> {
+    let mut placements: Vec<EdaPlacement> = vec![];
+    let mut sources_units: Vec<SourcePlacementUnits> = vec![];
+    let mut ref_des_sources: Vec<(String, Vec<String>)> = vec![];
+    // index into `ref_des_sources`, which keeps the order in which the ref_des were first loaded
+    let mut ref_des_indexes: HashMap<String, usize> = HashMap::new();
+
+    for placements_source in placements_sources {
+        let (mut source_placements, source_units) = load_eda_placements_and_units(eda_tool, &placements_source.source, units_override)?;
+        sources_units.push(source_units);
+        info!("Loaded {} placements from {}", source_placements.len(), placements_source);
+
+        for placement in source_placements.iter_mut() {
+            if let Some(pcb_side) = &placements_source.pcb_side {
+                placement.pcb_side = pcb_side.clone();
+            }
+
+            match ref_des_indexes.get(&placement.ref_des) {
+                Some(&index) => ref_des_sources[index].1.push(placements_source.source.clone()),
+                None => {
+                    ref_des_indexes.insert(placement.ref_des.clone(), ref_des_sources.len());
+                    ref_des_sources.push((placement.ref_des.clone(), vec![placements_source.source.clone()]));
+                },
+            }
+        }
+
+        placements.extend(source_placements);
+    }
+
+    let duplicates: Vec<DuplicateRefDes> = ref_des_sources.into_iter()
+        .filter(|(_ref_des, sources)| sources.len() > 1)
+        .map(|(ref_des, sources)| DuplicateRefDes { ref_des, sources })
+        .collect();
+
+    if !duplicates.is_empty() {
+        return Err(DuplicateRefDesError(duplicates).into())
+    }
+
+    Ok((placements, sources_units))
+}
+
+/// How the coordinates of the loaded placements relate to units.
+enum PlacementUnits {
+    /// The coordinates are as they appear in the file, the units were detected from the headers/preamble, if possible.
+    Unconverted(Option<LengthUnit>),
+    /// The units are part of the file format, the coordinates have already been converted to millimeters.
+    /// The units are `None` when they can differ per value, or are not known.
+    Converted(Option<LengthUnit>),
+}
+
+/// Coordinates of the returned placements are always in millimeters.
+///
+/// Placements that the EDA tool marks as not fitted, e.g. with a 'DNP' column or a 'No Pop' comment, are disabled.
+///
+/// The units override is used for files where the units are unknown or detected from headers; it is ignored for
+/// formats that declare the units themselves (e.g. KiCad board files, IPC-2581, Gerber X3, EDA profiles).
 #[tracing::instrument(level = Level::DEBUG)]
-pub fn load_eda_placements(eda_tool: EdaTool, placements_source: &String) -> Result<Vec<EdaPlacement>, Error> {
+pub fn load_eda_placements(eda_tool: &EdaTool, placements_source: &String, units_override: Option<LengthUnit>) -> Result<Vec<EdaPlacement>, Error> {
+    load_eda_placements_and_units(eda_tool, placements_source, units_override)
+        .map(|(placements, _units)| placements)
+}
+
+/// See `load_eda_placements`, also returns the units of the placements source.
+pub fn load_eda_placements_and_units(eda_tool: &EdaTool, placements_source: &String, units_override: Option<LengthUnit>) -> Result<(Vec<EdaPlacement>, SourcePlacementUnits), Error> {
     let placements_path_buf = PathBuf::from(placements_source);
     let placements_path = placements_path_buf.as_path();
-    let mut csv_reader = csv::ReaderBuilder::new().from_path(placements_path)
-        .with_context(|| format!("Error reading placements. file: {}", placements_path.to_str().unwrap()))?;
-    
 
     let mut placements: Vec<EdaPlacement> = vec![];
 
-    match eda_tool {
+    let units = match eda_tool {
         EdaTool::DipTrace => {
-            for result in csv_reader.deserialize() {
-                let record: DiptracePlacementRecord = result
-                    .with_context(|| "Deserializing placement record".to_string())?;
+            let mut csv_reader = build_csv_reader(placements_path)?;
+            let detected_units = strip_header_units(&mut csv_reader)?;
+            placements.extend(load_csv_placements(&mut csv_reader, DiptracePlacementRecord::STANDARD_COLUMNS, DiptracePlacementRecord::build_eda_placement)?);
+            PlacementUnits::Unconverted(detected_units)
+        },
+        EdaTool::KiCad if has_extension(placements_path, "kicad_pcb") => {
+            let content = read_placements_file(placements_path)?;
+
+            let footprints = parse_footprints(&content)
+                .with_context(|| format!("Parsing KiCad board. file: {}", placements_path.to_str().unwrap()))?;
+
+            for footprint in footprints {
+                trace!("{:?}", footprint);
+
+                let placement = footprint.build_eda_placement()
+                    .with_context(|| format!("Building placement from footprint. footprint: {:?}", footprint))?;
 
+                placements.push(placement);
+            }
+            PlacementUnits::Converted(Some(LengthUnit::Millimeters))
+        },
+        EdaTool::KiCad if has_extension(placements_path, "pos") => {
+            let content = read_placements_file(placements_path)?;
+
+            let records = parse_pos(&content)
+                .with_context(|| format!("Parsing KiCad position file. file: {}", placements_path.to_str().unwrap()))?;
+
+            let declared_units = records.first().map(|record| record.units());
+
+            for record in records {
                 trace!("{:?}", record);
 
                 let placement = record.build_eda_placement()
@@ -30,20 +231,485 @@ pub fn load_eda_placements(eda_tool: EdaTool, placements_source: &String) -> Res
 
                 placements.push(placement);
             }
+            PlacementUnits::Converted(declared_units)
         },
         EdaTool::KiCad => {
+            let mut csv_reader = build_csv_reader(placements_path)?;
+            let detected_units = strip_header_units(&mut csv_reader)?;
+            placements.extend(load_csv_placements(&mut csv_reader, KiCadPlacementRecord::STANDARD_COLUMNS, KiCadPlacementRecord::build_eda_placement)?);
+            PlacementUnits::Unconverted(detected_units)
+        },
+        EdaTool::Altium => {
+            let content = read_placements_file(placements_path)?;
+
+            let mut csv_reader = csv::ReaderBuilder::new()
+                .trim(csv::Trim::All)
+                .from_reader(strip_preamble(&content).as_bytes());
+            let detected_units = strip_header_units(&mut csv_reader)?;
+            placements.extend(load_csv_placements(&mut csv_reader, AltiumPlacementRecord::STANDARD_COLUMNS, AltiumPlacementRecord::build_eda_placement)?);
+            PlacementUnits::Unconverted(detect_preamble_units(&content).or(detected_units))
+        },
+        EdaTool::EasyEda => {
+            let bytes = std::fs::read(placements_path)
+                .with_context(|| format!("Error reading placements. file: {}", placements_path.to_str().unwrap()))?;
+            let content = decode_content(&bytes)
+                .with_context(|| format!("Decoding placements. file: {}", placements_path.to_str().unwrap()))?;
+
+            let mut csv_reader = csv::ReaderBuilder::new()
+                .delimiter(detect_delimiter(&content))
+                .from_reader(content.as_bytes());
+            placements.extend(load_csv_placements(&mut csv_reader, EasyEdaPlacementRecord::STANDARD_COLUMNS, EasyEdaPlacementRecord::build_eda_placement)?);
+            // each coordinate has a unit suffix
+            PlacementUnits::Converted(None)
+        },
+        EdaTool::Eagle => {
+            // the source can be either of the pair of files, the top and bottom side files share the same base name.
+            let sides = [
+                (placements_path.with_extension("mnt"), PcbSide::Top),
+                (placements_path.with_extension("mnb"), PcbSide::Bottom),
+            ];
+
+            let existing_sides: Vec<_> = sides.into_iter().filter(|(path, _side)| path.exists()).collect();
+            if existing_sides.is_empty() {
+                anyhow::bail!("Error reading placements, no '.mnt' or '.mnb' file found. source: {}", placements_path.to_str().unwrap());
+            }
+
+            for (side_path, side) in existing_sides {
+                let content = read_placements_file(&side_path)?;
+
+                let records = parse_mount_file(&content, side)
+                    .with_context(|| format!("Parsing Eagle mount file. file: {}", side_path.to_str().unwrap()))?;
+
+                for record in records {
+                    trace!("{:?}", record);
+
+                    let placement = record.build_eda_placement()
+                        .with_context(|| format!("Building placement from record. record: {:?}", record))?;
+
+                    placements.push(placement);
+                }
+            }
+            PlacementUnits::Unconverted(None)
+        },
+        EdaTool::Ipc2581 => {
+            let content = read_placements_file(placements_path)?;
+
+            let components = parse_components(&content)
+                .with_context(|| format!("Parsing IPC-2581 file. file: {}", placements_path.to_str().unwrap()))?;
+
+            let declared_units = components.first().map(|component| component.units());
+
+            for component in components {
+                trace!("{:?}", component);
+
+                let placement = component.build_eda_placement()
+                    .with_context(|| format!("Building placement from component. component: {:?}", component))?;
+
+                placements.push(placement);
+            }
+            PlacementUnits::Converted(declared_units)
+        },
+        EdaTool::GerberX3 => {
+            let content = read_placements_file(placements_path)?;
+
+            let components = parse_component_layer(&content)
+                .with_context(|| format!("Parsing Gerber X3 component layer. file: {}", placements_path.to_str().unwrap()))?;
+
+            for component in components {
+                trace!("{:?}", component);
+
+                let placement = component.build_eda_placement()
+                    .with_context(|| format!("Building placement from component. component: {:?}", component))?;
+
+                placements.push(placement);
+            }
+            PlacementUnits::Converted(None)
+        },
+        EdaTool::Profile(profile) => {
+            let mut csv_reader = csv::ReaderBuilder::new()
+                // the delimiter is ASCII, see `EdaProfile::delimiter`
+                .delimiter(profile.delimiter.map_or(b',', |delimiter| delimiter as u8))
+                .trim(csv::Trim::All)
+                .from_path(placements_path)
+                .with_context(|| format!("Error reading placements. file: {}", placements_path.to_str().unwrap()))?;
             for result in csv_reader.deserialize() {
-                let record: KiCadPlacementRecord = result
+                let record: HashMap<String, String> = result
                     .with_context(|| "Deserializing placement record".to_string())?;
 
                 trace!("{:?}", record);
 
-                let placement = record.build_eda_placement()
-                    .with_context(|| format!("Building placement from record. record: {:?}", record))?;
-                
+                let placement = profile.build_eda_placement(&record)
+                    .with_context(|| format!("Building placement from record using profile. profile: {}, record: {:?}", profile.name, record))?;
+
                 placements.push(placement);
             }
+            PlacementUnits::Converted(Some(profile.units))
+        },
+    };
+
+    let source_units = match (units, units_override) {
+        (PlacementUnits::Unconverted(detected_units), units_override) => {
+            let (units, origin) = match (units_override, detected_units) {
+                (Some(units), _) => (units, PlacementUnitsOrigin::Override),
+                (None, Some(units)) => (units, PlacementUnitsOrigin::Detected),
+                (None, None) => (LengthUnit::default(), PlacementUnitsOrigin::Default),
+            };
+            info!("Placement units: {} ({})", units, origin);
+
+            for placement in placements.iter_mut() {
+                placement.x = units.to_millimeters(placement.x);
+                placement.y = units.to_millimeters(placement.y);
+            }
+
+            SourcePlacementUnits { source: placements_source.clone(), units: Some(units), origin }
+        },
+        (PlacementUnits::Converted(declared_units), units_override) => {
+            match declared_units {
+                Some(units) => info!("Placement units: {} (declared)", units),
+                None => info!("Placement units: declared per value"),
+            }
+            if let Some(units_override) = units_override {
+                warn!("Ignoring placement units override, the placements file declares the units. override: {}", units_override);
+            }
+
+            SourcePlacementUnits { source: placements_source.clone(), units: declared_units, origin: PlacementUnitsOrigin::Declared }
+        },
+    };
+
+    for placement in placements.iter_mut().filter(|placement| placement.place) {
+        if let Some(reason) = find_disable_marker(&placement.fields) {
+            trace!("Disabling placement. ref_des: {}, reason: {}", placement.ref_des, reason);
+            placement.disable(reason);
         }
     }
+
+    Ok((placements, source_units))
+}
+
+/// Non-standard columns are carried as additional placement fields, e.g. `MPN` or `Description`; the field name is
+/// the snake case of the column name, e.g. `Manufacturer Part Number` becomes `manufacturer_part_number`.
+fn load_csv_placements<R, T, E>(
+    csv_reader: &mut csv::Reader<R>,
+    standard_columns: &[&str],
+    build_eda_placement: impl Fn(&T) -> Result<EdaPlacement, E>,
+) -> Result<Vec<EdaPlacement>, Error>
+where
+    R: Read,
+    T: DeserializeOwned + Debug,
+    E: std::error::Error + Send + Sync + 'static,
+{
+    let headers = csv_reader.headers()
+        .with_context(|| "Reading placement headers".to_string())?
+        .clone();
+
+    let mut placements = vec![];
+
+    for result in csv_reader.records() {
+        let string_record = result
+            .with_context(|| "Reading placement record".to_string())?;
+
+        let record: T = string_record.deserialize(Some(&headers))
+            .with_context(|| "Deserializing placement record".to_string())?;
+
+        trace!("{:?}", record);
+
+        let mut placement = build_eda_placement(&record)
+            .with_context(|| format!("Building placement from record. record: {:?}", record))?;
+
+        for (column, value) in headers.iter().zip(string_record.iter()) {
+            let name = column.to_snake_case();
+            if standard_columns.contains(&column) || placement.fields.iter().any(|field| field.name.eq(&name)) {
+                continue
+            }
+            placement.fields.push(EdaPlacementField::new(name, value.to_string()));
+        }
+
+        placements.push(placement);
+    }
+
     Ok(placements)
-}
\ No newline at end of file
+}
+
+fn build_csv_reader(placements_path: &Path) -> Result<csv::Reader<std::fs::File>, Error> {
+    csv::ReaderBuilder::new().from_path(placements_path)
+        .with_context(|| format!("Error reading placements. file: {}", placements_path.to_str().unwrap()))
+}
+
+/// Removes unit suffixes from the headers, e.g. `Center X (mil)` becomes `Center X`, returns the first unit found.
+fn strip_header_units<R: Read>(csv_reader: &mut csv::Reader<R>) -> Result<Option<LengthUnit>, Error> {
+    let headers = csv_reader.headers()
+        .with_context(|| "Reading placement headers".to_string())?
+        .clone();
+
+    let mut detected_units = None;
+    let headers: csv::StringRecord = headers.iter().map(|header| {
+        let (name, units) = split_header_unit(header);
+        detected_units = detected_units.or(units);
+        name
+    }).collect();
+
+    csv_reader.set_headers(headers);
+
+    Ok(detected_units)
+}
+
+fn read_placements_file(placements_path: &Path) -> Result<String, Error> {
+    std::fs::read_to_string(placements_path)
+        .with_context(|| format!("Error reading placements. file: {}", placements_path.to_str().unwrap()))
+}
+
+fn has_extension(path: &Path, extension: &str) -> bool {
+    path.extension().is_some_and(|candidate| candidate.eq_ignore_ascii_case(extension))
+}
+
+#[cfg(test)]
+mod eda_placements_tests {
+    use assert_fs::TempDir;
+    use indoc::indoc;
+    use rust_decimal_macros::dec;
+    use eda::EdaTool;
+    use eda::placement::{EdaPlacementDisableReason, EdaPlacementField};
+    use eda::units::LengthUnit;
+    use std::str::FromStr;
+    use rstest::rstest;
+    use pnp::pcb::PcbSide;
+    use crate::eda_placements::{load_eda_placements, load_eda_placements_and_units, load_eda_placements_from_sources, DuplicateRefDes, DuplicateRefDesError, PlacementUnitsOrigin, PlacementsSource, SourcePlacementUnits};
+
+    #[test]
+    fn diptrace_units_detected_from_headers() -> anyhow::Result<()> {
+        // given
+        let temp_dir = TempDir::new()?;
+        let mut test_placements_path = temp_dir.path().to_path_buf();
+        test_placements_path.push("placements.csv");
+        let test_placements_source = test_placements_path.to_str().unwrap().to_string();
+
+        std::fs::write(test_placements_path, indoc! {r#"
+            "RefDes","Name","Value","Side","Center X (mil)","Center Y (mil)","Rotation"
+            "R1","RES_0402","10K","Top","500","4000","0"
+        "#})?;
+
+        // when
+        let result = load_eda_placements(&EdaTool::DipTrace, &test_placements_source, None)?;
+
+        // then
+        assert_eq!((result[0].x, result[0].y), (dec!(12.7), dec!(101.6)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn other_columns_are_fields() -> anyhow::Result<()> {
+        // given
+        let temp_dir = TempDir::new()?;
+        let mut test_placements_path = temp_dir.path().to_path_buf();
+        test_placements_path.push("placements.csv");
+        let test_placements_source = test_placements_path.to_str().unwrap().to_string();
+
+        std::fs::write(test_placements_path, indoc! {r#"
+            "RefDes","Name","Value","MPN","Manufacturer Part Number","Side","X","Y","Rotation"
+            "R1","RES_0402","10K","RC0402FR-0710KL","","Top","5","40","0"
+        "#})?;
+
+        // and
+        let expected_fields = vec![
+            EdaPlacementField::new("name".to_string(), "RES_0402".to_string()),
+            EdaPlacementField::new("value".to_string(), "10K".to_string()),
+            EdaPlacementField::new("mpn".to_string(), "RC0402FR-0710KL".to_string()),
+            EdaPlacementField::new("manufacturer_part_number".to_string(), "".to_string()),
+        ];
+
+        // when
+        let result = load_eda_placements(&EdaTool::DipTrace, &test_placements_source, None)?;
+
+        // then
+        assert_eq!(result[0].fields, expected_fields);
+
+        Ok(())
+    }
+
+    #[test]
+    fn disable_markers() -> anyhow::Result<()> {
+        // given
+        let temp_dir = TempDir::new()?;
+        let mut test_placements_path = temp_dir.path().to_path_buf();
+        test_placements_path.push("placements.csv");
+        let test_placements_source = test_placements_path.to_str().unwrap().to_string();
+
+        std::fs::write(test_placements_path, indoc! {r#"
+            "Designator","Comment","Layer","Footprint","Center-X(mm)","Center-Y(mm)","Rotation","Description"
+            "R1","10K","TopLayer","0402","5","40","0","Resistor"
+            "R2","No Pop","TopLayer","0402","10","40","0","Resistor"
+        "#})?;
+
+        // and
+        let expected_disable_reasons = vec![
+            (true, None),
+            (false, Some(EdaPlacementDisableReason::Marker { field_name: "comment".to_string(), value: "No Pop".to_string() })),
+        ];
+
+        // when
+        let result = load_eda_placements(&EdaTool::Altium, &test_placements_source, None)?;
+
+        // then
+        let disable_reasons: Vec<_> = result.into_iter().map(|placement| (placement.place, placement.disable_reason)).collect();
+        assert_eq!(disable_reasons, expected_disable_reasons);
+
+        Ok(())
+    }
+
+    #[test]
+    fn merge_sources_with_forced_sides() -> anyhow::Result<()> {
+        // given
+        let temp_dir = TempDir::new()?;
+        let mut test_top_placements_path = temp_dir.path().to_path_buf();
+        test_top_placements_path.push("top.csv");
+        let mut test_bottom_placements_path = temp_dir.path().to_path_buf();
+        test_bottom_placements_path.push("bottom.csv");
+
+        std::fs::write(&test_top_placements_path, indoc! {r#"
+            "RefDes","Name","Value","Side","X","Y","Rotation"
+            "R1","RES_0402","10K","Top","5","40","0"
+        "#})?;
+        std::fs::write(&test_bottom_placements_path, indoc! {r#"
+            "RefDes","Name","Value","Side","X","Y","Rotation"
+            "C1","CAP_0402","100nF","Top","10","40","0"
+        "#})?;
+
+        // and
+        let sources = vec![
+            PlacementsSource::from_str(test_top_placements_path.to_str().unwrap())?,
+            PlacementsSource::from_str(&format!("bottom:{}", test_bottom_placements_path.to_str().unwrap()))?,
+        ];
+
+        // when
+        let (result, _units) = load_eda_placements_from_sources(&EdaTool::DipTrace, &sources, None)?;
+
+        // then
+        let sides: Vec<_> = result.iter().map(|placement| (placement.ref_des.as_str(), placement.pcb_side.clone())).collect();
+        assert_eq!(sides, vec![("R1", PcbSide::Top), ("C1", PcbSide::Bottom)]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn duplicate_ref_des_across_sources() -> anyhow::Result<()> {
+        // given
+        let temp_dir = TempDir::new()?;
+        let mut test_top_placements_path = temp_dir.path().to_path_buf();
+        test_top_placements_path.push("top.csv");
+        let mut test_bottom_placements_path = temp_dir.path().to_path_buf();
+        test_bottom_placements_path.push("bottom.csv");
+        let test_top_placements_source = test_top_placements_path.to_str().unwrap().to_string();
+        let test_bottom_placements_source = test_bottom_placements_path.to_str().unwrap().to_string();
+
+        std::fs::write(&test_top_placements_path, indoc! {r#"
+            "RefDes","Name","Value","Side","X","Y","Rotation"
+            "R1","RES_0402","10K","Top","5","40","0"
+            "R2","RES_0402","10K","Top","10","40","0"
+        "#})?;
+        std::fs::write(&test_bottom_placements_path, indoc! {r#"
+            "RefDes","Name","Value","Side","X","Y","Rotation"
+            "R2","RES_0402","10K","Bottom","10","40","0"
+        "#})?;
+
+        // and
+        let sources = vec![
+            PlacementsSource { source: test_top_placements_source.clone(), pcb_side: None },
+            PlacementsSource { source: test_bottom_placements_source.clone(), pcb_side: None },
+        ];
+
+        // and
+        let expected_error = DuplicateRefDesError(vec![
+            DuplicateRefDes { ref_des: "R2".to_string(), sources: vec![test_top_placements_source, test_bottom_placements_source] },
+        ]);
+
+        // when
+        let result = load_eda_placements_from_sources(&EdaTool::DipTrace, &sources, None);
+
+        // then
+        assert_eq!(result.unwrap_err().downcast_ref::<DuplicateRefDesError>(), Some(&expected_error));
+
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("placements.csv", PlacementsSource { source: "placements.csv".to_string(), pcb_side: None })]
+    #[case("top:placements.csv", PlacementsSource { source: "placements.csv".to_string(), pcb_side: Some(PcbSide::Top) })]
+    #[case("Bottom:placements.csv", PlacementsSource { source: "placements.csv".to_string(), pcb_side: Some(PcbSide::Bottom) })]
+    #[case("C:\\placements.csv", PlacementsSource { source: "C:\\placements.csv".to_string(), pcb_side: None })]
+    fn placements_source_from_str(#[case] value: &str, #[case] expected_result: PlacementsSource) {
+        assert_eq!(PlacementsSource::from_str(value).unwrap(), expected_result);
+    }
+
+    #[test]
+    fn units_override() -> anyhow::Result<()> {
+        // given
+        let temp_dir = TempDir::new()?;
+        let mut test_placements_path = temp_dir.path().to_path_buf();
+        test_placements_path.push("placements.csv");
+        let test_placements_source = test_placements_path.to_str().unwrap().to_string();
+
+        std::fs::write(test_placements_path, indoc! {r#"
+            "ref","Package","Val","Side","X","Y","Rotation"
+            "R1","R_0402","10K","top","0.5","-4","0"
+        "#})?;
+
+        // when
+        let result = load_eda_placements(&EdaTool::KiCad, &test_placements_source, Some(LengthUnit::Inches))?;
+
+        // then
+        assert_eq!((result[0].x, result[0].y), (dec!(12.7), dec!(-101.6)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn easyeda_std_utf16_export() -> anyhow::Result<()> {
+        // given a tab separated UTF-16LE file, as exported by EasyEDA Std
+        let test_placements_source = format!("{}/../../assets/eda/easyeda/example-1-CPL-std.csv", env!("CARGO_MANIFEST_DIR"));
+
+        // when
+        let result = load_eda_placements(&EdaTool::EasyEda, &test_placements_source, None)?;
+
+        // then
+        let placements: Vec<_> = result.iter()
+            .map(|placement| (placement.ref_des.as_str(), placement.pcb_side.clone(), placement.x, placement.y))
+            .collect();
+        assert_eq!(placements, vec![
+            ("R1", PcbSide::Top, dec!(12.7), dec!(101.6)),
+            ("C1", PcbSide::Bottom, dec!(20.5), dec!(30.25)),
+        ]);
+
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(r#""RefDes","Name","Value","Side","Center X (mil)","Center Y (mil)","Rotation""#, None, Some(LengthUnit::Mils), PlacementUnitsOrigin::Detected)]
+    #[case(r#""RefDes","Name","Value","Side","Center X (mil)","Center Y (mil)","Rotation""#, Some(LengthUnit::Inches), Some(LengthUnit::Inches), PlacementUnitsOrigin::Override)]
+    #[case(r#""RefDes","Name","Value","Side","X","Y","Rotation""#, None, Some(LengthUnit::Millimeters), PlacementUnitsOrigin::Default)]
+    fn units_origin(
+        #[case] headers: &str,
+        #[case] units_override: Option<LengthUnit>,
+        #[case] expected_units: Option<LengthUnit>,
+        #[case] expected_origin: PlacementUnitsOrigin,
+    ) -> anyhow::Result<()> {
+        // given
+        let temp_dir = TempDir::new()?;
+        let mut test_placements_path = temp_dir.path().to_path_buf();
+        test_placements_path.push("placements.csv");
+        let test_placements_source = test_placements_path.to_str().unwrap().to_string();
+
+        std::fs::write(test_placements_path, format!("{}\n{}\n", headers, r#""R1","RES_0402","10K","Top","500","4000","0""#))?;
+
+        // and
+        let expected_units = SourcePlacementUnits { source: test_placements_source.clone(), units: expected_units, origin: expected_origin };
+
+        // when
+        let (_placements, units) = load_eda_placements_and_units(&EdaTool::DipTrace, &test_placements_source, units_override)?;
+
+        // then
+        assert_eq!(units, expected_units);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,56 @@
+use tracing::Level;
+use std::fs::File;
+use std::path::PathBuf;
+use anyhow::{Context, Error};
+use tracing::trace;
+use eda::profile::EdaProfile;
+
+#[tracing::instrument(level = Level::DEBUG)]
+pub fn load_eda_profile(eda_profile_source: &String) -> Result<EdaProfile, Error> {
+    let eda_profile_path_buf = PathBuf::from(eda_profile_source);
+    let eda_profile_path = eda_profile_path_buf.as_path();
+    let eda_profile_file = File::open(eda_profile_path)
+        .with_context(|| format!("Error reading EDA profile. file: {}", eda_profile_path.to_str().unwrap()))?;
+
+    let eda_profile: EdaProfile = serde_json::from_reader(eda_profile_file)
+        .with_context(|| format!("Deserializing EDA profile. file: {}", eda_profile_path.to_str().unwrap()))?;
+
+    trace!("{:?}", eda_profile);
+
+    Ok(eda_profile)
+}
+
+#[cfg(test)]
+mod eda_profiles_tests {
+    use assert_fs::TempDir;
+    use indoc::indoc;
+    use crate::eda_profiles::load_eda_profile;
+
+    #[test]
+    fn non_ascii_delimiter() -> anyhow::Result<()> {
+        // given
+        let temp_dir = TempDir::new()?;
+        let mut test_profile_path = temp_dir.path().to_path_buf();
+        test_profile_path.push("profile.json");
+        let test_profile_source = test_profile_path.to_str().unwrap().to_string();
+
+        std::fs::write(test_profile_path, indoc! {r#"
+            {
+                "name": "FabExport",
+                "delimiter": "§",
+                "columns": { "ref_des": "Ref", "x": "PosX", "y": "PosY", "rotation": "Rot", "side": "Side" },
+                "sides": { "top": ["T"], "bottom": ["B"] },
+                "fields": []
+            }
+        "#})?;
+
+        // when
+        let result = load_eda_profile(&test_profile_source);
+
+        // then
+        let error = result.unwrap_err();
+        assert!(format!("{:#}", error.root_cause()).starts_with("Invalid delimiter, only single-byte ASCII delimiters are supported. delimiter: '§'"));
+
+        Ok(())
+    }
+}
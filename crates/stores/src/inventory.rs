@@ -0,0 +1,67 @@
+use tracing::Level;
+use anyhow::{Context, Error};
+use std::path::PathBuf;
+use tracing::trace;
+use pnp::inventory::InventoryItem;
+use crate::csv::InventoryItemRecord;
+
+#[tracing::instrument(level = Level::DEBUG)]
+pub fn load_items(inventory_source: &String) -> Result<Vec<InventoryItem>, Error> {
+    let inventory_path_buf = PathBuf::from(inventory_source);
+    let inventory_path = inventory_path_buf.as_path();
+    let mut csv_reader = csv::ReaderBuilder::new()
+        .from_path(inventory_path)
+        .with_context(|| format!("Error reading inventory. file: {}", inventory_path.to_str().unwrap()))?;
+
+    let mut items: Vec<InventoryItem> = vec![];
+
+    for result in csv_reader.deserialize() {
+        let record: InventoryItemRecord = result
+            .with_context(|| "Deserializing inventory record".to_string())?;
+
+        trace!("{:?}", record);
+
+        let inventory_item = record.build_inventory_item()
+            .with_context(|| format!("Building inventory item from record. record: {:?}", record))?;
+
+        items.push(inventory_item);
+    }
+    Ok(items)
+}
+
+#[cfg(test)]
+pub mod csv_loading_tests {
+    use assert_fs::TempDir;
+    use indoc::indoc;
+    use pnp::inventory::InventoryItem;
+    use crate::inventory::load_items;
+
+    #[test]
+    pub fn load_inventory() -> anyhow::Result<()> {
+        // given
+        let temp_dir = TempDir::new()?;
+        let mut test_inventory_path = temp_dir.path().to_path_buf();
+        test_inventory_path.push("inventory.csv");
+        let test_inventory_source = test_inventory_path.to_str().unwrap().to_string();
+
+        std::fs::write(test_inventory_path, indoc! {r#"
+            "Manufacturer","Mpn","Quantity","Location"
+            "RES_MFR1","RES1","5000","SHELF_1"
+            "RES_MFR1","RES1","250","DRAWER_2"
+        "#})?;
+
+        // and
+        let expected_result = vec![
+            InventoryItem::new("RES_MFR1".to_string(), "RES1".to_string(), 5000, "SHELF_1".to_string()),
+            InventoryItem::new("RES_MFR1".to_string(), "RES1".to_string(), 250, "DRAWER_2".to_string()),
+        ];
+
+        // when
+        let result = load_items(&test_inventory_source)?;
+
+        // then
+        assert_eq!(result, expected_result);
+
+        Ok(())
+    }
+}
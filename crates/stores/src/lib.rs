@@ -9,11 +9,13 @@
 /// * Etc.
 pub mod parts;
 pub mod eda_placements;
+pub mod eda_profiles;
 pub mod placements;
 pub mod part_mappings;
 
 pub mod substitutions;
 pub mod load_out;
+pub mod inventory;
 pub mod assembly_rules;
 pub mod csv;
 
@@ -2,12 +2,16 @@ use tracing::Level;
 use anyhow::{Context, Error};
 use std::path::PathBuf;
 use tracing::trace;
-use crate::csv::PartMappingRecord;
+use csv::QuoteStyle;
+use crate::csv::{build_part_mapping_columns, PartMappingRecord};
 use pnp::part::Part;
+use eda::EdaTool;
+use eda::placement::EdaPlacement;
+use eda::profile::EdaProfile;
 use part_mapper::part_mapping::PartMapping;
 
 #[tracing::instrument(level = Level::DEBUG)]
-pub fn load_part_mappings<'part>(parts: &'part Vec<Part>, part_mappings_source: &String) -> Result<Vec<PartMapping<'part>>, Error> {
+pub fn load_part_mappings<'part>(parts: &'part [Part], part_mappings_source: &String, eda_profiles: &[EdaProfile]) -> Result<Vec<PartMapping<'part>>, Error> {
     let part_mappings_path_buf = PathBuf::from(part_mappings_source);
     let part_mappings_path = part_mappings_path_buf.as_path();
     let mut csv_reader = csv::ReaderBuilder::new()
@@ -16,29 +20,85 @@ pub fn load_part_mappings<'part>(parts: &'part Vec<Part>, part_mappings_source:
 
     let mut part_mappings: Vec<PartMapping> = vec![];
 
-    for result in csv_reader.deserialize() {
-        let record: PartMappingRecord = result
+    let headers = csv_reader.headers()
+        .with_context(|| "Reading part mapping headers".to_string())?
+        .clone();
+
+    for result in csv_reader.records() {
+        let record = result
+            .with_context(|| "Reading part mapping record".to_string())?;
+        // the line the record starts on, a quoted value can span several lines.
+        let line = record.position().map(|position| position.line());
+        let record: PartMappingRecord = record.deserialize(Some(&headers))
             .with_context(|| "Deserializing part mapping record".to_string())?;
 
         trace!("{:?}", record);
 
-        let part_mapping = record.build_part_mapping(parts)
+        let part_mapping = record.build_part_mapping(parts, eda_profiles)
             .with_context(|| format!("Building part mapping from record. record: {:?}", record))?;
 
-        part_mappings.push(part_mapping);
+        part_mappings.push(PartMapping { line, ..part_mapping });
     }
     Ok(part_mappings)
 }
 
+/// Stores a part-mappings file that can be edited and then appended to a part-mappings file, one record for each
+/// distinct placement, using the placement's EDA fields and the optional part.
+pub fn store_part_mappings_fragment(output_path: &PathBuf, eda_tool: &EdaTool, items: &[(&EdaPlacement, Option<&Part>)]) -> Result<(), Error> {
+    let mut writer = csv::WriterBuilder::new()
+        .quote_style(QuoteStyle::Always)
+        .from_path(output_path)
+        .with_context(|| format!("Error writing part mappings. file: {}", output_path.to_str().unwrap()))?;
+
+    let mut records: Vec<Vec<(String, String)>> = vec![];
+    for (eda_placement, part) in items.iter() {
+        let record = build_part_mapping_columns(eda_tool, eda_placement, *part);
+        if !records.contains(&record) {
+            records.push(record);
+        }
+    }
+
+    // records can have different columns, e.g. only some placements have an `mpn` field, the part's columns are last.
+    let mut headers: Vec<String> = build_part_mapping_columns(eda_tool, &EdaPlacement::default(), None).into_iter()
+        .map(|(column, _value)| column)
+        .collect();
+    for record in records.iter() {
+        for (column, _value) in record.iter() {
+            if !headers.contains(column) {
+                // before the 'Manufacturer' and 'Mpn' columns
+                headers.insert(headers.len() - 2, column.clone());
+            }
+        }
+    }
+    writer.write_record(&headers)?;
+
+    for record in records {
+        writer.write_record(headers.iter().map(|header| {
+            record.iter()
+                .find(|(column, _value)| column.eq(header))
+                .map_or("", |(_column, value)| value.as_str())
+        }))?;
+    }
+
+    writer.flush()?;
+
+    Ok(())
+}
+
 #[cfg(test)]
 pub mod csv_loading_tests {
     use assert_fs::TempDir;
     use csv::QuoteStyle;
+    use indoc::indoc;
     use regex::Regex;
-    use criteria::{ExactMatchCriterion, GenericCriteria, RegexMatchCriterion};
+    use std::str::FromStr;
+    use criteria::{AllOfCriterion, AnyOfCriterion, ExactMatchCriterion, GenericCriteria, NotCriterion, QuantityMatchCriterion, QuantityRangeCriterion, RegexMatchCriterion};
+    use util::quantity::{Quantity, QuantityRange};
     use part_mapper::part_mapping::PartMapping;
     use pnp::part::Part;
-    use crate::part_mappings::load_part_mappings;
+    use eda::EdaTool;
+    use eda::placement::{EdaPlacement, EdaPlacementField};
+    use crate::part_mappings::{load_part_mappings, store_part_mappings_fragment};
     use crate::part_mappings::test::TestPartMappingRecord;
 
     /// Regression test for workaround to the serde flatten issue.
@@ -73,7 +133,7 @@ pub mod csv_loading_tests {
         println!("{csv_content:}");
 
         // when
-        let result = load_part_mappings(&parts, &test_part_mappings_source);
+        let result = load_part_mappings(&parts, &test_part_mappings_source, &[]);
 
         // then
         assert!(result.is_ok());
@@ -123,26 +183,332 @@ pub mod csv_loading_tests {
                     Box::new(ExactMatchCriterion { field_name: "name".to_string(), field_pattern: "12345".to_string() }),
                     Box::new(ExactMatchCriterion { field_name: "value".to_string(), field_pattern: "54321".to_string() }),
                 ] })
-            ] },
+            ], priority: None, line: Some(2) },
             PartMapping { part: parts.get(0).unwrap(), criteria: vec![
                 Box::new(GenericCriteria { criteria: vec![
                     Box::new(ExactMatchCriterion { field_name: "name".to_string(), field_pattern: "12345".to_string() }),
                     Box::new(RegexMatchCriterion { field_name: "value".to_string(), field_pattern: Regex::new(".*").unwrap() }),
                 ] })
-            ] },
+            ], priority: None, line: Some(3) },
         ];
         
         let csv_content = std::fs::read_to_string(test_part_mappings_source.clone())?;
         println!("{csv_content:}");
 
         // when
-        let result = load_part_mappings(&parts, &test_part_mappings_source)?;
+        let result = load_part_mappings(&parts, &test_part_mappings_source, &[])?;
+
+        // then
+        assert_eq!(result, expected_result);
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn gerber_x3_placement_manufacturer_and_mpn_are_optional_criteria() -> anyhow::Result<()>{
+        // given
+        let parts: Vec<Part> = vec![
+            Part{ manufacturer: "Yageo".to_string(), mpn: "RC0402FR-0710KL".to_string() },
+            Part{ manufacturer: "RES_MFR1".to_string(), mpn: "RES1".to_string() },
+        ];
+
+        // and
+        let temp_dir = TempDir::new()?;
+        let mut test_part_mappings_path = temp_dir.path().to_path_buf();
+        test_part_mappings_path.push("part-mappings.csv");
+        let test_part_mappings_source = test_part_mappings_path.to_str().unwrap().to_string();
+
+        // and a mapping on the placement's manufacturer and mpn, and a mapping to an alternate part on the value and footprint
+        std::fs::write(test_part_mappings_path, indoc! {r#"
+            "Eda","Value","Footprint","EdaManufacturer","EdaMpn","Manufacturer","Mpn"
+            "GerberX3","10K","R0402","Yageo","RC0402FR-0710KL","Yageo","RC0402FR-0710KL"
+            "GerberX3","10K","R0402","","","RES_MFR1","RES1"
+        "#})?;
+
+        // and
+        let expected_result: Vec<PartMapping> = vec![
+            PartMapping { part: &parts[0], criteria: vec![
+                Box::new(GenericCriteria { criteria: vec![
+                    Box::new(ExactMatchCriterion { field_name: "footprint".to_string(), field_pattern: "R0402".to_string() }),
+                    Box::new(ExactMatchCriterion { field_name: "manufacturer".to_string(), field_pattern: "Yageo".to_string() }),
+                    Box::new(ExactMatchCriterion { field_name: "mpn".to_string(), field_pattern: "RC0402FR-0710KL".to_string() }),
+                    Box::new(ExactMatchCriterion { field_name: "value".to_string(), field_pattern: "10K".to_string() }),
+                ] })
+            ], priority: None, line: Some(2) },
+            PartMapping { part: &parts[1], criteria: vec![
+                Box::new(GenericCriteria { criteria: vec![
+                    Box::new(ExactMatchCriterion { field_name: "footprint".to_string(), field_pattern: "R0402".to_string() }),
+                    Box::new(ExactMatchCriterion { field_name: "value".to_string(), field_pattern: "10K".to_string() }),
+                ] })
+            ], priority: None, line: Some(3) },
+        ];
+
+        // when
+        let result = load_part_mappings(&parts, &test_part_mappings_source, &[])?;
 
         // then
         assert_eq!(result, expected_result);
 
         Ok(())
     }
+
+    #[test]
+    pub fn eda_columns_are_criteria_when_they_have_a_value() -> anyhow::Result<()>{
+        // given
+        let parts: Vec<Part> = vec![Part{ manufacturer: "RES_MFR1".to_string(), mpn: "RES1".to_string() }];
+
+        // and
+        let temp_dir = TempDir::new()?;
+        let mut test_part_mappings_path = temp_dir.path().to_path_buf();
+        test_part_mappings_path.push("part-mappings.csv");
+        let test_part_mappings_source = test_part_mappings_path.to_str().unwrap().to_string();
+
+        // and 'EdaMpn' refers to the placement's 'mpn' field, 'Mpn' is the part's MPN, and 'Notes' is free text.
+        std::fs::write(test_part_mappings_path, indoc! {r#"
+            "Eda","Package","Val","EdaMpn","EdaDescription","Notes","Manufacturer","Mpn"
+            "KiCad","R_0402","10K","RC0402FR-0710KL","","bought at digikey","RES_MFR1","RES1"
+        "#})?;
+
+        // and
+        let expected_result: Vec<PartMapping> = vec![
+            PartMapping { part: parts.first().unwrap(), criteria: vec![
+                Box::new(GenericCriteria { criteria: vec![
+                    Box::new(ExactMatchCriterion { field_name: "mpn".to_string(), field_pattern: "RC0402FR-0710KL".to_string() }),
+                    Box::new(ExactMatchCriterion { field_name: "package".to_string(), field_pattern: "R_0402".to_string() }),
+                    Box::new(ExactMatchCriterion { field_name: "val".to_string(), field_pattern: "10K".to_string() }),
+                ] })
+            ], priority: None, line: Some(2) },
+        ];
+
+        // when
+        let result = load_part_mappings(&parts, &test_part_mappings_source, &[])?;
+
+        // then
+        assert_eq!(result, expected_result);
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn use_quantity_match_criterion() -> anyhow::Result<()>{
+        // given
+        let parts: Vec<Part> = vec![Part{ manufacturer: "RES_MFR1".to_string(), mpn: "RES1".to_string() }];
+
+        // and
+        let temp_dir = TempDir::new()?;
+        let mut test_part_mappings_path = temp_dir.path().to_path_buf();
+        test_part_mappings_path.push("part-mappings.csv");
+        let test_part_mappings_source = test_part_mappings_path.to_str().unwrap().to_string();
+
+        // and a bracketed value that is not a quantity is an exact match
+        std::fs::write(test_part_mappings_path, indoc! {r#"
+            "Eda","Package","Val","Manufacturer","Mpn"
+            "KiCad","R_0402","[10k]","RES_MFR1","RES1"
+            "KiCad","R_0402","[DNP]","RES_MFR1","RES1"
+        "#})?;
+
+        // and
+        let expected_result: Vec<PartMapping> = vec![
+            PartMapping { part: parts.first().unwrap(), criteria: vec![
+                Box::new(GenericCriteria { criteria: vec![
+                    Box::new(ExactMatchCriterion { field_name: "package".to_string(), field_pattern: "R_0402".to_string() }),
+                    Box::new(QuantityMatchCriterion { field_name: "val".to_string(), quantity: Quantity::from_str("10000")? }),
+                ] })
+            ], priority: None, line: Some(2) },
+            PartMapping { part: parts.first().unwrap(), criteria: vec![
+                Box::new(GenericCriteria { criteria: vec![
+                    Box::new(ExactMatchCriterion { field_name: "package".to_string(), field_pattern: "R_0402".to_string() }),
+                    Box::new(ExactMatchCriterion { field_name: "val".to_string(), field_pattern: "[DNP]".to_string() }),
+                ] })
+            ], priority: None, line: Some(3) },
+        ];
+
+        // when
+        let result = load_part_mappings(&parts, &test_part_mappings_source, &[])?;
+
+        // then
+        assert_eq!(result, expected_result);
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn use_any_all_and_not_criteria() -> anyhow::Result<()>{
+        // given
+        let parts: Vec<Part> = vec![Part{ manufacturer: "RES_MFR1".to_string(), mpn: "RES1".to_string() }];
+
+        // and
+        let temp_dir = TempDir::new()?;
+        let mut test_part_mappings_path = temp_dir.path().to_path_buf();
+        test_part_mappings_path.push("part-mappings.csv");
+        let test_part_mappings_source = test_part_mappings_path.to_str().unwrap().to_string();
+
+        std::fs::write(test_part_mappings_path, indoc! {r#"
+            "Eda","Name","Value","Manufacturer","Mpn"
+            "DipTrace","all(/^RES_(0402|0603)$/, not(RES_0603))","any(10k, [9.9k..10.1k])","RES_MFR1","RES1"
+        "#})?;
+
+        // and
+        let expected_result: Vec<PartMapping> = vec![
+            PartMapping { part: parts.first().unwrap(), criteria: vec![
+                Box::new(GenericCriteria { criteria: vec![
+                    Box::new(AllOfCriterion { criteria: vec![
+                        Box::new(RegexMatchCriterion { field_name: "name".to_string(), field_pattern: Regex::new("^RES_(0402|0603)$").unwrap() }),
+                        Box::new(NotCriterion { criterion: Box::new(ExactMatchCriterion { field_name: "name".to_string(), field_pattern: "RES_0603".to_string() }) }),
+                    ] }),
+                    Box::new(AnyOfCriterion { criteria: vec![
+                        Box::new(ExactMatchCriterion { field_name: "value".to_string(), field_pattern: "10k".to_string() }),
+                        Box::new(QuantityRangeCriterion { field_name: "value".to_string(), range: QuantityRange::from_str("9.9k..10.1k")? }),
+                    ] }),
+                ] })
+            ], priority: None, line: Some(2) },
+        ];
+
+        // when
+        let result = load_part_mappings(&parts, &test_part_mappings_source, &[])?;
+
+        // then
+        assert_eq!(result, expected_result);
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn priority_column() -> anyhow::Result<()>{
+        // given
+        let parts: Vec<Part> = vec![
+            Part{ manufacturer: "RES_MFR1".to_string(), mpn: "RES1".to_string() },
+            Part{ manufacturer: "RES_MFR2".to_string(), mpn: "RES2".to_string() },
+        ];
+
+        // and
+        let temp_dir = TempDir::new()?;
+        let mut test_part_mappings_path = temp_dir.path().to_path_buf();
+        test_part_mappings_path.push("part-mappings.csv");
+        let test_part_mappings_source = test_part_mappings_path.to_str().unwrap().to_string();
+
+        std::fs::write(test_part_mappings_path, indoc! {r#"
+            "Eda","Name","Value","Manufacturer","Mpn","Priority"
+            "DipTrace","RES_0402","10k","RES_MFR1","RES1","1"
+            "DipTrace","RES_0402","10k","RES_MFR2","RES2",""
+        "#})?;
+
+        // and
+        let expected_result: Vec<PartMapping> = vec![
+            PartMapping { part: &parts[0], criteria: vec![
+                Box::new(GenericCriteria { criteria: vec![
+                    Box::new(ExactMatchCriterion { field_name: "name".to_string(), field_pattern: "RES_0402".to_string() }),
+                    Box::new(ExactMatchCriterion { field_name: "value".to_string(), field_pattern: "10k".to_string() }),
+                ] })
+            ], priority: Some(1), line: Some(2) },
+            PartMapping { part: &parts[1], criteria: vec![
+                Box::new(GenericCriteria { criteria: vec![
+                    Box::new(ExactMatchCriterion { field_name: "name".to_string(), field_pattern: "RES_0402".to_string() }),
+                    Box::new(ExactMatchCriterion { field_name: "value".to_string(), field_pattern: "10k".to_string() }),
+                ] })
+            ], priority: None, line: Some(3) },
+        ];
+
+        // when
+        let result = load_part_mappings(&parts, &test_part_mappings_source, &[])?;
+
+        // then
+        assert_eq!(result, expected_result);
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn store_fragment_for_placements() -> anyhow::Result<()>{
+        // given
+        let parts = [Part{ manufacturer: "RES_MFR1".to_string(), mpn: "RES1".to_string() }];
+
+        // and two placements with the same fields, and another with a suggested part
+        let eda_placements = [
+            EdaPlacement { ref_des: "C1".to_string(), fields: vec![
+                EdaPlacementField::new("name".to_string(), "CAP_0402".to_string()),
+                EdaPlacementField::new("value".to_string(), "100nF".to_string()),
+            ], ..EdaPlacement::default() },
+            EdaPlacement { ref_des: "C2".to_string(), fields: vec![
+                EdaPlacementField::new("name".to_string(), "CAP_0402".to_string()),
+                EdaPlacementField::new("value".to_string(), "100nF".to_string()),
+            ], ..EdaPlacement::default() },
+            EdaPlacement { ref_des: "R1".to_string(), fields: vec![
+                EdaPlacementField::new("name".to_string(), "RES_0603".to_string()),
+                EdaPlacementField::new("value".to_string(), "10K".to_string()),
+                EdaPlacementField::new("description".to_string(), "Resistor".to_string()),
+            ], ..EdaPlacement::default() },
+        ];
+
+        // and
+        let temp_dir = TempDir::new()?;
+        let mut test_part_mappings_path = temp_dir.path().to_path_buf();
+        test_part_mappings_path.push("part-mappings.csv");
+
+        // and
+        let expected_content = indoc! {r#"
+            "Eda","Name","Value","Manufacturer","Mpn"
+            "DipTrace","CAP_0402","100nF","",""
+            "DipTrace","RES_0603","10K","RES_MFR1","RES1"
+        "#};
+
+        // when
+        store_part_mappings_fragment(&test_part_mappings_path, &EdaTool::DipTrace, &[
+            (&eda_placements[0], None),
+            (&eda_placements[1], None),
+            (&eda_placements[2], Some(&parts[0])),
+        ])?;
+
+        // then
+        let content = std::fs::read_to_string(test_part_mappings_path)?;
+        assert_eq!(content, expected_content);
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn store_fragment_for_gerber_x3_placements() -> anyhow::Result<()>{
+        // given
+        let parts = [Part{ manufacturer: "RES_MFR1".to_string(), mpn: "RES1".to_string() }];
+
+        // and a placement without a manufacturer and mpn, and another with them and a suggested part
+        let eda_placements = [
+            EdaPlacement { ref_des: "C1".to_string(), fields: vec![
+                EdaPlacementField::new("value".to_string(), "100nF".to_string()),
+                EdaPlacementField::new("footprint".to_string(), "C0402".to_string()),
+            ], ..EdaPlacement::default() },
+            EdaPlacement { ref_des: "R1".to_string(), fields: vec![
+                EdaPlacementField::new("value".to_string(), "10K".to_string()),
+                EdaPlacementField::new("footprint".to_string(), "R0402".to_string()),
+                EdaPlacementField::new("manufacturer".to_string(), "Yageo".to_string()),
+                EdaPlacementField::new("mpn".to_string(), "RC0402FR-0710KL".to_string()),
+            ], ..EdaPlacement::default() },
+        ];
+
+        // and
+        let temp_dir = TempDir::new()?;
+        let mut test_part_mappings_path = temp_dir.path().to_path_buf();
+        test_part_mappings_path.push("part-mappings.csv");
+
+        // and the placement's manufacturer and mpn are criteria, the suggested part is the mapping's part
+        let expected_content = indoc! {r#"
+            "Eda","Value","Footprint","EdaManufacturer","EdaMpn","Manufacturer","Mpn"
+            "GerberX3","100nF","C0402","","","",""
+            "GerberX3","10K","R0402","Yageo","RC0402FR-0710KL","RES_MFR1","RES1"
+        "#};
+
+        // when
+        store_part_mappings_fragment(&test_part_mappings_path, &EdaTool::GerberX3, &[
+            (&eda_placements[0], None),
+            (&eda_placements[1], Some(&parts[0])),
+        ])?;
+
+        // then
+        let content = std::fs::read_to_string(test_part_mappings_path)?;
+        assert_eq!(content, expected_content);
+
+        Ok(())
+    }
 }
 
 // FUTURE Ideally we want to include this module ONLY for integration tests or for unit tests
@@ -171,11 +537,19 @@ pub mod test {
         #[serde(skip_serializing_if = "Option::is_none")]
         pub val: Option<String>,
 
+        // Altium and EasyEDA specific
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub comment: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub footprint: Option<String>,
+
         //
         // To
         //
         pub manufacturer: String,
         pub mpn: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub priority: Option<String>,
     }
 
     impl TestPartMappingRecord {
@@ -192,5 +566,19 @@ pub mod test {
                 ..Default::default()
             }
         }
+
+        pub fn altium_defaults() -> TestPartMappingRecord {
+            TestPartMappingRecord {
+                eda: "Altium".to_string(),
+                ..Default::default()
+            }
+        }
+
+        pub fn eagle_defaults() -> TestPartMappingRecord {
+            TestPartMappingRecord {
+                eda: "Eagle".to_string(),
+                ..Default::default()
+            }
+        }
     }
 }
@@ -1,19 +1,28 @@
 use tracing::Level;
 use anyhow::{Context, Error};
 use std::path::PathBuf;
-use tracing::trace;
+use tracing::{trace, warn};
 use pnp::part::Part;
+use pnp::part_catalog::PartCatalog;
 use crate::csv::PartRecord;
 
 #[tracing::instrument(level = Level::DEBUG)]
 pub fn load_parts(parts_source: &String) -> Result<Vec<Part>, Error> {
+    let part_catalog = load_part_catalog(parts_source)?;
+
+    Ok(part_catalog.parts().to_vec())
+}
+
+/// Loads the parts and their attributes, a parts file with just the `Manufacturer` and `Mpn` columns is also supported.
+#[tracing::instrument(level = Level::DEBUG)]
+pub fn load_part_catalog(parts_source: &String) -> Result<PartCatalog, Error> {
     let parts_path_buf = PathBuf::from(parts_source);
     let parts_path = parts_path_buf.as_path();
     let mut csv_reader = csv::ReaderBuilder::new()
         .from_path(parts_path)
         .with_context(|| format!("Error reading parts. file: {}", parts_path.to_str().unwrap()))?;
 
-    let mut parts: Vec<Part> = vec![];
+    let mut part_catalog = PartCatalog::default();
 
     for result in csv_reader.deserialize() {
         let record: PartRecord = result
@@ -23,8 +32,183 @@ pub fn load_parts(parts_source: &String) -> Result<Vec<Part>, Error> {
 
         let part = record.build_part()
             .with_context(|| format!("Building part from record. record: {:?}", record))?;
+        let attributes = record.build_part_attributes()
+            .with_context(|| format!("Building part attributes from record. record: {:?}", record))?;
+
+        if part_catalog.parts().contains(&part) {
+            warn!("Ignoring duplicate part. record: {:?}", record);
+        }
+
+        part_catalog.add(part, attributes)
+            .with_context(|| format!("Adding part to catalog. record: {:?}", record))?;
+    }
+    Ok(part_catalog)
+}
+
+#[cfg(test)]
+pub mod csv_loading_tests {
+    use std::str::FromStr;
+    use assert_fs::TempDir;
+    use indoc::indoc;
+    use pnp::part::Part;
+    use pnp::part_catalog::{PartAttributes, PartAttributeValue};
+    use util::quantity::Quantity;
+    use crate::parts::{load_part_catalog, load_parts};
+
+    #[test]
+    pub fn load_parts_without_attributes() -> anyhow::Result<()> {
+        // given
+        let temp_dir = TempDir::new()?;
+        let mut test_parts_path = temp_dir.path().to_path_buf();
+        test_parts_path.push("parts.csv");
+        let test_parts_source = test_parts_path.to_str().unwrap().to_string();
+
+        std::fs::write(test_parts_path, indoc! {r#"
+            "Manufacturer","Mpn"
+            "RES_MFR1","RES1"
+            "CAP_MFR1","CAP1"
+        "#})?;
+
+        // and
+        let expected_result = vec![
+            Part::new("RES_MFR1".to_string(), "RES1".to_string()),
+            Part::new("CAP_MFR1".to_string(), "CAP1".to_string()),
+        ];
+
+        // when
+        let result = load_parts(&test_parts_source)?;
+
+        // then
+        assert_eq!(result, expected_result);
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn load_parts_with_attributes() -> anyhow::Result<()> {
+        // given
+        let temp_dir = TempDir::new()?;
+        let mut test_parts_path = temp_dir.path().to_path_buf();
+        test_parts_path.push("parts.csv");
+        let test_parts_source = test_parts_path.to_str().unwrap().to_string();
+
+        std::fs::write(test_parts_path, indoc! {r#"
+            "Manufacturer","Mpn","Package","Value","Height","BodySize","PinCount","Polarity","Msl"
+            "RES_MFR1","RES1","0402","10K 1%","0.35mm","1.0x0.5mm","2","No","1"
+            "CAP_MFR1","CAP1","0603","100nF","","","2","",""
+        "#})?;
+
+        // and
+        let part1 = Part::new("RES_MFR1".to_string(), "RES1".to_string());
+        let part2 = Part::new("CAP_MFR1".to_string(), "CAP1".to_string());
+
+        let expected_part1_attributes = PartAttributes::from([
+            ("package".to_string(), PartAttributeValue::Text("0402".to_string())),
+            ("value".to_string(), PartAttributeValue::Text("10K 1%".to_string())),
+            ("height".to_string(), PartAttributeValue::Quantity(Quantity::from_str("0.35mm")?)),
+            ("body_size".to_string(), PartAttributeValue::Text("1.0x0.5mm".to_string())),
+            ("pin_count".to_string(), PartAttributeValue::Integer(2)),
+            ("polarity".to_string(), PartAttributeValue::Boolean(false)),
+            ("msl".to_string(), PartAttributeValue::Text("1".to_string())),
+        ]);
+        // and empty values are not attributes
+        let expected_part2_attributes = PartAttributes::from([
+            ("package".to_string(), PartAttributeValue::Text("0603".to_string())),
+            ("value".to_string(), PartAttributeValue::Quantity(Quantity::from_str("100nF")?)),
+            ("pin_count".to_string(), PartAttributeValue::Integer(2)),
+        ]);
+
+        // when
+        let result = load_part_catalog(&test_parts_source)?;
+
+        // then
+        assert_eq!(result.parts(), &[part1.clone(), part2.clone()]);
+        assert_eq!(result.attributes(&part1), Some(&expected_part1_attributes));
+        assert_eq!(result.attributes(&part2), Some(&expected_part2_attributes));
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn load_parts_with_invalid_attribute() -> anyhow::Result<()> {
+        // given
+        let temp_dir = TempDir::new()?;
+        let mut test_parts_path = temp_dir.path().to_path_buf();
+        test_parts_path.push("parts.csv");
+        let test_parts_source = test_parts_path.to_str().unwrap().to_string();
+
+        std::fs::write(test_parts_path, indoc! {r#"
+            "Manufacturer","Mpn","PinCount"
+            "RES_MFR1","RES1","two"
+        "#})?;
+
+        // when
+        let result = load_part_catalog(&test_parts_source);
+
+        // then
+        let error = result.unwrap_err();
+        assert_eq!(
+            format!("{:#}", error.root_cause()),
+            r#"Invalid attribute. column: "PinCount", reason: Invalid part attribute. name: "pin_count", value: "two""#
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn load_parts_with_duplicate_part() -> anyhow::Result<()> {
+        // given
+        let temp_dir = TempDir::new()?;
+        let mut test_parts_path = temp_dir.path().to_path_buf();
+        test_parts_path.push("parts.csv");
+        let test_parts_source = test_parts_path.to_str().unwrap().to_string();
+
+        std::fs::write(test_parts_path, indoc! {r#"
+            "Manufacturer","Mpn"
+            "RES_MFR1","RES1"
+            "CAP_MFR1","CAP1"
+            "RES_MFR1","RES1"
+        "#})?;
+
+        // and
+        let expected_parts = vec![
+            Part::new("RES_MFR1".to_string(), "RES1".to_string()),
+            Part::new("CAP_MFR1".to_string(), "CAP1".to_string()),
+        ];
+
+        // when
+        let parts = load_parts(&test_parts_source)?;
+
+        // then
+        assert_eq!(parts, expected_parts);
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn load_parts_with_duplicate_part_with_different_attributes() -> anyhow::Result<()> {
+        // given
+        let temp_dir = TempDir::new()?;
+        let mut test_parts_path = temp_dir.path().to_path_buf();
+        test_parts_path.push("parts.csv");
+        let test_parts_source = test_parts_path.to_str().unwrap().to_string();
+
+        std::fs::write(test_parts_path, indoc! {r#"
+            "Manufacturer","Mpn","Package"
+            "RES_MFR1","RES1","0402"
+            "RES_MFR1","RES1","0603"
+        "#})?;
+
+        // when
+        let result = load_part_catalog(&test_parts_source);
+
+        // then
+        let error = result.unwrap_err();
+        assert_eq!(
+            format!("{:#}", error.root_cause()),
+            r#"Duplicate part with different attributes. manufacturer: "RES_MFR1", mpn: "RES1""#
+        );
 
-        parts.push(part);
+        Ok(())
     }
-    Ok(parts)
-}
\ No newline at end of file
+}
@@ -20,6 +20,9 @@ pub struct PlacementRecord {
     pub x: Decimal,
     pub y: Decimal,
     pub rotation: Decimal,
+    /// Why the placement is not placed, e.g. 'do not populate'; not present in files from older versions.
+    #[serde(default)]
+    pub disable_reason: Option<String>,
 }
 
 #[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
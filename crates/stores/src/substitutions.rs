@@ -3,28 +3,36 @@ use std::path::PathBuf;
 use anyhow::{Context, Error};
 use tracing::trace;
 use eda::substitution::EdaSubstitutionRule;
+use eda::profile::EdaProfile;
 use crate::csv::SubstitutionRecord;
 
 #[tracing::instrument(level = Level::DEBUG)]
-pub fn load_eda_substitutions(substitutions_source: &String) -> Result<Vec<EdaSubstitutionRule>, Error> {
+pub fn load_eda_substitutions(substitutions_source: &String, eda_profiles: &[EdaProfile]) -> Result<Vec<EdaSubstitutionRule>, Error> {
     let substitutions_path_buf = PathBuf::from(substitutions_source);
     let substitutions_path = substitutions_path_buf.as_path();
     let mut csv_reader = csv::ReaderBuilder::new().from_path(substitutions_path)
         .with_context(|| format!("Error reading substitutions. file: {}", substitutions_path.to_str().unwrap()))?;
 
-
     let mut eda_substitutions: Vec<EdaSubstitutionRule> = vec![];
 
-    for result in csv_reader.deserialize() {
-        let record: SubstitutionRecord = result
+    let headers = csv_reader.headers()
+        .with_context(|| "Reading substitution headers".to_string())?
+        .clone();
+
+    for result in csv_reader.records() {
+        let record = result
+            .with_context(|| "Reading substitution record".to_string())?;
+        // the line the record starts on, a quoted value can span several lines.
+        let line = record.position().map(|position| position.line());
+        let record: SubstitutionRecord = record.deserialize(Some(&headers))
             .with_context(|| "Deserializing substitution record".to_string())?;
 
         trace!("{:?}", record);
 
-        let eda_substitution = record.build_eda_substitution()
+        let eda_substitution = record.build_eda_substitution(eda_profiles)
             .with_context(|| format!("Building substitution from record. record: {:?}", record))?;
 
-        eda_substitutions.push(eda_substitution);
+        eda_substitutions.push(EdaSubstitutionRule { line, ..eda_substitution });
 
     }
     Ok(eda_substitutions)
@@ -34,8 +42,12 @@ pub fn load_eda_substitutions(substitutions_source: &String) -> Result<Vec<EdaSu
 pub mod csv_loading_tests {
     use assert_fs::TempDir;
     use csv::QuoteStyle;
+    use indoc::indoc;
     use regex::Regex;
-    use criteria::{ExactMatchCriterion, RegexMatchCriterion};
+    use std::ops::Bound;
+    use std::str::FromStr;
+    use criteria::{ExactMatchCriterion, NotCriterion, QuantityRangeCriterion, RegexMatchCriterion};
+    use util::quantity::{Quantity, QuantityRange};
     use eda::substitution::{EdaSubstitutionRule, EdaSubstitutionRuleTransformItem};
     use crate::substitutions::load_eda_substitutions;
     use crate::substitutions::test::TestEdaSubstitutionRecord;
@@ -81,6 +93,8 @@ pub mod csv_loading_tests {
                     EdaSubstitutionRuleTransformItem { field_name: "name".to_string(), field_value: "SUBSTITUTED_NAME1".to_string() }, 
                     EdaSubstitutionRuleTransformItem { field_name: "value".to_string(), field_value: "SUBSTITUTED_VALUE1".to_string() }
                 ],
+                removals: vec![],
+                line: Some(2),
             },
             EdaSubstitutionRule {
                 criteria: vec![
@@ -91,6 +105,8 @@ pub mod csv_loading_tests {
                     EdaSubstitutionRuleTransformItem { field_name: "name".to_string(), field_value: "SUBSTITUTED_NAME2".to_string() },
                     EdaSubstitutionRuleTransformItem { field_name: "value".to_string(), field_value: "SUBSTITUTED_VALUE2".to_string() }
                 ],
+                removals: vec![],
+                line: Some(3),
             }
         ];
 
@@ -98,15 +114,344 @@ pub mod csv_loading_tests {
         println!("{csv_content:}");
 
         // when
-        let result = load_eda_substitutions(&test_eda_substitutions_source)?;
+        let result = load_eda_substitutions(&test_eda_substitutions_source, &[])?;
 
         // then
         assert_eq!(result, expected_result);
 
         Ok(())
     }
-}
 
+    #[test]
+    pub fn easyeda_substitution() -> anyhow::Result<()>{
+        // given
+        let temp_dir = TempDir::new()?;
+        let mut test_eda_substitutions_path = temp_dir.path().to_path_buf();
+        test_eda_substitutions_path.push("substitutions.csv");
+        let test_eda_substitutions_source = test_eda_substitutions_path.to_str().unwrap().to_string();
+
+        let mut writer = csv::WriterBuilder::new()
+            .quote_style(QuoteStyle::Always)
+            .from_path(test_eda_substitutions_path)?;
+
+        writer.serialize(TestEdaSubstitutionRecord {
+            footprint_pattern: Some("0402".to_string()),
+            comment_pattern: Some("10k".to_string()),
+            footprint: Some("R0402".to_string()),
+            comment: Some("10K 1% 0402".to_string()),
+            ..TestEdaSubstitutionRecord::easyeda_defaults()
+        })?;
+
+        writer.flush()?;
+
+        // and
+        let expected_result: Vec<EdaSubstitutionRule> = vec![
+            EdaSubstitutionRule {
+                criteria: vec![
+                    Box::new(ExactMatchCriterion { field_name: "footprint".to_string(), field_pattern: "0402".to_string() }),
+                    Box::new(ExactMatchCriterion { field_name: "comment".to_string(), field_pattern: "10k".to_string() }),
+                ],
+                transforms: vec![
+                    EdaSubstitutionRuleTransformItem { field_name: "footprint".to_string(), field_value: "R0402".to_string() },
+                    EdaSubstitutionRuleTransformItem { field_name: "comment".to_string(), field_value: "10K 1% 0402".to_string() }
+                ],
+                removals: vec![],
+                line: Some(2),
+            },
+        ];
+
+        // when
+        let result = load_eda_substitutions(&test_eda_substitutions_source, &[])?;
+
+        // then
+        assert_eq!(result, expected_result);
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn other_columns_are_substituted_when_they_have_a_pattern() -> anyhow::Result<()>{
+        // given
+        let temp_dir = TempDir::new()?;
+        let mut test_eda_substitutions_path = temp_dir.path().to_path_buf();
+        test_eda_substitutions_path.push("substitutions.csv");
+        let test_eda_substitutions_source = test_eda_substitutions_path.to_str().unwrap().to_string();
+
+        std::fs::write(test_eda_substitutions_path, indoc! {r#"
+            "Eda","NamePattern","ValuePattern","MpnPattern","DescriptionPattern","Name","Value","Mpn","Description"
+            "DipTrace","NAME1","VALUE1","MPN1","","NAME1","VALUE1","SUBSTITUTED_MPN1",""
+        "#})?;
+
+        // and
+        let expected_result: Vec<EdaSubstitutionRule> = vec![
+            EdaSubstitutionRule {
+                criteria: vec![
+                    Box::new(ExactMatchCriterion { field_name: "name".to_string(), field_pattern: "NAME1".to_string() }),
+                    Box::new(ExactMatchCriterion { field_name: "value".to_string(), field_pattern: "VALUE1".to_string() }),
+                    Box::new(ExactMatchCriterion { field_name: "mpn".to_string(), field_pattern: "MPN1".to_string() }),
+                ],
+                transforms: vec![
+                    EdaSubstitutionRuleTransformItem { field_name: "name".to_string(), field_value: "NAME1".to_string() },
+                    EdaSubstitutionRuleTransformItem { field_name: "value".to_string(), field_value: "VALUE1".to_string() },
+                    EdaSubstitutionRuleTransformItem { field_name: "mpn".to_string(), field_value: "SUBSTITUTED_MPN1".to_string() },
+                ],
+                removals: vec![],
+                line: Some(2),
+            },
+        ];
+
+        // when
+        let result = load_eda_substitutions(&test_eda_substitutions_source, &[])?;
+
+        // then
+        assert_eq!(result, expected_result);
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn use_quantity_range_criterion() -> anyhow::Result<()>{
+        // given
+        let temp_dir = TempDir::new()?;
+        let mut test_eda_substitutions_path = temp_dir.path().to_path_buf();
+        test_eda_substitutions_path.push("substitutions.csv");
+        let test_eda_substitutions_source = test_eda_substitutions_path.to_str().unwrap().to_string();
+
+        std::fs::write(test_eda_substitutions_path, indoc! {r#"
+            "Eda","NamePattern","ValuePattern","Name","Value"
+            "DipTrace","RES_0402","[10k±1%]","RES_0402","10K 1% 0402"
+        "#})?;
+
+        // and
+        let expected_range = QuantityRange {
+            min: Bound::Included(Quantity::from_str("9.9k")?),
+            max: Bound::Included(Quantity::from_str("10.1k")?),
+        };
+        let expected_result: Vec<EdaSubstitutionRule> = vec![
+            EdaSubstitutionRule {
+                criteria: vec![
+                    Box::new(ExactMatchCriterion { field_name: "name".to_string(), field_pattern: "RES_0402".to_string() }),
+                    Box::new(QuantityRangeCriterion { field_name: "value".to_string(), range: expected_range }),
+                ],
+                transforms: vec![
+                    EdaSubstitutionRuleTransformItem { field_name: "name".to_string(), field_value: "RES_0402".to_string() },
+                    EdaSubstitutionRuleTransformItem { field_name: "value".to_string(), field_value: "10K 1% 0402".to_string() },
+                ],
+                removals: vec![],
+                line: Some(2),
+            },
+        ];
+
+        // when
+        let result = load_eda_substitutions(&test_eda_substitutions_source, &[])?;
+
+        // then
+        assert_eq!(result, expected_result);
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn placement_attributes_are_only_criteria() -> anyhow::Result<()>{
+        // given
+        let temp_dir = TempDir::new()?;
+        let mut test_eda_substitutions_path = temp_dir.path().to_path_buf();
+        test_eda_substitutions_path.push("substitutions.csv");
+        let test_eda_substitutions_source = test_eda_substitutions_path.to_str().unwrap().to_string();
+
+        std::fs::write(test_eda_substitutions_path, indoc! {r#"
+            "Eda","NamePattern","ValuePattern","RefDesPattern","PcbSidePattern","Name","Value"
+            "DipTrace","CAP_0402","100nF","/^C/","bottom","CAP_0402","100nF 16V"
+        "#})?;
+
+        // and
+        let expected_result: Vec<EdaSubstitutionRule> = vec![
+            EdaSubstitutionRule {
+                criteria: vec![
+                    Box::new(ExactMatchCriterion { field_name: "name".to_string(), field_pattern: "CAP_0402".to_string() }),
+                    Box::new(ExactMatchCriterion { field_name: "value".to_string(), field_pattern: "100nF".to_string() }),
+                    Box::new(ExactMatchCriterion { field_name: "pcb_side".to_string(), field_pattern: "bottom".to_string() }),
+                    Box::new(RegexMatchCriterion { field_name: "ref_des".to_string(), field_pattern: Regex::new("^C").unwrap() }),
+                ],
+                transforms: vec![
+                    EdaSubstitutionRuleTransformItem { field_name: "name".to_string(), field_value: "CAP_0402".to_string() },
+                    EdaSubstitutionRuleTransformItem { field_name: "value".to_string(), field_value: "100nF 16V".to_string() },
+                ],
+                removals: vec![],
+                line: Some(2),
+            },
+        ];
+
+        // when
+        let result = load_eda_substitutions(&test_eda_substitutions_source, &[])?;
+
+        // then
+        assert_eq!(result, expected_result);
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn set_and_remove_fields() -> anyhow::Result<()>{
+        // given
+        let temp_dir = TempDir::new()?;
+        let mut test_eda_substitutions_path = temp_dir.path().to_path_buf();
+        test_eda_substitutions_path.push("substitutions.csv");
+        let test_eda_substitutions_source = test_eda_substitutions_path.to_str().unwrap().to_string();
+
+        std::fs::write(test_eda_substitutions_path, indoc! {r#"
+            "Eda","NamePattern","ValuePattern","Name","Value","SetMpn","SetManufacturer","RemoveFields"
+            "DipTrace","RES_0402","/^(10k)$/","RES_0402","10k","RC0402FR-07${1}L","Yageo","Description, InternalNotes"
+            "DipTrace","CAP_0402","100nF","CAP_0402","100nF","","",""
+        "#})?;
+
+        // and
+        let expected_result: Vec<EdaSubstitutionRule> = vec![
+            EdaSubstitutionRule {
+                criteria: vec![
+                    Box::new(ExactMatchCriterion { field_name: "name".to_string(), field_pattern: "RES_0402".to_string() }),
+                    Box::new(RegexMatchCriterion { field_name: "value".to_string(), field_pattern: Regex::new("^(10k)$").unwrap() }),
+                ],
+                transforms: vec![
+                    EdaSubstitutionRuleTransformItem { field_name: "name".to_string(), field_value: "RES_0402".to_string() },
+                    EdaSubstitutionRuleTransformItem { field_name: "value".to_string(), field_value: "10k".to_string() },
+                    EdaSubstitutionRuleTransformItem { field_name: "manufacturer".to_string(), field_value: "Yageo".to_string() },
+                    EdaSubstitutionRuleTransformItem { field_name: "mpn".to_string(), field_value: "RC0402FR-07${1}L".to_string() },
+                ],
+                removals: vec!["description".to_string(), "internal_notes".to_string()],
+                line: Some(2),
+            },
+            EdaSubstitutionRule {
+                criteria: vec![
+                    Box::new(ExactMatchCriterion { field_name: "name".to_string(), field_pattern: "CAP_0402".to_string() }),
+                    Box::new(ExactMatchCriterion { field_name: "value".to_string(), field_pattern: "100nF".to_string() }),
+                ],
+                transforms: vec![
+                    EdaSubstitutionRuleTransformItem { field_name: "name".to_string(), field_value: "CAP_0402".to_string() },
+                    EdaSubstitutionRuleTransformItem { field_name: "value".to_string(), field_value: "100nF".to_string() },
+                ],
+                removals: vec![],
+                line: Some(3),
+            },
+        ];
+
+        // when
+        let result = load_eda_substitutions(&test_eda_substitutions_source, &[])?;
+
+        // then
+        assert_eq!(result, expected_result);
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn placement_attributes_cannot_be_set() -> anyhow::Result<()>{
+        // given
+        let temp_dir = TempDir::new()?;
+        let mut test_eda_substitutions_path = temp_dir.path().to_path_buf();
+        test_eda_substitutions_path.push("substitutions.csv");
+        let test_eda_substitutions_source = test_eda_substitutions_path.to_str().unwrap().to_string();
+
+        std::fs::write(test_eda_substitutions_path, indoc! {r#"
+            "Eda","NamePattern","ValuePattern","Name","Value","SetRefDes"
+            "DipTrace","RES_0402","10k","RES_0402","10k","R100"
+        "#})?;
+
+        // when
+        let result = load_eda_substitutions(&test_eda_substitutions_source, &[]);
+
+        // then
+        let error = result.unwrap_err();
+        assert_eq!(format!("{:?}", error.root_cause()), "AttributeTransform { column: \"SetRefDes\" }");
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn pcb_side_pattern_is_case_insensitive() -> anyhow::Result<()>{
+        // given
+        let temp_dir = TempDir::new()?;
+        let mut test_eda_substitutions_path = temp_dir.path().to_path_buf();
+        test_eda_substitutions_path.push("substitutions.csv");
+        let test_eda_substitutions_source = test_eda_substitutions_path.to_str().unwrap().to_string();
+
+        std::fs::write(test_eda_substitutions_path, indoc! {r#"
+            "Eda","NamePattern","ValuePattern","PcbSidePattern","Name","Value"
+            "DipTrace","CAP_0402","100nF","not(Top)","CAP_0402","100nF 16V"
+        "#})?;
+
+        // and
+        let expected_result: Vec<EdaSubstitutionRule> = vec![
+            EdaSubstitutionRule {
+                criteria: vec![
+                    Box::new(ExactMatchCriterion { field_name: "name".to_string(), field_pattern: "CAP_0402".to_string() }),
+                    Box::new(ExactMatchCriterion { field_name: "value".to_string(), field_pattern: "100nF".to_string() }),
+                    Box::new(NotCriterion { criterion: Box::new(ExactMatchCriterion { field_name: "pcb_side".to_string(), field_pattern: "top".to_string() }) }),
+                ],
+                transforms: vec![
+                    EdaSubstitutionRuleTransformItem { field_name: "name".to_string(), field_value: "CAP_0402".to_string() },
+                    EdaSubstitutionRuleTransformItem { field_name: "value".to_string(), field_value: "100nF 16V".to_string() },
+                ],
+                removals: vec![],
+                line: Some(2),
+            },
+        ];
+
+        // when
+        let result = load_eda_substitutions(&test_eda_substitutions_source, &[])?;
+
+        // then
+        assert_eq!(result, expected_result);
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn unknown_pcb_side_pattern() -> anyhow::Result<()>{
+        // given
+        let temp_dir = TempDir::new()?;
+        let mut test_eda_substitutions_path = temp_dir.path().to_path_buf();
+        test_eda_substitutions_path.push("substitutions.csv");
+        let test_eda_substitutions_source = test_eda_substitutions_path.to_str().unwrap().to_string();
+
+        std::fs::write(test_eda_substitutions_path, indoc! {r#"
+            "Eda","NamePattern","ValuePattern","PcbSidePattern","Name","Value"
+            "DipTrace","CAP_0402","100nF","Front","CAP_0402","100nF 16V"
+        "#})?;
+
+        // when
+        let result = load_eda_substitutions(&test_eda_substitutions_source, &[]);
+
+        // then
+        let error = result.unwrap_err();
+        assert_eq!(format!("{:?}", error.root_cause()), "InvalidPcbSide { value: \"Front\" }");
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn unknown_capture_group_reference() -> anyhow::Result<()>{
+        // given a value that refers to a group named '1x', instead of '${1}x'
+        let temp_dir = TempDir::new()?;
+        let mut test_eda_substitutions_path = temp_dir.path().to_path_buf();
+        test_eda_substitutions_path.push("substitutions.csv");
+        let test_eda_substitutions_source = test_eda_substitutions_path.to_str().unwrap().to_string();
+
+        std::fs::write(test_eda_substitutions_path, indoc! {r#"
+            "Eda","NamePattern","ValuePattern","Name","Value"
+            "DipTrace","RES_0402","/^(10k)$/","RES_0402","$1x"
+        "#})?;
+
+        // when
+        let result = load_eda_substitutions(&test_eda_substitutions_source, &[]);
+
+        // then
+        let error = result.unwrap_err();
+        assert_eq!(format!("{:?}", error.root_cause()), "UnknownCaptureGroup { field: \"value\", reference: \"1x\" }");
+
+        Ok(())
+    }
+}
 
 // FUTURE Ideally we want to include this module ONLY for integration tests or for unit tests
 //        but when compiling for integration tests, `test` is NOT defined so we cannot use
@@ -137,6 +482,16 @@ pub mod test {
         pub package: Option<String>,
         #[serde(skip_serializing_if = "Option::is_none")]
         pub val: Option<String>,
+
+        // Altium and EasyEDA specific
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub comment_pattern: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub footprint_pattern: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub comment: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub footprint: Option<String>,
     }
 
     impl TestEdaSubstitutionRecord {
@@ -153,5 +508,12 @@ pub mod test {
                 ..Default::default()
             }
         }
+
+        pub fn easyeda_defaults() -> TestEdaSubstitutionRecord {
+            TestEdaSubstitutionRecord {
+                eda: "EasyEda".to_string(),
+                ..Default::default()
+            }
+        }
     }
 }
\ No newline at end of file
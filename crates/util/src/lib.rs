@@ -1,6 +1,7 @@
 pub mod dynamic;
 pub mod assert;
 pub mod sorting;
+pub mod quantity;
 
 #[cfg(any(test, feature = "testing"))]
 pub mod test;
\ No newline at end of file
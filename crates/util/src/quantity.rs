@@ -0,0 +1,381 @@
+use std::fmt::{Display, Formatter};
+use std::ops::Bound;
+use std::str::FromStr;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use thiserror::Error;
+
+/// An electrical, or length, quantity, e.g. `10k`, `4k7`, `330R`, `100nF` or `1.2mm`.
+///
+/// The value is normalized to the base unit, e.g. `100nF` -> 0.0000001 F, so that `10k`, `10K0` and `10000` are
+/// equivalent.  The unit is `None` when the value does not specify one, e.g. `10k`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Quantity {
+    pub value: Decimal,
+    pub unit: Option<QuantityUnit>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuantityUnit {
+    Ohm,
+    Farad,
+    Henry,
+    Volt,
+    Ampere,
+    Watt,
+    Hertz,
+    Meter,
+}
+
+impl QuantityUnit {
+    fn from_symbol(symbol: &str) -> Option<Self> {
+        match symbol {
+            "Ω" | "R" | "ohm" | "ohms" | "Ohm" | "Ohms" => Some(QuantityUnit::Ohm),
+            "F" => Some(QuantityUnit::Farad),
+            "H" => Some(QuantityUnit::Henry),
+            "V" => Some(QuantityUnit::Volt),
+            "A" => Some(QuantityUnit::Ampere),
+            "W" => Some(QuantityUnit::Watt),
+            "Hz" => Some(QuantityUnit::Hertz),
+            "m" => Some(QuantityUnit::Meter),
+            _ => None,
+        }
+    }
+}
+
+impl Display for QuantityUnit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QuantityUnit::Ohm => write!(f, "Ω"),
+            QuantityUnit::Farad => write!(f, "F"),
+            QuantityUnit::Henry => write!(f, "H"),
+            QuantityUnit::Volt => write!(f, "V"),
+            QuantityUnit::Ampere => write!(f, "A"),
+            QuantityUnit::Watt => write!(f, "W"),
+            QuantityUnit::Hertz => write!(f, "Hz"),
+            QuantityUnit::Meter => write!(f, "m"),
+        }
+    }
+}
+
+impl Quantity {
+    /// The values are equal and the units are the same, a quantity without a unit is equivalent to a quantity with
+    /// any unit, e.g. `10k` is equivalent to `10kΩ`.
+    pub fn is_equivalent(&self, other: &Quantity) -> bool {
+        self.value.eq(&other.value) && self.is_unit_compatible(other)
+    }
+
+    pub fn is_unit_compatible(&self, other: &Quantity) -> bool {
+        match (self.unit, other.unit) {
+            (Some(unit), Some(other_unit)) => unit.eq(&other_unit),
+            _ => true,
+        }
+    }
+}
+
+#[derive(Error, Debug, PartialEq)]
+#[error("Invalid quantity. value: '{0}'")]
+pub struct InvalidQuantityError(pub String);
+
+/// (symbol, multiplier), in the order used for formatting.
+const PREFIXES: [(&str, Decimal); 7] = [
+    ("G", dec!(1000000000)),
+    ("M", dec!(1000000)),
+    ("k", dec!(1000)),
+    ("m", dec!(0.001)),
+    ("u", dec!(0.000001)),
+    ("n", dec!(0.000000001)),
+    ("p", dec!(0.000000000001)),
+];
+
+fn prefix_multiplier(prefix: char) -> Option<Decimal> {
+    match prefix {
+        'G' => Some(dec!(1000000000)),
+        'M' => Some(dec!(1000000)),
+        'k' | 'K' => Some(dec!(1000)),
+        'm' => Some(dec!(0.001)),
+        'u' | 'µ' | 'μ' => Some(dec!(0.000001)),
+        'n' => Some(dec!(0.000000001)),
+        'p' => Some(dec!(0.000000000001)),
+        _ => None,
+    }
+}
+
+impl FromStr for Quantity {
+    type Err = InvalidQuantityError;
+
+    /// The prefix can also be used as the decimal point, e.g. `4k7` = 4.7k, `10K0` = 10k and `4R7` = 4.7Ω.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let error = || InvalidQuantityError(value.to_string());
+
+        let trimmed = value.trim();
+        let (sign, trimmed) = match trimmed.strip_prefix('-') {
+            Some(remainder) => (Decimal::NEGATIVE_ONE, remainder),
+            None => (Decimal::ONE, trimmed.strip_prefix('+').unwrap_or(trimmed)),
+        };
+        let number_end = trimmed.find(|c: char| !(c.is_ascii_digit() || c == '.')).unwrap_or(trimmed.len());
+        let (number, remainder) = trimmed.split_at(number_end);
+        let remainder = remainder.trim_start();
+        if !number.starts_with(|c: char| c.is_ascii_digit()) {
+            return Err(error())
+        }
+
+        // 'R' is used as the decimal point for resistances, e.g. '4R7', or as the unit, e.g. '330R'.
+        let (multiplier, mut unit, remainder) = match remainder.chars().next() {
+            Some('R') => (Some(Decimal::ONE), Some(QuantityUnit::Ohm), &remainder[1..]),
+            Some(prefix) => match prefix_multiplier(prefix) {
+                Some(multiplier) => (Some(multiplier), None, &remainder[prefix.len_utf8()..]),
+                None => (None, None, remainder),
+            },
+            None => (None, None, remainder),
+        };
+
+        let fraction_end = remainder.find(|c: char| !c.is_ascii_digit()).unwrap_or(remainder.len());
+        let (fraction, remainder) = remainder.split_at(fraction_end);
+        if !fraction.is_empty() && (multiplier.is_none() || number.contains('.')) {
+            return Err(error())
+        }
+
+        let symbol = remainder.trim_start();
+        if !symbol.is_empty() {
+            match (QuantityUnit::from_symbol(symbol), unit) {
+                (Some(symbol_unit), None) => unit = Some(symbol_unit),
+                (Some(QuantityUnit::Ohm), Some(QuantityUnit::Ohm)) => (),
+                _ => return Err(error()),
+            }
+        }
+
+        let number = match fraction.is_empty() {
+            true => number.to_string(),
+            false => format!("{}.{}", number, fraction),
+        };
+        let number = Decimal::from_str(&number).map_err(|_err| error())?;
+
+        Ok(Quantity {
+            value: (sign * number * multiplier.unwrap_or(Decimal::ONE)).normalize(),
+            unit,
+        })
+    }
+}
+
+/// Uses engineering notation, e.g. `4.7k`, `100nF`.
+impl Display for Quantity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let magnitude = self.value.abs();
+        let prefixed = PREFIXES.iter().find(|(_prefix, multiplier)| magnitude >= *multiplier);
+        let (prefix, mantissa) = match prefixed {
+            Some((prefix, multiplier)) if magnitude >= dec!(1000) || magnitude < Decimal::ONE => (*prefix, self.value / multiplier),
+            _ => ("", self.value),
+        };
+
+        write!(f, "{}{}", mantissa.normalize(), prefix)?;
+        if let Some(unit) = self.unit {
+            write!(f, "{}", unit)?;
+        }
+        Ok(())
+    }
+}
+
+/// A range of quantities, e.g. `9.9k..10.1k`, `10k±1%`, `<=1.2mm` or `>100nF`.
+///
+/// A tolerance is converted to an inclusive range, `10k±1%` is `9.9k..10.1k`, and can also be absolute, e.g.
+/// `10k±100`.  `+-` can be used instead of `±`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuantityRange {
+    pub min: Bound<Quantity>,
+    pub max: Bound<Quantity>,
+}
+
+impl QuantityRange {
+    pub fn contains(&self, quantity: &Quantity) -> bool {
+        let above_min = match &self.min {
+            Bound::Included(min) => min.is_unit_compatible(quantity) && quantity.value >= min.value,
+            Bound::Excluded(min) => min.is_unit_compatible(quantity) && quantity.value > min.value,
+            Bound::Unbounded => true,
+        };
+        let below_max = match &self.max {
+            Bound::Included(max) => max.is_unit_compatible(quantity) && quantity.value <= max.value,
+            Bound::Excluded(max) => max.is_unit_compatible(quantity) && quantity.value < max.value,
+            Bound::Unbounded => true,
+        };
+
+        above_min && below_max
+    }
+}
+
+impl FromStr for QuantityRange {
+    type Err = InvalidQuantityError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let error = || InvalidQuantityError(value.to_string());
+        let parse = |value: &str| Quantity::from_str(value).map_err(|_err| error());
+
+        let trimmed = value.trim();
+
+        if let Some(max) = trimmed.strip_prefix("<=") {
+            Ok(QuantityRange { min: Bound::Unbounded, max: Bound::Included(parse(max)?) })
+        } else if let Some(max) = trimmed.strip_prefix('<') {
+            Ok(QuantityRange { min: Bound::Unbounded, max: Bound::Excluded(parse(max)?) })
+        } else if let Some(min) = trimmed.strip_prefix(">=") {
+            Ok(QuantityRange { min: Bound::Included(parse(min)?), max: Bound::Unbounded })
+        } else if let Some(min) = trimmed.strip_prefix('>') {
+            Ok(QuantityRange { min: Bound::Excluded(parse(min)?), max: Bound::Unbounded })
+        } else if let Some((min, max)) = trimmed.split_once("..") {
+            let bound = |value: &str| match value.trim().is_empty() {
+                true => Ok(Bound::Unbounded),
+                false => parse(value).map(Bound::Included),
+            };
+            match (bound(min)?, bound(max)?) {
+                (Bound::Unbounded, Bound::Unbounded) => Err(error()),
+                (min, max) => Ok(QuantityRange { min, max }),
+            }
+        } else if let Some((nominal, tolerance)) = trimmed.split_once('±').or_else(|| trimmed.split_once("+-")) {
+            let nominal = parse(nominal)?;
+            let delta = match tolerance.trim().strip_suffix('%') {
+                Some(percentage) => {
+                    let percentage = Decimal::from_str(percentage.trim()).map_err(|_err| error())?;
+                    (nominal.value * percentage / dec!(100)).abs()
+                },
+                None => {
+                    let tolerance = parse(tolerance)?;
+                    if !tolerance.is_unit_compatible(&nominal) {
+                        return Err(error())
+                    }
+                    tolerance.value
+                },
+            };
+
+            let bound = |value: Decimal| Bound::Included(Quantity { value: value.normalize(), unit: nominal.unit });
+            Ok(QuantityRange { min: bound(nominal.value - delta), max: bound(nominal.value + delta) })
+        } else {
+            Err(error())
+        }
+    }
+}
+
+impl Display for QuantityRange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match (&self.min, &self.max) {
+            (Bound::Unbounded, Bound::Included(max)) => write!(f, "<={}", max),
+            (Bound::Unbounded, Bound::Excluded(max)) => write!(f, "<{}", max),
+            (Bound::Included(min), Bound::Unbounded) => write!(f, ">={}", min),
+            (Bound::Excluded(min), Bound::Unbounded) => write!(f, ">{}", min),
+            (min, max) => {
+                let format_bound = |bound: &Bound<Quantity>| match bound {
+                    Bound::Included(quantity) | Bound::Excluded(quantity) => quantity.to_string(),
+                    Bound::Unbounded => "".to_string(),
+                };
+                write!(f, "{}..{}", format_bound(min), format_bound(max))
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod quantity_tests {
+    use std::str::FromStr;
+    use rstest::rstest;
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+    use crate::quantity::{InvalidQuantityError, Quantity, QuantityRange, QuantityUnit};
+
+    #[rstest]
+    #[case("10k", dec!(10000), None)]
+    #[case("10K0", dec!(10000), None)]
+    #[case("10000", dec!(10000), None)]
+    #[case("4k7", dec!(4700), None)]
+    #[case("4.7k", dec!(4700), None)]
+    #[case("330R", dec!(330), Some(QuantityUnit::Ohm))]
+    #[case("4R7", dec!(4.7), Some(QuantityUnit::Ohm))]
+    #[case("10kΩ", dec!(10000), Some(QuantityUnit::Ohm))]
+    #[case("100nF", dec!(0.0000001), Some(QuantityUnit::Farad))]
+    #[case("0.1uF", dec!(0.0000001), Some(QuantityUnit::Farad))]
+    #[case("0.1µF", dec!(0.0000001), Some(QuantityUnit::Farad))]
+    #[case("10 uH", dec!(0.00001), Some(QuantityUnit::Henry))]
+    #[case("6.3V", dec!(6.3), Some(QuantityUnit::Volt))]
+    #[case("1.2mm", dec!(0.0012), Some(QuantityUnit::Meter))]
+    #[case("32.768kHz", dec!(32768), Some(QuantityUnit::Hertz))]
+    #[case("-12.5", dec!(-12.5), None)]
+    fn parse(#[case] value: &str, #[case] expected_value: Decimal, #[case] expected_unit: Option<QuantityUnit>) {
+        assert_eq!(Quantity::from_str(value), Ok(Quantity { value: expected_value, unit: expected_unit }));
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("k")]
+    #[case("RES_0402")]
+    #[case("10uF 6.3V")]
+    #[case("4.7k7")]
+    #[case("10kF2")]
+    #[case("10X")]
+    fn parse_invalid(#[case] value: &str) {
+        assert_eq!(Quantity::from_str(value), Err(InvalidQuantityError(value.to_string())));
+    }
+
+    #[rstest]
+    #[case("10k", "10000", true)]
+    #[case("10k", "10kΩ", true)]
+    #[case("100nF", "0.1uF", true)]
+    #[case("100nF", "100nH", false)]
+    #[case("10k", "10k1", false)]
+    fn equivalence(#[case] value: &str, #[case] other_value: &str, #[case] expected_result: bool) {
+        // given
+        let quantity = Quantity::from_str(value).unwrap();
+        let other_quantity = Quantity::from_str(other_value).unwrap();
+
+        // expect
+        assert_eq!(quantity.is_equivalent(&other_quantity), expected_result);
+    }
+
+    #[rstest]
+    #[case("10000", "10k")]
+    #[case("4k7", "4.7k")]
+    #[case("330R", "330Ω")]
+    #[case("0.1uF", "100nF")]
+    #[case("1.2mm", "1.2mm")]
+    #[case("0", "0")]
+    fn display(#[case] value: &str, #[case] expected_result: &str) {
+        assert_eq!(Quantity::from_str(value).unwrap().to_string(), expected_result);
+    }
+
+    #[rstest]
+    #[case("9.9k..10.1k", "10k", true)]
+    #[case("9.9k..10.1k", "10.1k", true)]
+    #[case("9.9k..10.1k", "10k2", false)]
+    #[case("10k±1%", "9k9", true)]
+    #[case("10k+-1%", "9.8k", false)]
+    #[case("10k±100", "10.1k", true)]
+    #[case("<=1.2mm", "1.2mm", true)]
+    #[case("<1.2mm", "1.2mm", false)]
+    #[case("<=1.2mm", "1.2V", false)]
+    #[case(">=100nF", "0.1uF", true)]
+    #[case(">100nF", "0.1uF", false)]
+    #[case("..10k", "100", true)]
+    #[case("-50..-10", "-20.5", true)]
+    fn range_contains(#[case] range: &str, #[case] value: &str, #[case] expected_result: bool) {
+        // given
+        let range = QuantityRange::from_str(range).unwrap();
+
+        // expect
+        assert_eq!(range.contains(&Quantity::from_str(value).unwrap()), expected_result);
+    }
+
+    #[rstest]
+    #[case("9.9k..10.1k", "9.9k..10.1k")]
+    #[case("10k±1%", "9.9k..10.1k")]
+    #[case("< 1.2mm", "<1.2mm")]
+    #[case(">=100nF", ">=100nF")]
+    fn range_display(#[case] range: &str, #[case] expected_result: &str) {
+        assert_eq!(QuantityRange::from_str(range).unwrap().to_string(), expected_result);
+    }
+
+    #[rstest]
+    #[case("..")]
+    #[case("10k")]
+    #[case("<=")]
+    #[case("10kΩ±1V")]
+    #[case("10k±x%")]
+    fn range_invalid(#[case] range: &str) {
+        assert_eq!(QuantityRange::from_str(range), Err(InvalidQuantityError(range.to_string())));
+    }
+}
@@ -0,0 +1,214 @@
+use std::fs::File;
+use std::io::Write;
+use std::path::PathBuf;
+use serde::Serialize;
+use tracing::info;
+use eda::placement::EdaPlacement;
+use eda::substitution::{EdaSubstitutionResult, EdaSubstitutionRule};
+use part_mapper::{PartMappingError, PlacementPartMappingResult};
+use part_mapper::part_mapping::PartMapping;
+
+#[derive(serde::Serialize, Default, Debug)]
+pub struct CoverageReport {
+    /// Rules that did not match any placement.
+    pub unused_substitution_rules: Vec<SubstitutionRuleCoverageItem>,
+    /// Rules that matched placements, but other rules were applied to the placements instead, a rule that was applied
+    /// to some placements is also reported when it was overridden for others.
+    pub overridden_substitution_rules: Vec<SubstitutionRuleCoverageItem>,
+    /// Mappings that did not match any placement.
+    pub unused_part_mappings: Vec<PartMappingCoverageItem>,
+    /// Mappings that matched placements, but other mappings were selected for the placements instead, a mapping that was
+    /// selected for some placements is also reported when it was overridden for others.
+    pub overridden_part_mappings: Vec<PartMappingCoverageItem>,
+}
+
+#[derive(serde::Serialize, Debug)]
+pub struct SubstitutionRuleCoverageItem {
+    pub source: String,
+    /// The line of the source the rule was loaded from, see `EdaSubstitutionRule::line`.
+    pub line: Option<u64>,
+    pub rule: String,
+    /// The placements that the rule matched, empty for unused rules.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub ref_des: Vec<String>,
+}
+
+#[derive(serde::Serialize, Debug)]
+pub struct PartMappingCoverageItem {
+    pub source: String,
+    /// The line of the source the mapping was loaded from, see `PartMapping::line`.
+    pub line: Option<u64>,
+    pub manufacturer: String,
+    pub mpn: String,
+    /// The placements that the mapping matched, empty for unused mappings.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub ref_des: Vec<String>,
+}
+
+pub fn build_coverage_report(
+    eda_substitution_rules: &[EdaSubstitutionRule],
+    // the source each rule was loaded from
+    eda_substitution_rule_sources: &[String],
+    eda_substitution_results: &[EdaSubstitutionResult],
+    part_mappings: &[PartMapping],
+    part_mappings_source: &str,
+    matched_mappings: &[PlacementPartMappingResult],
+) -> CoverageReport {
+    let mut report = CoverageReport::default();
+
+    for (rule, source) in eda_substitution_rules.iter().zip(eda_substitution_rule_sources.iter()) {
+        let mut applied = false;
+        let mut overridden_ref_des = vec![];
+
+        for result in eda_substitution_results.iter() {
+            if result.chain.iter().any(|entry| std::ptr::eq(entry.rule, rule)) {
+                applied = true;
+                continue
+            }
+
+            // a rule that matches, but would not change the placement, is not overridden.
+            let overridden = substitution_states(result).iter().any(|state| {
+                rule.matches(state) && rule.apply(state).ne(state)
+            });
+            if overridden {
+                overridden_ref_des.push(result.original_placement.ref_des.clone());
+            }
+        }
+
+        let item = SubstitutionRuleCoverageItem { source: source.clone(), line: rule.line, rule: rule.format(), ref_des: overridden_ref_des };
+        match (applied, item.ref_des.is_empty()) {
+            (false, true) => report.unused_substitution_rules.push(item),
+            (_, false) => report.overridden_substitution_rules.push(item),
+            (true, true) => (),
+        }
+    }
+
+    for part_mapping in part_mappings.iter() {
+        let mut matched = false;
+        let mut overridden_ref_des = vec![];
+
+        for PlacementPartMappingResult { eda_placement, mapping_result, .. } in matched_mappings.iter() {
+            let part_mapping_results = match mapping_result {
+                Ok(part_mapping_results) => part_mapping_results,
+                Err(PartMappingError::ConflictingRules(part_mapping_results)) => part_mapping_results,
+                Err(PartMappingError::NoRulesApplied(part_mapping_results)) => part_mapping_results,
+                Err(PartMappingError::NoMappings) => continue,
+            };
+
+            let mut results_for_mapping = part_mapping_results.iter()
+                .filter(|part_mapping_result| std::ptr::eq(part_mapping_result.part_mapping, part_mapping))
+                .peekable();
+
+            if results_for_mapping.peek().is_none() {
+                continue
+            }
+            matched = true;
+
+            // unresolved placements are reported by the mapping result.
+            let selected = results_for_mapping.any(|part_mapping_result| part_mapping_result.applied_rule.is_some());
+            if mapping_result.is_ok() && !selected {
+                overridden_ref_des.push(eda_placement.ref_des.clone());
+            }
+        }
+
+        let item = PartMappingCoverageItem {
+            source: part_mappings_source.to_string(),
+            line: part_mapping.line,
+            manufacturer: part_mapping.part.manufacturer.clone(),
+            mpn: part_mapping.part.mpn.clone(),
+            ref_des: overridden_ref_des,
+        };
+        match (matched, item.ref_des.is_empty()) {
+            (false, _) => report.unused_part_mappings.push(item),
+            (true, false) => report.overridden_part_mappings.push(item),
+            (true, true) => (),
+        }
+    }
+
+    report
+}
+
+/// The original placement and the placement after each rule in the chain was applied.
+fn substitution_states(result: &EdaSubstitutionResult) -> Vec<EdaPlacement> {
+    let mut states = vec![result.original_placement.clone()];
+    for entry in result.chain.iter() {
+        let state = entry.rule.apply(states.last().unwrap());
+        states.push(state);
+    }
+    states
+}
+
+pub fn coverage_report_save(report: &CoverageReport, report_file_path: &PathBuf) -> anyhow::Result<()> {
+    let report_file = File::create(report_file_path)?;
+    let formatter = serde_json::ser::PrettyFormatter::with_indent(b"    ");
+    let mut ser = serde_json::Serializer::with_formatter(report_file, formatter);
+    report.serialize(&mut ser)?;
+
+    let mut report_file = ser.into_inner();
+    report_file.write_all(b"\n")?;
+
+    info!("Generated coverage report. path: {:?}", report_file_path);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod coverage_tests {
+    use eda::placement::EdaPlacement;
+    use part_mapper::part_mapping::PartMapping;
+    use part_mapper::{AppliedMappingRule, PartMappingResult, PlacementPartMappingResult};
+    use pnp::part::Part;
+    use crate::coverage::build_coverage_report;
+
+    #[test]
+    pub fn mapping_selected_for_one_placement_and_overridden_for_another() {
+        // given
+        let parts = [
+            Part::new("RES_MFR1".to_string(), "RES1".to_string()),
+            Part::new("RES_MFR2".to_string(), "RES2".to_string()),
+        ];
+        let part_mappings = [
+            PartMapping { line: Some(2), ..PartMapping::new(&parts[0], vec![]) },
+            PartMapping { line: Some(3), ..PartMapping::new(&parts[1], vec![]) },
+        ];
+
+        // and
+        let eda_placements = [
+            EdaPlacement { ref_des: "R1".to_string(), ..EdaPlacement::default() },
+            EdaPlacement { ref_des: "R2".to_string(), ..EdaPlacement::default() },
+        ];
+
+        // and each mapping is selected for one placement and overridden for the other
+        let matched_mappings = vec![
+            PlacementPartMappingResult {
+                eda_placement: &eda_placements[0],
+                mapping_result: Ok(vec![
+                    PartMappingResult { part_mapping: &part_mappings[0], applied_rule: Some(AppliedMappingRule::AssemblyRule) },
+                    PartMappingResult { part_mapping: &part_mappings[1], applied_rule: None },
+                ]),
+                part: Some(&parts[0]),
+            },
+            PlacementPartMappingResult {
+                eda_placement: &eda_placements[1],
+                mapping_result: Ok(vec![
+                    PartMappingResult { part_mapping: &part_mappings[0], applied_rule: None },
+                    PartMappingResult { part_mapping: &part_mappings[1], applied_rule: Some(AppliedMappingRule::AssemblyRule) },
+                ]),
+                part: Some(&parts[1]),
+            },
+        ];
+
+        // when
+        let report = build_coverage_report(&[], &[], &[], &part_mappings, "part-mappings.csv", &matched_mappings);
+
+        // then
+        let overridden: Vec<(Option<u64>, &str, Vec<String>)> = report.overridden_part_mappings.iter()
+            .map(|item| (item.line, item.mpn.as_str(), item.ref_des.clone()))
+            .collect();
+        assert_eq!(overridden, vec![
+            (Some(2), "RES1", vec!["R2".to_string()]),
+            (Some(3), "RES2", vec!["R1".to_string()]),
+        ]);
+        assert!(report.unused_part_mappings.is_empty());
+    }
+}
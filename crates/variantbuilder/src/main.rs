@@ -1,3 +1,5 @@
+use std::fs::File;
+use std::io::Write;
 use std::path::PathBuf;
 use anyhow::Error;
 use clap::{Args, Parser, Subcommand};
@@ -5,18 +7,28 @@ use clap_verbosity_flag::{InfoLevel, Verbosity};
 use csv::QuoteStyle;
 use termtree::Tree;
 use thiserror::Error;
-use tracing::{error, info, Level, trace};
+use tracing::{error, info, Level, trace, warn};
 use assembly::AssemblyVariantProcessor;
 use assembly::assembly_variant::AssemblyVariant;
 use cli;
-use cli::args::EdaToolArg;
-use eda::placement::{EdaPlacement, EdaPlacementField};
+use cli::args::{EdaToolArg, LengthUnitArg};
+use eda::placement::{EdaPlacement, EdaPlacementDisableReason, EdaPlacementField};
 use eda::substitution::{EdaSubstitutionResult, EdaSubstitutionRule, EdaSubstitutor};
 use eda::EdaTool;
-use stores::{assembly_rules, eda_placements, load_out, part_mappings, parts, substitutions};
+use eda::profile::EdaProfile;
+use eda::units::LengthUnit;
+use stores::{assembly_rules, eda_placements, eda_profiles, inventory, load_out, part_mappings, parts, substitutions};
+use serde::Serialize;
+use stores::eda_placements::{PlacementsSource, SourcePlacementUnits};
 use stores::placements::PlacementRecord;
 use stores::load_out::LoadOutSource;
 use part_mapper::{PartMapper, PartMapperError, PartMappingError, PartMappingResult, PlacementPartMappingResult};
+use part_mapper::stock::{find_stock_shortfalls, StockShortfall};
+use part_mapper::suggestion::{suggest_part_mappings, PartMappingSuggestion};
+use pnp::part::Part;
+use pnp::part_catalog::PartCatalog;
+
+mod coverage;
 
 #[derive(Parser)]
 #[command(name = "variantbuilder")]
@@ -70,13 +82,25 @@ enum Command {
         #[arg(long)]
         eda: EdaToolArg,
 
+        /// EDA profile source, required when using the 'profile' EDA tool
+        #[arg(long, value_name = "SOURCE", required_if_eq("eda", "profile"))]
+        eda_profile: Option<String>,
+
         /// Load-out source
         #[arg(long, value_name = "SOURCE")]
         load_out: Option<LoadOutSource>,
 
-        /// Placements source
+        /// Inventory source, used to select parts that are in stock
         #[arg(long, value_name = "SOURCE")]
-        placements: String,
+        inventory: Option<String>,
+
+        /// Placements source, repeat for each source, prefix a source with 'top:' or 'bottom:' to force the side of its placements
+        #[arg(long, required = true, value_name = "SOURCE")]
+        placements: Vec<PlacementsSource>,
+
+        /// Placement coordinate units, overrides units detected from the placements file headers
+        #[arg(long)]
+        units: Option<LengthUnitArg>,
 
         /// Parts source
         #[arg(long, value_name = "SOURCE")]
@@ -120,54 +144,90 @@ fn main() -> anyhow::Result<()>{
     match &opts.command.unwrap() {
         Command::Build {
             eda,
+            eda_profile,
             placements,
+            units,
             assembly_variant_args,
             parts,
             part_mappings,
             substitutions,
             load_out,
+            inventory,
             assembly_rules,
             output,
             ref_des_disable_list,
         } => {
-            let eda_tool= eda.build();
+            let eda_profile = match eda_profile {
+                Some(source) => Some(eda_profiles::load_eda_profile(source)?),
+                None => None,
+            };
+            let eda_tool = eda.build(eda_profile)?;
             let assembly_variant = assembly_variant_args.as_ref().map_or_else(|| Ok(AssemblyVariant::default()), | args | {
                 args.build_assembly_variant()
             })?;
 
-            build_assembly_variant(eda_tool, placements, assembly_variant, parts, part_mappings, substitutions, load_out, assembly_rules, output, ref_des_disable_list)?;
+            let units_override = units.as_ref().map(LengthUnit::from);
+
+            let sources = BuildSources { placements, parts, part_mappings, substitutions, load_out, inventory, assembly_rules };
+
+            build_assembly_variant(eda_tool, sources, units_override, assembly_variant, output, ref_des_disable_list)?;
         },
     }
 
     Ok(())
 }
 
+/// The sources given to the `build` command.
+#[derive(Debug)]
+struct BuildSources<'a> {
+    placements: &'a [PlacementsSource],
+    parts: &'a String,
+    part_mappings: &'a String,
+    substitutions: &'a [String],
+    load_out: &'a Option<LoadOutSource>,
+    inventory: &'a Option<String>,
+    assembly_rules: &'a Option<String>,
+}
+
 #[tracing::instrument(level = Level::DEBUG)]
 fn build_assembly_variant(
     eda_tool: EdaTool,
-    placements_source: &String,
+    sources: BuildSources,
+    units_override: Option<LengthUnit>,
     assembly_variant: AssemblyVariant,
-    parts_source: &String,
-    part_mappings_source: &String,
-    eda_substitutions_sources: &[String],
-    load_out_source: &Option<LoadOutSource>,
-    assembly_rules_source: &Option<String>,
     output: &String,
     ref_des_disable_list: &Vec<String>
 ) -> Result<(), Error> {
+    let BuildSources {
+        placements: placements_sources,
+        parts: parts_source,
+        part_mappings: part_mappings_source,
+        substitutions: eda_substitutions_sources,
+        load_out: load_out_source,
+        inventory: inventory_source,
+        assembly_rules: assembly_rules_source,
+    } = sources;
+
+    // a profile can be referred to by name in the substitution and part-mapping files, only the profile used for the
+    // placements is loaded, see `EdaProfile`.
+    let eda_profiles: Vec<EdaProfile> = match &eda_tool {
+        EdaTool::Profile(profile) => vec![profile.as_ref().clone()],
+        _ => vec![],
+    };
 
-    let mut original_eda_placements = eda_placements::load_eda_placements(eda_tool, placements_source)?;
+    let (mut original_eda_placements, placements_units) = eda_placements::load_eda_placements_from_sources(&eda_tool, placements_sources, units_override)?;
     info!("Loaded {} placements", original_eda_placements.len());
 
-    let eda_substitution_rules = eda_substitutions_sources.iter().try_fold(vec![], |mut rules, source| {
-        let source_rules = substitutions::load_eda_substitutions(source)?;
+    let (eda_substitution_rules, eda_substitution_rule_sources) = eda_substitutions_sources.iter().try_fold((vec![], vec![]), |(mut rules, mut rule_sources), source| {
+        let source_rules = substitutions::load_eda_substitutions(source, &eda_profiles)?;
         info!("Loaded {} substitution rules from {}", source_rules.len(), source);
+        rule_sources.extend(source_rules.iter().map(|_rule| source.clone()));
         rules.extend(source_rules);
 
-        Ok::<Vec<EdaSubstitutionRule>, anyhow::Error>(rules)
+        Ok::<(Vec<EdaSubstitutionRule>, Vec<String>), anyhow::Error>((rules, rule_sources))
     })?;
 
-    let eda_substitution_results = EdaSubstitutor::substitute(original_eda_placements.as_mut_slice(), eda_substitution_rules.as_slice());
+    let eda_substitution_results = EdaSubstitutor::substitute(original_eda_placements.as_mut_slice(), eda_substitution_rules.as_slice())?;
     trace!("eda_substitution_results: {:?}", eda_substitution_results);
 
     info!("disabling placements: {:?}", ref_des_disable_list);
@@ -175,14 +235,15 @@ fn build_assembly_variant(
 
     for eda_placement in eda_placements.iter_mut() {
         if ref_des_disable_list.contains(&eda_placement.ref_des) {
-            eda_placement.place = false;
+            eda_placement.disable(EdaPlacementDisableReason::DisableList);
         }
     }
 
-    let parts = parts::load_parts(parts_source)?;
-    info!("Loaded {} parts", parts.len());
+    let part_catalog = parts::load_part_catalog(parts_source)?;
+    info!("Loaded {} parts", part_catalog.len());
+    trace!("{:?}", part_catalog);
 
-    let part_mappings = part_mappings::load_part_mappings(&parts, part_mappings_source)?;
+    let part_mappings = part_mappings::load_part_mappings(part_catalog.parts(), part_mappings_source, &eda_profiles)?;
     info!("Loaded {} part mappings", part_mappings.len());
     trace!("{:?}", part_mappings);
 
@@ -192,6 +253,12 @@ fn build_assembly_variant(
     }?;
     info!("Loaded {} load-out items", load_out_items.len());
 
+    let inventory_items = match inventory_source {
+        Some(source) => inventory::load_items(source),
+        None => Ok(vec![]),
+    }?;
+    info!("Loaded {} inventory items", inventory_items.len());
+
     let assembly_rules = match assembly_rules_source {
         Some(source) => assembly_rules::load(source),
         None => Ok(vec![]),
@@ -201,6 +268,9 @@ fn build_assembly_variant(
     info!("Assembly variant: {}", assembly_variant.name);
     info!("Ref_des list: {}", assembly_variant.ref_des_list.join(", "));
 
+    let assembly_variant_name = assembly_variant.name.clone();
+    info!("Matched {} assembly rules for assembly variant", assembly_rules.iter().filter(|rule| rule.applies_to_variant(&assembly_variant_name)).count());
+
     let result = AssemblyVariantProcessor::process(&eda_placements, assembly_variant)?;
     let variant_placements = result.placements;
     let variant_placements_count = variant_placements.len();
@@ -209,7 +279,7 @@ fn build_assembly_variant(
 
     trace!("{:?}", part_mappings);
 
-    let processing_result = PartMapper::process(&variant_placements, &part_mappings, &load_out_items, &assembly_rules);
+    let processing_result = PartMapper::process(&variant_placements, &part_mappings, &load_out_items, &inventory_items, &assembly_rules, &assembly_variant_name);
 
     trace!("{:?}", processing_result);
 
@@ -218,7 +288,23 @@ fn build_assembly_variant(
         Err(PartMapperError::MappingErrors(mappings)) => mappings,
     };
 
-    let tree = build_mapping_tree(matched_mappings, eda_substitution_results);
+    let coverage_report = coverage::build_coverage_report(
+        &eda_substitution_rules,
+        &eda_substitution_rule_sources,
+        &eda_substitution_results,
+        &part_mappings,
+        part_mappings_source,
+        matched_mappings,
+    );
+    trace!("{:?}", coverage_report);
+
+    // placements that are not placed do not need mappings
+    let unmapped_placement_suggestions: Vec<(&EdaPlacement, Vec<PartMappingSuggestion>)> = matched_mappings.iter()
+        .filter(|result| result.eda_placement.place && matches!(result.mapping_result, Err(PartMappingError::NoMappings)))
+        .map(|result| (result.eda_placement, suggest_part_mappings(result.eda_placement, &part_mappings)))
+        .collect();
+
+    let tree = build_mapping_tree(matched_mappings, eda_substitution_results, &unmapped_placement_suggestions, &part_catalog);
     info!("{}", tree);
 
     match &processing_result {
@@ -228,7 +314,24 @@ fn build_assembly_variant(
         }
     }
 
+    if !inventory_items.is_empty() {
+        for StockShortfall { part, required, available, ref_des } in find_stock_shortfalls(matched_mappings, &inventory_items) {
+            warn!("Insufficient stock. manufacturer: '{}', mpn: '{}', required: {}, available: {}, ref_des: [{}]", part.manufacturer, part.mpn, required, available, ref_des.join(", "));
+        }
+    }
+
     write_output_csv(output, matched_mappings)?;
+    placement_units_save(&placements_units, &build_related_file_path(output, "placement_units", "json"))?;
+    coverage::coverage_report_save(&coverage_report, &build_related_file_path(output, "coverage", "json"))?;
+
+    let part_mappings_fragment_items: Vec<(&EdaPlacement, Option<&Part>)> = unmapped_placement_suggestions.iter()
+        .map(|(eda_placement, suggestions)| (*eda_placement, suggestions.first().map(|suggestion| suggestion.part)))
+        .collect();
+    if !part_mappings_fragment_items.is_empty() {
+        let part_mappings_fragment_path = build_related_file_path(output, "part_mappings", "csv");
+        part_mappings::store_part_mappings_fragment(&part_mappings_fragment_path, &eda_tool, &part_mappings_fragment_items)?;
+        info!("Generated part-mappings for {} unmapped placements. path: {:?}", part_mappings_fragment_items.len(), part_mappings_fragment_path);
+    }
 
     Ok(())
 }
@@ -255,6 +358,7 @@ fn write_output_csv(output_file_name: &String, matched_mappings: &Vec<PlacementP
                     x: eda_placement.x,
                     y: eda_placement.y,
                     rotation: eda_placement.rotation,
+                    disable_reason: eda_placement.disable_reason.as_ref().map(|reason| reason.to_string()),
                 };
 
                 writer.serialize(record)?;
@@ -267,7 +371,22 @@ fn write_output_csv(output_file_name: &String, matched_mappings: &Vec<PlacementP
     Ok(())
 }
 
-fn build_mapping_tree(matched_mappings: &Vec<PlacementPartMappingResult>, eda_substitution_results: Vec<EdaSubstitutionResult>) -> Tree<String> {
+/// The units of each placements source, and how they were found, since the output coordinates are always in millimeters.
+fn placement_units_save(placements_units: &[SourcePlacementUnits], placement_units_file_path: &PathBuf) -> anyhow::Result<()> {
+    let placement_units_file = File::create(placement_units_file_path)?;
+    let formatter = serde_json::ser::PrettyFormatter::with_indent(b"    ");
+    let mut ser = serde_json::Serializer::with_formatter(placement_units_file, formatter);
+    placements_units.serialize(&mut ser)?;
+
+    let mut placement_units_file = ser.into_inner();
+    placement_units_file.write_all(b"\n")?;
+
+    info!("Generated placement units. path: {:?}", placement_units_file_path);
+
+    Ok(())
+}
+
+fn build_mapping_tree(matched_mappings: &Vec<PlacementPartMappingResult>, eda_substitution_results: Vec<EdaSubstitutionResult>, unmapped_placement_suggestions: &[(&EdaPlacement, Vec<PartMappingSuggestion>)], part_catalog: &PartCatalog) -> Tree<String> {
     let mut tree = Tree::new("Mapping Result".to_string());
 
     for PlacementPartMappingResult { eda_placement, mapping_result: part_mappings_result, .. } in matched_mappings.iter() {
@@ -280,16 +399,23 @@ fn build_mapping_tree(matched_mappings: &Vec<PlacementPartMappingResult>, eda_su
         if let Some(substitution_result) = eda_substitution_results.iter().find(|candidate|{
             candidate.original_placement.ref_des.eq(&eda_placement.ref_des)
         }) {
-            let placement_label = format!("{} ({})", eda_placement.ref_des, EdaPlacementTreeFormatter::format(&substitution_result.original_placement.fields.as_slice()));
+            let mut placement_label = format!("{} ({})", eda_placement.ref_des, EdaPlacementTreeFormatter::format(&substitution_result.original_placement.fields.as_slice()));
+            if let Some(reason) = &eda_placement.disable_reason {
+                placement_label.push_str(&format!(" [Disabled: {}]", reason));
+            }
             let mut placement_node = Tree::new(placement_label);
 
             let mut parent = &mut placement_node;
 
+            // the placement each rule of the chain was applied to, so that capture group references can be expanded.
+            let mut substituted_placement = substitution_result.original_placement.clone();
+
             for chain_entry in substitution_result.chain.iter() {
                 let substitution_label = format!("Substituted ({}), by ({})",
-                     chain_entry.rule.format_transform(),
+                     chain_entry.rule.format_applied_transform(&substituted_placement),
                      chain_entry.rule.format_criteria(),
                 );
+                substituted_placement = chain_entry.rule.apply(&substituted_placement);
 
                 let substitution_node = Tree::new(substitution_label);
                 parent.leaves.push(substitution_node);
@@ -298,18 +424,24 @@ fn build_mapping_tree(matched_mappings: &Vec<PlacementPartMappingResult>, eda_su
 
             match part_mappings_result {
                 Ok(part_mapping_results) => {
-                    add_mapping_nodes(part_mapping_results, parent);
+                    add_mapping_nodes(part_mapping_results, parent, part_catalog);
                 }
                 Err(PartMappingError::ConflictingRules(part_mapping_results)) => {
-                    add_mapping_nodes(part_mapping_results, parent);
+                    add_mapping_nodes(part_mapping_results, parent, part_catalog);
                     add_error_node(parent, "Conflicting rules");
                 },
                 Err(PartMappingError::NoRulesApplied(part_mapping_results)) => {
-                    add_mapping_nodes(part_mapping_results, parent);
+                    add_mapping_nodes(part_mapping_results, parent, part_catalog);
                     add_error_node(parent, "No rules applied");
                 },
                 Err(PartMappingError::NoMappings) => {
                     add_error_node(parent, "No mappings found");
+
+                    let maybe_suggestions = unmapped_placement_suggestions.iter()
+                        .find(|(candidate, _suggestions)| std::ptr::eq(*candidate, *eda_placement));
+                    if let Some((_eda_placement, suggestions)) = maybe_suggestions {
+                        add_suggestion_nodes(suggestions, parent.leaves.last_mut().unwrap(), part_catalog);
+                    }
                 },
             }
 
@@ -321,9 +453,9 @@ fn build_mapping_tree(matched_mappings: &Vec<PlacementPartMappingResult>, eda_su
     tree
 }
 
-fn add_mapping_nodes(part_mapping_results: &Vec<PartMappingResult>, placement_node: &mut Tree<String>) {
+fn add_mapping_nodes(part_mapping_results: &Vec<PartMappingResult>, placement_node: &mut Tree<String>, part_catalog: &PartCatalog) {
     for PartMappingResult { part_mapping, applied_rule } in part_mapping_results.iter() {
-        let part_chunk = format!("manufacturer: '{}', mpn: '{}'", part_mapping.part.manufacturer, part_mapping.part.mpn);
+        let part_chunk = format_part(part_mapping.part, part_catalog);
         let mut chunks = vec![part_chunk];
 
         if let Some(rule) = applied_rule {
@@ -338,6 +470,32 @@ fn add_mapping_nodes(part_mapping_results: &Vec<PartMappingResult>, placement_no
     }
 }
 
+fn add_suggestion_nodes(suggestions: &[PartMappingSuggestion], error_node: &mut Tree<String>, part_catalog: &PartCatalog) {
+    for PartMappingSuggestion { part, similarity } in suggestions.iter() {
+        let suggestion_label = format!("Suggestion: {} (similarity: {:.0}%)", format_part(part, part_catalog), similarity * 100.0);
+        error_node.leaves.push(Tree::new(suggestion_label));
+    }
+}
+
+/// e.g. `manufacturer: 'RES_MFR1', mpn: 'RES1', package: '0402'`, the part's attributes are included so that the
+/// package or value of a mapped or suggested part can be checked against the placement.
+fn format_part(part: &Part, part_catalog: &PartCatalog) -> String {
+    let mut chunks = vec![format!("manufacturer: '{}', mpn: '{}'", part.manufacturer, part.mpn)];
+
+    if let Some(attributes) = part_catalog.attributes(part) {
+        chunks.extend(attributes.iter().map(|(name, value)| format!("{}: '{}'", name, value)));
+    }
+
+    chunks.join(", ")
+}
+
+/// e.g. `output.csv` -> `output_coverage.json`, in the same directory as the output file.
+fn build_related_file_path(output_file_name: &String, suffix: &str, extension: &str) -> PathBuf {
+    let output_path = PathBuf::from(output_file_name);
+    let stem = output_path.file_stem().map_or_else(|| "output".to_string(), |stem| stem.to_string_lossy().to_string());
+    output_path.with_file_name(format!("{}_{}.{}", stem, suffix, extension))
+}
+
 struct EdaPlacementTreeFormatter {}
 
 impl EdaPlacementTreeFormatter {
@@ -12,6 +12,7 @@ mod tests {
     use predicates_tree::CaseTreeExt;
     use rust_decimal::Decimal;
     use rust_decimal_macros::dec;
+    use serde_json::json;
     use tempfile::tempdir;
     use stores::part_mappings::test::TestPartMappingRecord;
     use util::test::{build_temp_csv_file, build_temp_file, prepare_args, print};
@@ -376,23 +377,23 @@ mod tests {
             │   └── Substituted (name: 'HEADER_2P', value: 'BLACK'), by (name_pattern: 'HEADER_2P', value_pattern: 'POWER')
             │       └── Substituted (name: 'CONN_HEADER_2P54_2P_NS_V', value: 'BLACK'), by (name_pattern: 'HEADER_2P', value_pattern: 'BLACK')
             │           └── manufacturer: 'CONN_MFR1', mpn: 'CONN1' (Auto-selected)
-            ├── TP1 (name: '', value: '')
+            ├── TP1 (name: '', value: '') [Disabled: ref-des disable list]
             │   └── ERROR: Unresolved mapping - No mappings found.
-            └── TP2 (name: '', value: '')
+            └── TP2 (name: '', value: '') [Disabled: ref-des disable list]
                 └── ERROR: Unresolved mapping - No mappings found.
         "};
 
         // and
         let expected_csv_content = indoc! {r#"
-            "RefDes","Manufacturer","Mpn","Place","PcbSide","X","Y","Rotation"
-            "R1","RES_MFR2","RES2","true","Top","10","110","0"
-            "R3","","","true","Top","30","130","180"
-            "R4","","","true","Top","40","140","-90"
-            "D1","DIO_MFR2","DIO2","true","Top","50","150","45"
-            "C1","","","true","Top","60","160","135"
-            "J1","CONN_MFR1","CONN1","true","Top","70","170","-135"
-            "TP1","","","false","Top","80","180","-45"
-            "TP2","","","false","Top","90","190","5"
+            "RefDes","Manufacturer","Mpn","Place","PcbSide","X","Y","Rotation","DisableReason"
+            "R1","RES_MFR2","RES2","true","Top","10","110","0",""
+            "R3","","","true","Top","30","130","180",""
+            "R4","","","true","Top","40","140","-90",""
+            "D1","DIO_MFR2","DIO2","true","Top","50","150","45",""
+            "C1","","","true","Top","60","160","135",""
+            "J1","CONN_MFR1","CONN1","true","Top","70","170","-135",""
+            "TP1","","","false","Top","80","180","-45","ref-des disable list"
+            "TP2","","","false","Top","90","190","5","ref-des disable list"
         "#}.to_string();
 
         let (test_csv_output_path, test_csv_output_file_name) = build_temp_csv_file(&temp_dir, "output");
@@ -471,59 +472,863 @@ mod tests {
         // and
         let temp_dir = tempdir()?;
 
+        // and placements, with a comma in the file name
+        let (test_placements_path, test_placements_file_name) = build_temp_csv_file(&temp_dir, "placements,all-pos");
+
+        let mut writer = csv::WriterBuilder::new()
+            .quote_style(QuoteStyle::Always)
+            .from_path(test_placements_path)?;
+
+        writer.serialize(TestKiCadPlacementRecord {
+            ref_des: "R1".to_string(),
+            package: "R_0402_1005Metric".to_string(),
+            val: "330R".to_string(),
+            side: "top".to_string(),
+            x: Decimal::from(10),
+            y: Decimal::from(110),
+            rotation: dec!(-179.999),
+
+        })?;
+
+        writer.flush()?;
+
+        let placements_arg = format!("--placements {}", test_placements_file_name.to_str().unwrap());
+
+        // and global substitutions
+        let (test_global_substitutions_path, test_global_substitutions_file_name) = build_temp_csv_file(&temp_dir, "global-substitutions");
+
+        let mut writer = csv::WriterBuilder::new()
+            .quote_style(QuoteStyle::Always)
+            .from_path(test_global_substitutions_path)?;
+
+        writer.serialize(TestKiCadSubstitutionRecord {
+            eda: "KiCad".to_string(),
+            package_pattern: "R_0402_1005Metric".to_string(),
+            val_pattern: "330R".to_string(),
+            package: "R_0402_1005Metric".to_string(),
+            val: "330R 1/16W 5%".to_string(),
+        })?;
+
+        writer.flush()?;
+
+        let substitutions_arg = format!("--substitutions {}",
+            test_global_substitutions_file_name.to_str().unwrap(),
+        );
+
+        // and parts
+        let (test_parts_path, test_parts_file_name) = build_temp_csv_file(&temp_dir, "parts");
+
+        let mut writer = csv::WriterBuilder::new()
+            .quote_style(QuoteStyle::Always)
+            .from_path(test_parts_path)?;
+
+        writer.serialize(TestPartRecord {
+            manufacturer: "RES_MFR1".to_string(),
+            mpn: "RES1".to_string(),
+        })?;
+
+        writer.flush()?;
+
+        let parts_arg = format!("--parts {}", test_parts_file_name.to_str().unwrap());
+
+        // and part mappings
+        let (test_part_mappings_path, test_part_mappings_file_name) = build_temp_csv_file(&temp_dir, "part_mappings");
+
+        let mut writer = csv::WriterBuilder::new()
+            .quote_style(QuoteStyle::Always)
+            .from_path(test_part_mappings_path)?;
+
+        // and a mapping for a resistor
+        writer.serialize(TestPartMappingRecord {
+            package: Some("R_0402_1005Metric".to_string()),
+            val: Some("330R 1/16W 5%".to_string()),
+            // maps to
+            manufacturer: "RES_MFR1".to_string(),
+            mpn: "RES1".to_string(),
+            ..TestPartMappingRecord::kicad_defaults()
+        })?;
+
+        writer.flush()?;
+
+        let part_mappings_arg = format!("--part-mappings {}", test_part_mappings_file_name.to_str().unwrap());
+
+        let (test_csv_output_path, test_csv_output_file_name) = build_temp_csv_file(&temp_dir, "output");
+        let csv_output_arg = format!("--output {}", test_csv_output_file_name.to_str().unwrap());
+
+        // and
+        let (test_trace_log_path, test_trace_log_file_name) = build_temp_file(&temp_dir, "trace", "log");
+        let trace_log_arg = format!("--trace {}", test_trace_log_file_name.to_str().unwrap());
+
+        // and
+        let expected_part_mapping_tree = indoc! {"
+            Mapping Result
+            └── R1 (package: 'R_0402_1005Metric', val: '330R')
+                └── Substituted (package: 'R_0402_1005Metric', val: '330R 1/16W 5%'), by (package_pattern: 'R_0402_1005Metric', val_pattern: '330R')
+                    └── manufacturer: 'RES_MFR1', mpn: 'RES1' (Auto-selected)
+        "};
+
+        // and
+        let expected_csv_content = indoc! {r#"
+            "RefDes","Manufacturer","Mpn","Place","PcbSide","X","Y","Rotation","DisableReason"
+            "R1","RES_MFR1","RES1","true","Top","10","110","-179.999",""
+        "#}.to_string();
+
+        // when
+        cmd.args(prepare_args(vec![
+            trace_log_arg.as_str(),
+            "build",
+            "--eda kicad",
+            placements_arg.as_str(),
+            parts_arg.as_str(),
+            part_mappings_arg.as_str(),
+            csv_output_arg.as_str(),
+            substitutions_arg.as_str(),
+        ]))
+            // then
+            .assert()
+            .stderr(print("stderr"))
+            .stdout(print("stdout"))
+            .success();
+
+        // and
+        let trace_content: String = read_to_string(test_trace_log_path.clone())?;
+        println!("{}", trace_content);
+
+        let expected_substitutions_file_1_message = format!("Loaded 1 substitution rules from {}\n", test_global_substitutions_file_name.to_str().unwrap());
+
+        assert_contains_inorder!(trace_content, [
+            "Loaded 1 placements\n",
+            expected_substitutions_file_1_message.as_str(),
+            "Loaded 1 parts\n",
+            "Assembly variant: Default\n",
+            "Ref_des list: \n",
+            "Matched 1 placements for assembly variant\n",
+            expected_part_mapping_tree,
+        ]);
+
+        // and
+        let csv_output_file = assert_fs::NamedTempFile::new(test_csv_output_path).unwrap();
+        let csv_content = read_to_string(csv_output_file)?;
+        println!("{}", csv_content);
+
+        assert_csv_content(csv_content, expected_csv_content);
+
+        // and no part-mappings, since every placement is mapped
+        let (test_part_mappings_output_path, _test_part_mappings_output_file_name) = build_temp_csv_file(&temp_dir, "output_part_mappings");
+        assert!(!test_part_mappings_output_path.exists());
+
+        Ok(())
+    }
+
+    #[test]
+    fn build_altium_using_default_assembly_variant() -> Result<(), std::io::Error> {
+        let mut cmd = Command::new(env!("CARGO_BIN_EXE_variantbuilder"));
+
+        // and
+        let temp_dir = tempdir()?;
+
+        // and placements
+        let (test_placements_path, test_placements_file_name) = build_temp_csv_file(&temp_dir, "pick-place");
+
+        let placements_content = indoc! {r#"
+            Altium Designer Pick and Place Locations
+            C:\Projects\Board\Project Outputs for Board\Pick Place for Board.csv
+
+            ========================================================================================================================
+            File Design Information:
+
+            Date:       01/08/24
+            Time:       12:00
+            Revision:   Not in VersionControl
+            Variant:    No variations
+            Units used: mm
+
+            "Designator","Comment","Layer","Footprint","Center-X(mm)","Center-Y(mm)","Rotation","Description"
+            "R1","330R","TopLayer","RESC1005X40N","10.0000","110.0000","270","Resistor"
+        "#};
+        std::fs::write(test_placements_path, placements_content)?;
+
+        let placements_arg = format!("--placements {}", test_placements_file_name.to_str().unwrap());
+
+        // and global substitutions
+        let (test_global_substitutions_path, test_global_substitutions_file_name) = build_temp_csv_file(&temp_dir, "global-substitutions");
+
+        let mut writer = csv::WriterBuilder::new()
+            .quote_style(QuoteStyle::Always)
+            .from_path(test_global_substitutions_path)?;
+
+        writer.serialize(TestAltiumSubstitutionRecord {
+            eda: "Altium".to_string(),
+            comment_pattern: "330R".to_string(),
+            footprint_pattern: "RESC1005X40N".to_string(),
+            comment: "330R 1/16W 5%".to_string(),
+            footprint: "RESC1005X40N".to_string(),
+        })?;
+
+        writer.flush()?;
+
+        let substitutions_arg = format!("--substitutions {}",
+            test_global_substitutions_file_name.to_str().unwrap(),
+        );
+
+        // and parts
+        let (test_parts_path, test_parts_file_name) = build_temp_csv_file(&temp_dir, "parts");
+
+        let mut writer = csv::WriterBuilder::new()
+            .quote_style(QuoteStyle::Always)
+            .from_path(test_parts_path)?;
+
+        writer.serialize(TestPartRecord {
+            manufacturer: "RES_MFR1".to_string(),
+            mpn: "RES1".to_string(),
+        })?;
+
+        writer.flush()?;
+
+        let parts_arg = format!("--parts {}", test_parts_file_name.to_str().unwrap());
+
+        // and part mappings
+        let (test_part_mappings_path, test_part_mappings_file_name) = build_temp_csv_file(&temp_dir, "part_mappings");
+
+        let mut writer = csv::WriterBuilder::new()
+            .quote_style(QuoteStyle::Always)
+            .from_path(test_part_mappings_path)?;
+
+        // and a mapping for a resistor
+        writer.serialize(TestPartMappingRecord {
+            comment: Some("330R 1/16W 5%".to_string()),
+            footprint: Some("RESC1005X40N".to_string()),
+            // maps to
+            manufacturer: "RES_MFR1".to_string(),
+            mpn: "RES1".to_string(),
+            ..TestPartMappingRecord::altium_defaults()
+        })?;
+
+        writer.flush()?;
+
+        let part_mappings_arg = format!("--part-mappings {}", test_part_mappings_file_name.to_str().unwrap());
+
+        let (test_csv_output_path, test_csv_output_file_name) = build_temp_csv_file(&temp_dir, "output");
+        let csv_output_arg = format!("--output {}", test_csv_output_file_name.to_str().unwrap());
+
+        // and
+        let (test_trace_log_path, test_trace_log_file_name) = build_temp_file(&temp_dir, "trace", "log");
+        let trace_log_arg = format!("--trace {}", test_trace_log_file_name.to_str().unwrap());
+
+        // and
+        let expected_part_mapping_tree = indoc! {"
+            Mapping Result
+            └── R1 (comment: '330R', footprint: 'RESC1005X40N', description: 'Resistor')
+                └── Substituted (comment: '330R 1/16W 5%', footprint: 'RESC1005X40N'), by (comment_pattern: '330R', footprint_pattern: 'RESC1005X40N')
+                    └── manufacturer: 'RES_MFR1', mpn: 'RES1' (Auto-selected)
+        "};
+
+        // and
+        let expected_csv_content = indoc! {r#"
+            "RefDes","Manufacturer","Mpn","Place","PcbSide","X","Y","Rotation","DisableReason"
+            "R1","RES_MFR1","RES1","true","Top","10","110","-90",""
+        "#}.to_string();
+
+        // when
+        cmd.args(prepare_args(vec![
+            trace_log_arg.as_str(),
+            "build",
+            "--eda altium",
+            placements_arg.as_str(),
+            parts_arg.as_str(),
+            part_mappings_arg.as_str(),
+            csv_output_arg.as_str(),
+            substitutions_arg.as_str(),
+        ]))
+            // then
+            .assert()
+            .stderr(print("stderr"))
+            .stdout(print("stdout"))
+            .success();
+
+        // and
+        let trace_content: String = read_to_string(test_trace_log_path.clone())?;
+        println!("{}", trace_content);
+
+        assert_contains_inorder!(trace_content, [
+            "Placement units: mm (detected)\n",
+            "Loaded 1 placements\n",
+            "Loaded 1 parts\n",
+            "Matched 1 placements for assembly variant\n",
+            expected_part_mapping_tree,
+        ]);
+
+        // and
+        let csv_output_file = assert_fs::NamedTempFile::new(test_csv_output_path).unwrap();
+        let csv_content = read_to_string(csv_output_file)?;
+        println!("{}", csv_content);
+
+        assert_csv_content(csv_content, expected_csv_content);
+
+        // and the units of the placements file, alongside the output CSV
+        let expected_placement_units = json!([
+            { "source": test_placements_file_name.to_str().unwrap(), "units": "millimeters", "origin": "detected" },
+        ]);
+
+        let (test_placement_units_path, _test_placement_units_file_name) = build_temp_file(&temp_dir, "output_placement_units", "json");
+        let placement_units_content = read_to_string(test_placement_units_path)?;
+        println!("{}", placement_units_content);
+
+        let placement_units: serde_json::Value = serde_json::from_str(&placement_units_content)?;
+        assert_eq!(placement_units, expected_placement_units);
+
+        Ok(())
+    }
+
+    #[test]
+    fn build_eagle_using_top_and_bottom_mount_files() -> Result<(), std::io::Error> {
+        let mut cmd = Command::new(env!("CARGO_BIN_EXE_variantbuilder"));
+
+        // and
+        let temp_dir = tempdir()?;
+
+        // and placements, top and bottom
+        let (test_top_placements_path, test_top_placements_file_name) = build_temp_file(&temp_dir, "board", "mnt");
+        let (test_bottom_placements_path, _test_bottom_placements_file_name) = build_temp_file(&temp_dir, "board", "mnb");
+
+        std::fs::write(test_top_placements_path, "R1 10.00 110.00 270 330R R0402\n")?;
+        std::fs::write(test_bottom_placements_path, "C1 20.00 120.00 0 100nF C0402\n")?;
+
+        let placements_arg = format!("--placements {}", test_top_placements_file_name.to_str().unwrap());
+
+        // and parts
+        let (test_parts_path, test_parts_file_name) = build_temp_csv_file(&temp_dir, "parts");
+
+        let mut writer = csv::WriterBuilder::new()
+            .quote_style(QuoteStyle::Always)
+            .from_path(test_parts_path)?;
+
+        writer.serialize(TestPartRecord {
+            manufacturer: "RES_MFR1".to_string(),
+            mpn: "RES1".to_string(),
+        })?;
+        writer.serialize(TestPartRecord {
+            manufacturer: "CAP_MFR1".to_string(),
+            mpn: "CAP1".to_string(),
+        })?;
+
+        writer.flush()?;
+
+        let parts_arg = format!("--parts {}", test_parts_file_name.to_str().unwrap());
+
+        // and part mappings
+        let (test_part_mappings_path, test_part_mappings_file_name) = build_temp_csv_file(&temp_dir, "part_mappings");
+
+        let mut writer = csv::WriterBuilder::new()
+            .quote_style(QuoteStyle::Always)
+            .from_path(test_part_mappings_path)?;
+
+        writer.serialize(TestPartMappingRecord {
+            value: Some("330R".to_string()),
+            package: Some("R0402".to_string()),
+            // maps to
+            manufacturer: "RES_MFR1".to_string(),
+            mpn: "RES1".to_string(),
+            ..TestPartMappingRecord::eagle_defaults()
+        })?;
+        writer.serialize(TestPartMappingRecord {
+            value: Some("100nF".to_string()),
+            package: Some("C0402".to_string()),
+            // maps to
+            manufacturer: "CAP_MFR1".to_string(),
+            mpn: "CAP1".to_string(),
+            ..TestPartMappingRecord::eagle_defaults()
+        })?;
+
+        writer.flush()?;
+
+        let part_mappings_arg = format!("--part-mappings {}", test_part_mappings_file_name.to_str().unwrap());
+
+        let (test_csv_output_path, test_csv_output_file_name) = build_temp_csv_file(&temp_dir, "output");
+        let csv_output_arg = format!("--output {}", test_csv_output_file_name.to_str().unwrap());
+
+        // and
+        let (test_trace_log_path, test_trace_log_file_name) = build_temp_file(&temp_dir, "trace", "log");
+        let trace_log_arg = format!("--trace {}", test_trace_log_file_name.to_str().unwrap());
+
+        // and
+        let expected_part_mapping_tree = indoc! {"
+            Mapping Result
+            ├── R1 (value: '330R', package: 'R0402')
+            │   └── manufacturer: 'RES_MFR1', mpn: 'RES1' (Auto-selected)
+            └── C1 (value: '100nF', package: 'C0402')
+                └── manufacturer: 'CAP_MFR1', mpn: 'CAP1' (Auto-selected)
+        "};
+
+        // and
+        let expected_csv_content = indoc! {r#"
+            "RefDes","Manufacturer","Mpn","Place","PcbSide","X","Y","Rotation","DisableReason"
+            "R1","RES_MFR1","RES1","true","Top","10.00","110.00","-90",""
+            "C1","CAP_MFR1","CAP1","true","Bottom","20.00","120.00","0",""
+        "#}.to_string();
+
+        // when
+        cmd.args(prepare_args(vec![
+            trace_log_arg.as_str(),
+            "build",
+            "--eda eagle",
+            placements_arg.as_str(),
+            parts_arg.as_str(),
+            part_mappings_arg.as_str(),
+            csv_output_arg.as_str(),
+        ]))
+            // then
+            .assert()
+            .stderr(print("stderr"))
+            .stdout(print("stdout"))
+            .success();
+
+        // and
+        let trace_content: String = read_to_string(test_trace_log_path.clone())?;
+        println!("{}", trace_content);
+
+        assert_contains_inorder!(trace_content, [
+            "Loaded 2 placements\n",
+            "Matched 2 placements for assembly variant\n",
+            expected_part_mapping_tree,
+        ]);
+
+        // and
+        let csv_output_file = assert_fs::NamedTempFile::new(test_csv_output_path).unwrap();
+        let csv_content = read_to_string(csv_output_file)?;
+        println!("{}", csv_content);
+
+        assert_csv_content(csv_content, expected_csv_content);
+
+        Ok(())
+    }
+
+    #[test]
+    fn build_using_eda_profile() -> Result<(), std::io::Error> {
+        let mut cmd = Command::new(env!("CARGO_BIN_EXE_variantbuilder"));
+
+        // and
+        let temp_dir = tempdir()?;
+
+        // and profile
+        let (test_profile_path, test_profile_file_name) = build_temp_file(&temp_dir, "profile", "json");
+
+        std::fs::write(test_profile_path, indoc! {r#"
+            {
+                "name": "FabExport",
+                "delimiter": ";",
+                "columns": { "ref_des": "Ref", "x": "PosX", "y": "PosY", "rotation": "Rot", "side": "Side" },
+                "sides": { "top": ["T"], "bottom": ["B"] },
+                "rotation_direction": "clockwise",
+                "fields": [
+                    { "name": "value", "column": "Val" },
+                    { "name": "package", "column": "Package" }
+                ]
+            }
+        "#})?;
+
+        let eda_profile_arg = format!("--eda-profile {}", test_profile_file_name.to_str().unwrap());
+
         // and placements
-        let (test_placements_path, test_placements_file_name) = build_temp_csv_file(&temp_dir, "placements-all-pos");
+        let (test_placements_path, test_placements_file_name) = build_temp_csv_file(&temp_dir, "placements");
+
+        std::fs::write(test_placements_path, indoc! {"
+            Ref;Val;Package;PosX;PosY;Rot;Side
+            R1;330R;R0402;10.00;110.00;90;T
+            C1;100nF;C0402;20.00;120.00;0;B
+        "})?;
+
+        let placements_arg = format!("--placements {}", test_placements_file_name.to_str().unwrap());
+
+        // and parts
+        let (test_parts_path, test_parts_file_name) = build_temp_csv_file(&temp_dir, "parts");
+
+        let mut writer = csv::WriterBuilder::new()
+            .quote_style(QuoteStyle::Always)
+            .from_path(test_parts_path)?;
+
+        writer.serialize(TestPartRecord {
+            manufacturer: "RES_MFR1".to_string(),
+            mpn: "RES1".to_string(),
+        })?;
+        writer.serialize(TestPartRecord {
+            manufacturer: "CAP_MFR1".to_string(),
+            mpn: "CAP1".to_string(),
+        })?;
+
+        writer.flush()?;
+
+        let parts_arg = format!("--parts {}", test_parts_file_name.to_str().unwrap());
+
+        // and part mappings
+        let (test_part_mappings_path, test_part_mappings_file_name) = build_temp_csv_file(&temp_dir, "part_mappings");
+
+        let mut writer = csv::WriterBuilder::new()
+            .quote_style(QuoteStyle::Always)
+            .from_path(test_part_mappings_path)?;
+
+        writer.serialize(TestPartMappingRecord {
+            value: Some("330R".to_string()),
+            package: Some("R0402".to_string()),
+            // maps to
+            manufacturer: "RES_MFR1".to_string(),
+            mpn: "RES1".to_string(),
+            eda: "FabExport".to_string(),
+            ..Default::default()
+        })?;
+        writer.serialize(TestPartMappingRecord {
+            value: Some("100nF".to_string()),
+            package: Some("C0402".to_string()),
+            // maps to
+            manufacturer: "CAP_MFR1".to_string(),
+            mpn: "CAP1".to_string(),
+            eda: "FabExport".to_string(),
+            ..Default::default()
+        })?;
+
+        writer.flush()?;
+
+        let part_mappings_arg = format!("--part-mappings {}", test_part_mappings_file_name.to_str().unwrap());
+
+        let (test_csv_output_path, test_csv_output_file_name) = build_temp_csv_file(&temp_dir, "output");
+        let csv_output_arg = format!("--output {}", test_csv_output_file_name.to_str().unwrap());
+
+        // and
+        let (test_trace_log_path, test_trace_log_file_name) = build_temp_file(&temp_dir, "trace", "log");
+        let trace_log_arg = format!("--trace {}", test_trace_log_file_name.to_str().unwrap());
+
+        // and
+        let expected_part_mapping_tree = indoc! {"
+            Mapping Result
+            ├── R1 (value: '330R', package: 'R0402')
+            │   └── manufacturer: 'RES_MFR1', mpn: 'RES1' (Auto-selected)
+            └── C1 (value: '100nF', package: 'C0402')
+                └── manufacturer: 'CAP_MFR1', mpn: 'CAP1' (Auto-selected)
+        "};
+
+        // and
+        let expected_csv_content = indoc! {r#"
+            "RefDes","Manufacturer","Mpn","Place","PcbSide","X","Y","Rotation","DisableReason"
+            "R1","RES_MFR1","RES1","true","Top","10.00","110.00","-90",""
+            "C1","CAP_MFR1","CAP1","true","Bottom","20.00","120.00","0",""
+        "#}.to_string();
+
+        // when
+        cmd.args(prepare_args(vec![
+            trace_log_arg.as_str(),
+            "build",
+            "--eda profile",
+            eda_profile_arg.as_str(),
+            placements_arg.as_str(),
+            parts_arg.as_str(),
+            part_mappings_arg.as_str(),
+            csv_output_arg.as_str(),
+        ]))
+            // then
+            .assert()
+            .stderr(print("stderr"))
+            .stdout(print("stdout"))
+            .success();
+
+        // and
+        let trace_content: String = read_to_string(test_trace_log_path.clone())?;
+        println!("{}", trace_content);
+
+        assert_contains_inorder!(trace_content, [
+            "Loaded 2 placements\n",
+            "Matched 2 placements for assembly variant\n",
+            expected_part_mapping_tree,
+        ]);
+
+        // and
+        let csv_output_file = assert_fs::NamedTempFile::new(test_csv_output_path).unwrap();
+        let csv_content = read_to_string(csv_output_file)?;
+        println!("{}", csv_content);
+
+        assert_csv_content(csv_content, expected_csv_content);
+
+        Ok(())
+    }
+
+    #[test]
+    fn version() {
+        // given
+        let mut cmd = Command::new(env!("CARGO_BIN_EXE_variantbuilder"));
+
+        // when
+        cmd.args(["-V"])
+            // then
+            .assert()
+            .success()
+            .stderr(print("stderr"))
+            .stdout(print("stdout").and(predicate::str::diff("variantbuilder 0.1.0\n")));
+    }
+
+    #[test]
+    fn build_with_coverage_report() -> Result<(), std::io::Error> {
+        let mut cmd = Command::new(env!("CARGO_BIN_EXE_variantbuilder"));
+
+        // and
+        let temp_dir = tempdir()?;
+
+        // and placements
+        let (test_placements_path, test_placements_file_name) = build_temp_csv_file(&temp_dir, "placements");
+
+        let mut writer = csv::WriterBuilder::new()
+            .quote_style(QuoteStyle::Always)
+            .from_path(test_placements_path)?;
+
+        writer.serialize(TestDiptracePlacementRecord {
+            ref_des: "R1".to_string(),
+            name: "RES_0402".to_string(),
+            value: "330R".to_string(),
+            side: "Top".to_string(),
+            x: Decimal::from(10),
+            y: Decimal::from(110),
+            rotation: Decimal::from(0),
+        })?;
+        writer.serialize(TestDiptracePlacementRecord {
+            ref_des: "C1".to_string(),
+            name: "CAP_0402".to_string(),
+            value: "10uF 6.3V 20%".to_string(),
+            side: "Top".to_string(),
+            x: Decimal::from(20),
+            y: Decimal::from(120),
+            rotation: Decimal::from(0),
+        })?;
+        writer.serialize(TestDiptracePlacementRecord {
+            ref_des: "TP1".to_string(),
+            name: "".to_string(),
+            value: "".to_string(),
+            side: "Top".to_string(),
+            x: Decimal::from(30),
+            y: Decimal::from(130),
+            rotation: Decimal::from(0),
+        })?;
+
+        writer.flush()?;
+
+        let placements_arg = format!("--placements {}", test_placements_file_name.to_str().unwrap());
+
+        // and substitutions
+        let (test_substitutions_path, test_substitutions_file_name) = build_temp_csv_file(&temp_dir, "substitutions");
+
+        let mut writer = csv::WriterBuilder::new()
+            .quote_style(QuoteStyle::Always)
+            .from_path(test_substitutions_path)?;
+
+        writer.serialize(TestDiptraceSubstitutionRecord {
+            eda: "DipTrace".to_string(),
+            name_pattern: "RES_0402".to_string(),
+            value_pattern: "330R".to_string(),
+            name: "RES_0402".to_string(),
+            value: "330R 1/16W 5%".to_string(),
+        })?;
+        // and a substitution that does not match any placement
+        writer.serialize(TestDiptraceSubstitutionRecord {
+            eda: "DipTrace".to_string(),
+            name_pattern: "HEADER_3P".to_string(),
+            value_pattern: "POWER".to_string(),
+            name: "HEADER_3P".to_string(),
+            value: "BLACK".to_string(),
+        })?;
+
+        writer.flush()?;
+
+        let substitutions_arg = format!("--substitutions {}", test_substitutions_file_name.to_str().unwrap());
+
+        // and load-out
+        let (test_load_out_path, test_load_out_file_name) = build_temp_csv_file(&temp_dir, "load_out");
+
+        let mut writer = csv::WriterBuilder::new()
+            .quote_style(QuoteStyle::Always)
+            .from_path(test_load_out_path)?;
+
+        writer.serialize(TestLoadOutRecord {
+            reference: "FEEDER_1".to_string(),
+            manufacturer: "RES_MFR2".to_string(),
+            mpn: "RES2".to_string(),
+        })?;
+
+        writer.flush()?;
+
+        let load_out_arg = format!("--load-out {}", test_load_out_file_name.to_str().unwrap());
+
+        // and parts
+        let (test_parts_path, test_parts_file_name) = build_temp_csv_file(&temp_dir, "parts");
+
+        let mut writer = csv::WriterBuilder::new()
+            .quote_style(QuoteStyle::Always)
+            .from_path(test_parts_path)?;
+
+        writer.serialize(TestPartRecord {
+            manufacturer: "RES_MFR1".to_string(),
+            mpn: "RES1".to_string(),
+        })?;
+        writer.serialize(TestPartRecord {
+            manufacturer: "RES_MFR2".to_string(),
+            mpn: "RES2".to_string(),
+        })?;
+        writer.serialize(TestPartRecord {
+            manufacturer: "DIO_MFR1".to_string(),
+            mpn: "DIO1".to_string(),
+        })?;
+
+        writer.flush()?;
+
+        let parts_arg = format!("--parts {}", test_parts_file_name.to_str().unwrap());
+
+        // and part mappings
+        let (test_part_mappings_path, test_part_mappings_file_name) = build_temp_csv_file(&temp_dir, "part_mappings");
+
+        let mut writer = csv::WriterBuilder::new()
+            .quote_style(QuoteStyle::Always)
+            .from_path(test_part_mappings_path)?;
+
+        // and two mappings for the same resistor, the second is in the load-out
+        writer.serialize(TestPartMappingRecord {
+            name: Some("RES_0402".to_string()),
+            value: Some("330R 1/16W 5%".to_string()),
+            // maps to
+            manufacturer: "RES_MFR1".to_string(),
+            mpn: "RES1".to_string(),
+            ..TestPartMappingRecord::diptrace_defaults()
+        })?;
+        writer.serialize(TestPartMappingRecord {
+            name: Some("RES_0402".to_string()),
+            value: Some("330R 1/16W 5%".to_string()),
+            // maps to
+            manufacturer: "RES_MFR2".to_string(),
+            mpn: "RES2".to_string(),
+            ..TestPartMappingRecord::diptrace_defaults()
+        })?;
+        // and a mapping that does not match any placement
+        writer.serialize(TestPartMappingRecord {
+            name: Some("DIO_0805".to_string()),
+            value: Some("1A 10V".to_string()),
+            // maps to
+            manufacturer: "DIO_MFR1".to_string(),
+            mpn: "DIO1".to_string(),
+            ..TestPartMappingRecord::diptrace_defaults()
+        })?;
+
+        writer.flush()?;
+
+        let part_mappings_arg = format!("--part-mappings {}", test_part_mappings_file_name.to_str().unwrap());
+
+        let (_test_csv_output_path, test_csv_output_file_name) = build_temp_csv_file(&temp_dir, "output");
+        let csv_output_arg = format!("--output {}", test_csv_output_file_name.to_str().unwrap());
+
+        // and
+        let expected_coverage_report = json!({
+            "unused_substitution_rules": [
+                {
+                    "source": test_substitutions_file_name.to_str().unwrap(),
+                    "line": 3,
+                    "rule": "(name_pattern: 'HEADER_3P', value_pattern: 'POWER') -> (name: 'HEADER_3P', value: 'BLACK')",
+                },
+            ],
+            "overridden_substitution_rules": [],
+            "unused_part_mappings": [
+                {
+                    "source": test_part_mappings_file_name.to_str().unwrap(),
+                    "line": 4,
+                    "manufacturer": "DIO_MFR1",
+                    "mpn": "DIO1",
+                },
+            ],
+            "overridden_part_mappings": [
+                {
+                    "source": test_part_mappings_file_name.to_str().unwrap(),
+                    "line": 2,
+                    "manufacturer": "RES_MFR1",
+                    "mpn": "RES1",
+                    "ref_des": ["R1"],
+                },
+            ],
+        });
+
+        // and part-mappings for the unmapped placements, disabled placements are not included
+        let expected_part_mappings_content = indoc! {r#"
+            "Eda","Name","Value","Manufacturer","Mpn"
+            "DipTrace","CAP_0402","10uF 6.3V 20%","",""
+        "#}.to_string();
+
+        // when
+        cmd.args(prepare_args(vec![
+            "build",
+            "--eda diptrace",
+            placements_arg.as_str(),
+            parts_arg.as_str(),
+            part_mappings_arg.as_str(),
+            load_out_arg.as_str(),
+            substitutions_arg.as_str(),
+            csv_output_arg.as_str(),
+            "--ref-des-disable-list TP1",
+        ]))
+            // then
+            .assert()
+            .stderr(print("stderr"))
+            .stdout(print("stdout"))
+            .success();
 
-        let mut writer = csv::WriterBuilder::new()
-            .quote_style(QuoteStyle::Always)
-            .from_path(test_placements_path)?;
+        // and a coverage report, alongside the output CSV
+        let (test_coverage_report_path, _test_coverage_report_file_name) = build_temp_file(&temp_dir, "output_coverage", "json");
+        let coverage_report_content = read_to_string(test_coverage_report_path)?;
+        println!("{}", coverage_report_content);
 
-        writer.serialize(TestKiCadPlacementRecord {
-            ref_des: "R1".to_string(),
-            package: "R_0402_1005Metric".to_string(),
-            val: "330R".to_string(),
-            side: "top".to_string(),
-            x: Decimal::from(10),
-            y: Decimal::from(110),
-            rotation: dec!(-179.999),
+        let coverage_report: serde_json::Value = serde_json::from_str(&coverage_report_content)?;
+        assert_eq!(coverage_report, expected_coverage_report);
 
-        })?;
+        // and
+        let (test_part_mappings_output_path, _test_part_mappings_output_file_name) = build_temp_csv_file(&temp_dir, "output_part_mappings");
+        let part_mappings_content = read_to_string(test_part_mappings_output_path)?;
+        println!("{}", part_mappings_content);
 
-        writer.flush()?;
+        assert_csv_content(part_mappings_content, expected_part_mappings_content);
 
-        let placements_arg = format!("--placements {}", test_placements_file_name.to_str().unwrap());
+        Ok(())
+    }
 
-        // and global substitutions
-        let (test_global_substitutions_path, test_global_substitutions_file_name) = build_temp_csv_file(&temp_dir, "global-substitutions");
+    #[test]
+    fn build_with_part_attributes() -> Result<(), std::io::Error> {
+        let mut cmd = Command::new(env!("CARGO_BIN_EXE_variantbuilder"));
+
+        // and
+        let temp_dir = tempdir()?;
+
+        // and placements
+        let (test_placements_path, test_placements_file_name) = build_temp_csv_file(&temp_dir, "placements");
 
         let mut writer = csv::WriterBuilder::new()
             .quote_style(QuoteStyle::Always)
-            .from_path(test_global_substitutions_path)?;
+            .from_path(test_placements_path)?;
 
-        writer.serialize(TestKiCadSubstitutionRecord {
-            eda: "KiCad".to_string(),
-            package_pattern: "R_0402_1005Metric".to_string(),
-            val_pattern: "330R".to_string(),
-            package: "R_0402_1005Metric".to_string(),
-            val: "330R 1/16W 5%".to_string(),
+        writer.serialize(TestDiptracePlacementRecord {
+            ref_des: "J1".to_string(),
+            name: "HEADER_2P".to_string(),
+            value: "BLACK".to_string(),
+            side: "Top".to_string(),
+            x: Decimal::from(10),
+            y: Decimal::from(110),
+            rotation: Decimal::from(0),
         })?;
 
         writer.flush()?;
 
-        let substitutions_arg = format!("--substitutions {}",
-            test_global_substitutions_file_name.to_str().unwrap(),
-        );
+        let placements_arg = format!("--placements {}", test_placements_file_name.to_str().unwrap());
 
-        // and parts
+        // and parts, the package is shown alongside the mapped part
         let (test_parts_path, test_parts_file_name) = build_temp_csv_file(&temp_dir, "parts");
 
         let mut writer = csv::WriterBuilder::new()
             .quote_style(QuoteStyle::Always)
             .from_path(test_parts_path)?;
 
-        writer.serialize(TestPartRecord {
-            manufacturer: "RES_MFR1".to_string(),
-            mpn: "RES1".to_string(),
+        writer.serialize(TestPartWithPackageRecord {
+            manufacturer: "CONN_MFR1".to_string(),
+            mpn: "CONN1".to_string(),
+            package: "CONN_2P54_2P".to_string(),
         })?;
 
         writer.flush()?;
@@ -537,21 +1342,20 @@ mod tests {
             .quote_style(QuoteStyle::Always)
             .from_path(test_part_mappings_path)?;
 
-        // and a mapping for a resistor
         writer.serialize(TestPartMappingRecord {
-            package: Some("R_0402_1005Metric".to_string()),
-            val: Some("330R 1/16W 5%".to_string()),
+            name: Some("HEADER_2P".to_string()),
+            value: Some("BLACK".to_string()),
             // maps to
-            manufacturer: "RES_MFR1".to_string(),
-            mpn: "RES1".to_string(),
-            ..TestPartMappingRecord::kicad_defaults()
+            manufacturer: "CONN_MFR1".to_string(),
+            mpn: "CONN1".to_string(),
+            ..TestPartMappingRecord::diptrace_defaults()
         })?;
 
         writer.flush()?;
 
         let part_mappings_arg = format!("--part-mappings {}", test_part_mappings_file_name.to_str().unwrap());
 
-        let (test_csv_output_path, test_csv_output_file_name) = build_temp_csv_file(&temp_dir, "output");
+        let (_test_csv_output_path, test_csv_output_file_name) = build_temp_csv_file(&temp_dir, "output");
         let csv_output_arg = format!("--output {}", test_csv_output_file_name.to_str().unwrap());
 
         // and
@@ -561,27 +1365,19 @@ mod tests {
         // and
         let expected_part_mapping_tree = indoc! {"
             Mapping Result
-            └── R1 (package: 'R_0402_1005Metric', val: '330R')
-                └── Substituted (package: 'R_0402_1005Metric', val: '330R 1/16W 5%'), by (package_pattern: 'R_0402_1005Metric', val_pattern: '330R')
-                    └── manufacturer: 'RES_MFR1', mpn: 'RES1' (Auto-selected)
+            └── J1 (name: 'HEADER_2P', value: 'BLACK')
+                └── manufacturer: 'CONN_MFR1', mpn: 'CONN1', package: 'CONN_2P54_2P' (Auto-selected)
         "};
 
-        // and
-        let expected_csv_content = indoc! {r#"
-            "RefDes","Manufacturer","Mpn","Place","PcbSide","X","Y","Rotation"
-            "R1","RES_MFR1","RES1","true","Top","10","110","-179.999"
-        "#}.to_string();
-
         // when
         cmd.args(prepare_args(vec![
             trace_log_arg.as_str(),
             "build",
-            "--eda kicad",
+            "--eda diptrace",
             placements_arg.as_str(),
             parts_arg.as_str(),
             part_mappings_arg.as_str(),
             csv_output_arg.as_str(),
-            substitutions_arg.as_str(),
         ]))
             // then
             .assert()
@@ -593,40 +1389,161 @@ mod tests {
         let trace_content: String = read_to_string(test_trace_log_path.clone())?;
         println!("{}", trace_content);
 
-        let expected_substitutions_file_1_message = format!("Loaded 1 substitution rules from {}\n", test_global_substitutions_file_name.to_str().unwrap());
-
         assert_contains_inorder!(trace_content, [
-            "Loaded 1 placements\n",
-            expected_substitutions_file_1_message.as_str(),
             "Loaded 1 parts\n",
-            "Assembly variant: Default\n",
-            "Ref_des list: \n",
-            "Matched 1 placements for assembly variant\n",
             expected_part_mapping_tree,
         ]);
 
-        // and
-        let csv_output_file = assert_fs::NamedTempFile::new(test_csv_output_path).unwrap();
-        let csv_content = read_to_string(csv_output_file)?;
-        println!("{}", csv_content);
-
-        assert_csv_content(csv_content, expected_csv_content);
-
         Ok(())
     }
 
     #[test]
-    fn version() {
-        // given
+    fn build_with_assembly_rules_for_other_assembly_variants() -> Result<(), std::io::Error> {
         let mut cmd = Command::new(env!("CARGO_BIN_EXE_variantbuilder"));
 
+        // and
+        let temp_dir = tempdir()?;
+
+        // and placements
+        let (test_placements_path, test_placements_file_name) = build_temp_csv_file(&temp_dir, "placements");
+
+        let mut writer = csv::WriterBuilder::new()
+            .quote_style(QuoteStyle::Always)
+            .from_path(test_placements_path)?;
+
+        writer.serialize(TestDiptracePlacementRecord {
+            ref_des: "D1".to_string(),
+            name: "DIO_0603".to_string(),
+            value: "1A 10V".to_string(),
+            side: "Top".to_string(),
+            x: Decimal::from(10),
+            y: Decimal::from(110),
+            rotation: Decimal::from(0),
+        })?;
+
+        writer.flush()?;
+
+        let placements_arg = format!("--placements {}", test_placements_file_name.to_str().unwrap());
+
+        // and parts
+        let (test_parts_path, test_parts_file_name) = build_temp_csv_file(&temp_dir, "parts");
+
+        let mut writer = csv::WriterBuilder::new()
+            .quote_style(QuoteStyle::Always)
+            .from_path(test_parts_path)?;
+
+        writer.serialize(TestPartRecord {
+            manufacturer: "DIO_MFR1".to_string(),
+            mpn: "DIO1".to_string(),
+        })?;
+        writer.serialize(TestPartRecord {
+            manufacturer: "DIO_MFR2".to_string(),
+            mpn: "DIO2".to_string(),
+        })?;
+
+        writer.flush()?;
+
+        let parts_arg = format!("--parts {}", test_parts_file_name.to_str().unwrap());
+
+        // and two mappings for the diode
+        let (test_part_mappings_path, test_part_mappings_file_name) = build_temp_csv_file(&temp_dir, "part_mappings");
+
+        let mut writer = csv::WriterBuilder::new()
+            .quote_style(QuoteStyle::Always)
+            .from_path(test_part_mappings_path)?;
+
+        writer.serialize(TestPartMappingRecord {
+            name: Some("DIO_0603".to_string()),
+            value: Some("1A 10V".to_string()),
+            // maps to
+            manufacturer: "DIO_MFR1".to_string(),
+            mpn: "DIO1".to_string(),
+            ..TestPartMappingRecord::diptrace_defaults()
+        })?;
+        writer.serialize(TestPartMappingRecord {
+            name: Some("DIO_0603".to_string()),
+            value: Some("1A 10V".to_string()),
+            // maps to
+            manufacturer: "DIO_MFR2".to_string(),
+            mpn: "DIO2".to_string(),
+            ..TestPartMappingRecord::diptrace_defaults()
+        })?;
+
+        writer.flush()?;
+
+        let part_mappings_arg = format!("--part-mappings {}", test_part_mappings_file_name.to_str().unwrap());
+
+        // and assembly-rules, for all assembly variants and for a different assembly variant
+        let (test_assembly_rule_path, test_assembly_rule_file_name) = build_temp_csv_file(&temp_dir, "assembly_rule");
+
+        let mut writer = csv::WriterBuilder::new()
+            .quote_style(QuoteStyle::Always)
+            .from_path(test_assembly_rule_path)?;
+
+        writer.serialize(TestAssemblyVariantRuleRecord {
+            ref_des: "D1".to_string(),
+            manufacturer: "DIO_MFR2".to_string(),
+            mpn: "DIO2".to_string(),
+            assembly_variant: "".to_string(),
+        })?;
+        writer.serialize(TestAssemblyVariantRuleRecord {
+            ref_des: "D1".to_string(),
+            manufacturer: "DIO_MFR1".to_string(),
+            mpn: "DIO1".to_string(),
+            assembly_variant: "Variant_2".to_string(),
+        })?;
+
+        writer.flush()?;
+
+        let assembly_rules_arg = format!("--assembly-rules {}", test_assembly_rule_file_name.to_str().unwrap());
+
+        let (_test_csv_output_path, test_csv_output_file_name) = build_temp_csv_file(&temp_dir, "output");
+        let csv_output_arg = format!("--output {}", test_csv_output_file_name.to_str().unwrap());
+
+        // and
+        let (test_trace_log_path, test_trace_log_file_name) = build_temp_file(&temp_dir, "trace", "log");
+        let trace_log_arg = format!("--trace {}", test_trace_log_file_name.to_str().unwrap());
+
+        // and
+        let expected_part_mapping_tree = indoc! {"
+            Mapping Result
+            └── D1 (name: 'DIO_0603', value: '1A 10V')
+                ├── manufacturer: 'DIO_MFR1', mpn: 'DIO1'
+                └── manufacturer: 'DIO_MFR2', mpn: 'DIO2' (Matched assembly-rule)
+        "};
+
         // when
-        cmd.args(["-V"])
+        cmd.args(prepare_args(vec![
+            trace_log_arg.as_str(),
+            "build",
+            "--eda diptrace",
+            placements_arg.as_str(),
+            parts_arg.as_str(),
+            part_mappings_arg.as_str(),
+            assembly_rules_arg.as_str(),
+            csv_output_arg.as_str(),
+            "--name",
+            "Variant_1",
+            "--ref-des-list D1",
+        ]))
             // then
             .assert()
-            .success()
             .stderr(print("stderr"))
-            .stdout(print("stdout").and(predicate::str::diff("variantbuilder 0.1.0\n")));
+            .stdout(print("stdout"))
+            .success();
+
+        // and
+        let trace_content: String = read_to_string(test_trace_log_path.clone())?;
+        println!("{}", trace_content);
+
+        assert_contains_inorder!(trace_content, [
+            "Loaded 2 assembly rules\n",
+            "Assembly variant: Variant_1\n",
+            "Matched 1 assembly rules for assembly variant\n",
+            expected_part_mapping_tree,
+        ]);
+
+        Ok(())
     }
 
     #[derive(Debug, serde::Serialize)]
@@ -668,6 +1585,14 @@ mod tests {
         mpn: String,
     }
 
+    #[derive(Debug, serde::Serialize)]
+    #[serde(rename_all(serialize = "PascalCase"))]
+    struct TestPartWithPackageRecord {
+        manufacturer: String,
+        mpn: String,
+        package: String,
+    }
+
     #[derive(Debug, serde::Serialize)]
     #[serde(rename_all(serialize = "PascalCase"))]
     struct TestAssemblyRuleRecord {
@@ -676,6 +1601,15 @@ mod tests {
         mpn: String,
     }
 
+    #[derive(Debug, serde::Serialize)]
+    #[serde(rename_all(serialize = "PascalCase"))]
+    struct TestAssemblyVariantRuleRecord {
+        ref_des: String,
+        manufacturer: String,
+        mpn: String,
+        assembly_variant: String,
+    }
+
     #[derive(Debug, serde::Serialize)]
     #[serde(rename_all(serialize = "PascalCase"))]
     struct TestDiptraceSubstitutionRecord {
@@ -695,6 +1629,16 @@ mod tests {
         package: String,
         val: String,
     }
+
+    #[derive(Debug, serde::Serialize)]
+    #[serde(rename_all(serialize = "PascalCase"))]
+    struct TestAltiumSubstitutionRecord {
+        eda: String,
+        comment_pattern: String,
+        footprint_pattern: String,
+        comment: String,
+        footprint: String,
+    }
 }
 
 mod help {
@@ -754,17 +1698,23 @@ mod help {
 
             Options:
                   --eda <EDA>
-                      EDA tool [possible values: diptrace, kicad]
+                      EDA tool [possible values: diptrace, kicad, altium, easyeda, eagle, ipc2581, gerberx3, profile]
+                  --eda-profile <SOURCE>
+                      EDA profile source, required when using the 'profile' EDA tool
                   --load-out <SOURCE>
                       Load-out source
-                  --placements <SOURCE>
-                      Placements source
               -v, --verbose...
                       Increase logging verbosity
-                  --parts <SOURCE>
-                      Parts source
+                  --inventory <SOURCE>
+                      Inventory source, used to select parts that are in stock
               -q, --quiet...
                       Decrease logging verbosity
+                  --placements <SOURCE>
+                      Placements source, repeat for each source, prefix a source with 'top:' or 'bottom:' to force the side of its placements
+                  --units <UNITS>
+                      Placement coordinate units, overrides units detected from the placements file headers [possible values: mm, mil, in]
+                  --parts <SOURCE>
+                      Parts source
                   --part-mappings <SOURCE>
                       Part-mappings source
                   --substitutions [<SOURCE>...]